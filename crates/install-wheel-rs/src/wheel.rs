@@ -124,8 +124,9 @@ fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<
 
 /// Format the shebang for a given Python executable.
 ///
-/// Like pip, if a shebang is non-simple (too long or contains spaces), we use `/bin/sh` as the
-/// executable.
+/// Like pip, if a shebang is non-simple (too long or contains whitespace), we use `/bin/sh` as the
+/// executable. The length limit applies to bytes, so paths with non-ASCII characters may exceed it
+/// with fewer characters.
 ///
 /// See: <https://github.com/pypa/pip/blob/0ad4c94be74cc24874c6feb5bb3c2152c398a18e/src/pip/_vendor/distlib/scripts.py#L136-L165>
 fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
@@ -138,8 +139,8 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
         // newline.
         let shebang_length = 2 + executable.len() + 1;
 
-        // If the shebang is too long, or contains spaces, wrap it in `/bin/sh`.
-        if shebang_length > 127 || executable.contains(' ') {
+        // If the shebang is too long, or contains whitespace, wrap it in `/bin/sh`.
+        if shebang_length > 127 || executable.contains(char::is_whitespace) {
             // Like Python's `shlex.quote`:
             // > Use single quotes, and put single quotes into double quotes
             // > The string $'b is then quoted as '$'"'"'b'
@@ -157,7 +158,7 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
 ///
 /// <https://github.com/pypa/pip/blob/fd0ea6bc5e8cb95e518c23d901c26ca14db17f89/src/pip/_vendor/distlib/scripts.py#L248-L262>
 #[allow(unused_variables)]
-pub(crate) fn windows_script_launcher(
+pub fn windows_script_launcher(
     launcher_python_script: &str,
     is_gui: bool,
     python_executable: impl AsRef<Path>,
@@ -918,6 +919,22 @@ mod test {
         let executable = Path::new("/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3");
         let os_name = "posix";
         assert_eq!(format_shebang(executable, os_name), "#!/bin/sh\n'''exec' '/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3' \"$0\" \"$@\"\n' '''");
+
+        // The length is measured in bytes, not characters.
+        let executable = format!("/home/{}/.venv/bin/python3", "ü".repeat(60));
+        let os_name = "posix";
+        assert_eq!(
+            format_shebang(&executable, os_name),
+            format!("#!/bin/sh\n'''exec' '{executable}' \"$0\" \"$@\"\n' '''")
+        );
+
+        // Tabs split the shebang like spaces, and single quotes in the path must be escaped.
+        let executable = Path::new("/home/it's\tme/.venv/bin/python3");
+        let os_name = "posix";
+        assert_eq!(
+            format_shebang(executable, os_name),
+            "#!/bin/sh\n'''exec' '/home/it'\"'\"'s\tme/.venv/bin/python3' \"$0\" \"$@\"\n' '''"
+        );
     }
 
     #[test]
@@ -1,4 +1,3 @@
-use std::collections::BTreeSet;
 use std::fmt::Formatter;
 use std::sync::Arc;
 use std::{cmp, num::NonZeroU32};
@@ -181,6 +180,24 @@ impl Tags {
         Ok(Self::new(tags))
     }
 
+    /// Returns the tags as `(python_tag, abi_tag, platform_tag)` triples, ordered from highest to
+    /// lowest priority, i.e., in the order of the PEP 425 tag priority list.
+    pub fn by_priority(&self) -> Vec<(&str, &str, &str)> {
+        let mut tags = Vec::new();
+        for (python_tag, abi_tags) in self.map.iter() {
+            for (abi_tag, platform_tags) in abi_tags {
+                for (platform_tag, priority) in platform_tags {
+                    tags.push((
+                        *priority,
+                        (python_tag.as_str(), abi_tag.as_str(), platform_tag.as_str()),
+                    ));
+                }
+            }
+        }
+        tags.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+        tags.into_iter().map(|(_, tag)| tag).collect()
+    }
+
     /// Returns true when there exists at least one tag for this platform
     /// whose individual components all appear in each of the slices given.
     ///
@@ -279,16 +296,8 @@ impl TryFrom<usize> for TagPriority {
 impl std::fmt::Display for Tags {
     /// Display tags from high to low priority
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut tags = BTreeSet::new();
-        for (python_tag, abi_tags) in self.map.iter() {
-            for (abi_tag, platform_tags) in abi_tags {
-                for (platform_tag, priority) in platform_tags {
-                    tags.insert((priority, format!("{python_tag}-{abi_tag}-{platform_tag}")));
-                }
-            }
-        }
-        for (_, tag) in tags.iter().rev() {
-            writeln!(f, "{tag}")?;
+        for (python_tag, abi_tag, platform_tag) in self.by_priority() {
+            writeln!(f, "{python_tag}-{abi_tag}-{platform_tag}")?;
         }
         Ok(())
     }
@@ -408,10 +417,14 @@ fn compatible_tags(platform: &Platform) -> Result<Vec<String>, PlatformError> {
             platform_tags
         }
         (Os::Musllinux { major, minor }, _) => {
-            let mut platform_tags = vec![format!("linux_{}", arch)];
             // musl 1.1 is the lowest supported version in musllinux
-            platform_tags
-                .extend((1..=*minor).map(|minor| format!("musllinux_{major}_{minor}_{arch}")));
+            let mut platform_tags: Vec<String> = (1..=*minor)
+                .rev()
+                .map(|minor| format!("musllinux_{major}_{minor}_{arch}"))
+                .collect();
+            // Non-musllinux is lowest priority
+            // <https://github.com/pypa/packaging/blob/fd4f11139d1c884a637be8aa26bb60a31fbc9411/packaging/tags.py#L444>
+            platform_tags.push(format!("linux_{arch}"));
             platform_tags
         }
         (Os::Macos { major, minor }, Arch::X86_64) => {
@@ -602,6 +615,28 @@ mod tests {
         );
     }
 
+    /// Check that the musllinux tags are ordered from the most recent version, with the generic
+    /// `linux` tag last, as in `packaging`.
+    #[test]
+    fn test_platform_tags_musllinux() {
+        let tags = compatible_tags(&Platform::new(
+            Os::Musllinux { major: 1, minor: 3 },
+            Arch::Aarch64,
+        ))
+        .unwrap();
+        assert_debug_snapshot!(
+            tags,
+            @r###"
+        [
+            "musllinux_1_3_aarch64",
+            "musllinux_1_2_aarch64",
+            "musllinux_1_1_aarch64",
+            "linux_aarch64",
+        ]
+        "###
+        );
+    }
+
     #[test]
     fn test_platform_tags_macos() {
         let tags = compatible_tags(&Platform::new(
@@ -2022,4 +2057,50 @@ mod tests {
         "###
         );
     }
+
+    #[test]
+    fn test_tags_by_priority() {
+        let tags = Tags::from_env(
+            &Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            (3, 12),
+            "cpython",
+            (3, 12),
+            false,
+        )
+        .unwrap();
+        let by_priority = tags.by_priority();
+        assert_eq!(
+            by_priority.first(),
+            Some(&("cp312", "cp312", "manylinux_2_17_x86_64"))
+        );
+        assert_eq!(by_priority.last(), Some(&("py30", "none", "any")));
+
+        // The tags are ranked consistently with `Tags::compatibility`.
+        let compatibility = |(python_tag, abi_tag, platform_tag): (&str, &str, &str)| {
+            tags.compatibility(
+                &[python_tag.to_string()],
+                &[abi_tag.to_string()],
+                &[platform_tag.to_string()],
+            )
+        };
+        assert!(by_priority
+            .windows(2)
+            .all(|pair| compatibility(pair[0]) > compatibility(pair[1])));
+        assert!(by_priority.contains(&("cp32", "abi3", "manylinux2014_x86_64")));
+        assert!(!by_priority.contains(&("cp32", "abi3", "manylinux_2_18_x86_64")));
+        assert_eq!(
+            by_priority
+                .iter()
+                .filter(|(python_tag, abi_tag, _)| *python_tag == "cp312" && *abi_tag == "cp312")
+                .map(|(_, _, platform_tag)| *platform_tag)
+                .last(),
+            Some("linux_x86_64")
+        );
+    }
 }
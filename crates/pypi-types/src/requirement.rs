@@ -108,7 +108,7 @@ impl Display for Requirement {
                     write!(f, "@{reference}")?;
                 }
                 if let Some(subdirectory) = subdirectory {
-                    writeln!(f, "#subdirectory={}", subdirectory.display())?;
+                    write!(f, "#subdirectory={}", subdirectory.display())?;
                 }
             }
             RequirementSource::Path { url, .. } => {
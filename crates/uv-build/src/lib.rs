@@ -430,13 +430,11 @@ impl SourceBuild {
 
         // Create a virtual environment, or install into the shared environment if requested.
         let venv = match build_isolation {
-            BuildIsolation::Isolated => uv_virtualenv::create_venv(
-                &temp_dir.path().join(".venv"),
-                interpreter.clone(),
-                uv_virtualenv::Prompt::None,
-                false,
-                false,
-            )?,
+            BuildIsolation::Isolated => {
+                uv_virtualenv::VenvBuilder::new(temp_dir.path().join(".venv"))
+                    .interpreter(interpreter.clone())
+                    .create()?
+            }
             BuildIsolation::Shared(venv) => venv.clone(),
         };
 
@@ -636,7 +636,7 @@ impl CacheBucket {
             Self::BuiltWheels => "built-wheels-v3",
             Self::FlatIndex => "flat-index-v0",
             Self::Git => "git-v0",
-            Self::Interpreter => "interpreter-v2",
+            Self::Interpreter => "interpreter-v9",
             Self::Simple => "simple-v8",
             Self::Wheels => "wheels-v1",
             Self::Archive => "archive-v0",
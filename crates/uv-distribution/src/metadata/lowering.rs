@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use path_absolutize::Absolutize;
 use thiserror::Error;
@@ -8,7 +9,7 @@ use url::Url;
 
 use pep440_rs::VersionSpecifiers;
 use pep508_rs::{VerbatimUrl, VersionOrUrl};
-use pypi_types::{Requirement, RequirementSource, VerbatimParsedUrl};
+use pypi_types::{HashDigest, HashError, Requirement, RequirementSource, VerbatimParsedUrl};
 use uv_configuration::PreviewMode;
 use uv_fs::Simplified;
 use uv_git::GitReference;
@@ -24,6 +25,8 @@ use crate::Workspace;
 pub enum LoweringError {
     #[error("Package is not included as workspace package in `tool.uv.workspace`")]
     UndeclaredWorkspacePackage,
+    #[error("`workspace = true` requires either a workspace member with that name, or an entry in the `tool.uv.sources` of the workspace root")]
+    MissingWorkspaceSource,
     #[error("Can only specify one of: `rev`, `tag`, or `branch`")]
     MoreThanOneGitRef,
     #[error("Unable to combine options in `tool.uv.sources`")]
@@ -38,6 +41,8 @@ pub enum LoweringError {
     Absolutize(PathBuf, #[source] io::Error),
     #[error("Fragments are not allowed in URLs: `{0}`")]
     ForbiddenFragment(Url),
+    #[error(transparent)]
+    InvalidHash(#[from] HashError),
     #[error("`workspace = false` is not yet supported")]
     WorkspaceFalse,
     #[error("`tool.uv.sources` is a preview feature; use `--preview` or set `UV_PREVIEW=1` to enable it")]
@@ -94,6 +99,37 @@ pub(crate) fn lower_requirement(
         return Err(LoweringError::MissingPreview);
     }
 
+    // For packages outside the workspace, `workspace = true` inherits the source from the
+    // workspace root, with relative paths resolved against the workspace root.
+    let (source, source_dir) = match source {
+        Source::Workspace {
+            workspace: true,
+            editable,
+        } if !workspace.packages().contains_key(&requirement.name) => {
+            if matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))) {
+                return Err(LoweringError::ConflictingUrls);
+            }
+            match workspace.sources().get(&requirement.name) {
+                None | Some(Source::Workspace { .. }) => {
+                    return Err(LoweringError::MissingWorkspaceSource)
+                }
+                // The member may still opt in or out of an editable install.
+                Some(Source::Path {
+                    path,
+                    editable: root_editable,
+                }) => (
+                    Source::Path {
+                        path: path.clone(),
+                        editable: editable.or(*root_editable),
+                    },
+                    workspace.root().as_path(),
+                ),
+                Some(source) => (source.clone(), workspace.root().as_path()),
+            }
+        }
+        source => (source, project_dir),
+    };
+
     let source = match source {
         Source::Git {
             git,
@@ -105,17 +141,14 @@ pub(crate) fn lower_requirement(
             if matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))) {
                 return Err(LoweringError::ConflictingUrls);
             }
+            // The reference and subdirectory are given as separate fields, not as URL fragments.
+            if git.fragment().is_some() {
+                return Err(LoweringError::ForbiddenFragment(git));
+            }
             let reference = match (rev, tag, branch) {
                 (None, None, None) => GitReference::DefaultBranch,
-                (Some(rev), None, None) => {
-                    if rev.starts_with("refs/") {
-                        GitReference::NamedRef(rev.clone())
-                    } else if rev.len() == 40 {
-                        GitReference::FullCommit(rev.clone())
-                    } else {
-                        GitReference::ShortCommit(rev.clone())
-                    }
-                }
+                // A `rev` may be a commit, but also a branch, tag or named ref.
+                (Some(rev), None, None) => GitReference::from_rev(rev),
                 (None, Some(tag), None) => GitReference::Tag(tag),
                 (None, None, Some(branch)) => GitReference::Branch(branch),
                 _ => return Err(LoweringError::MoreThanOneGitRef),
@@ -141,20 +174,24 @@ pub(crate) fn lower_requirement(
                 subdirectory: subdirectory.map(PathBuf::from),
             }
         }
-        Source::Url { url, subdirectory } => {
+        Source::Url {
+            url,
+            subdirectory,
+            hash,
+        } => {
             if matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))) {
                 return Err(LoweringError::ConflictingUrls);
             }
-
-            let mut verbatim_url = url.clone();
-            if verbatim_url.fragment().is_some() {
+            if url.fragment().is_some() {
                 return Err(LoweringError::ForbiddenFragment(url));
             }
-            if let Some(subdirectory) = &subdirectory {
-                verbatim_url.set_fragment(Some(subdirectory));
+            // The hash itself is enforced through the hash strategy, see
+            // `Workspace::source_hashes`.
+            if let Some(hash) = &hash {
+                HashDigest::from_str(hash)?;
             }
 
-            let verbatim_url = VerbatimUrl::from_url(verbatim_url);
+            let verbatim_url = VerbatimUrl::from_url(url_source_url(&url, subdirectory.as_deref()));
             RequirementSource::Url {
                 location: url,
                 subdirectory: subdirectory.map(PathBuf::from),
@@ -165,7 +202,7 @@ pub(crate) fn lower_requirement(
             if matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))) {
                 return Err(LoweringError::ConflictingUrls);
             }
-            path_source(path, project_dir, editable.unwrap_or(false))?
+            path_source(path, source_dir, editable.unwrap_or(false))?
         }
         Source::Registry { index } => match requirement.version_or_url {
             None => {
@@ -215,6 +252,16 @@ pub(crate) fn lower_requirement(
     })
 }
 
+/// The URL of a `url` source as it appears in the lowered requirement, with the subdirectory (if
+/// any) as fragment.
+pub(crate) fn url_source_url(url: &Url, subdirectory: Option<&str>) -> Url {
+    let mut url = url.clone();
+    if let Some(subdirectory) = subdirectory {
+        url.set_fragment(Some(&format!("subdirectory={subdirectory}")));
+    }
+    url
+}
+
 /// Convert a path string to a path section.
 fn path_source(
     path: impl AsRef<Path>,
@@ -12,7 +12,7 @@ use crate::metadata::lowering::LoweringError;
 pub use crate::metadata::requires_dist::{RequiresDist, DEV_DEPENDENCIES};
 use crate::WorkspaceError;
 
-mod lowering;
+pub(crate) mod lowering;
 mod requires_dist;
 
 #[derive(Debug, Error)]
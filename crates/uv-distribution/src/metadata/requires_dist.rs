@@ -150,8 +150,11 @@ mod test {
     use indoc::indoc;
     use insta::assert_snapshot;
 
+    use pypi_types::RequirementSource;
     use uv_configuration::PreviewMode;
 
+    use crate::metadata::lowering::LoweringError;
+    use crate::metadata::MetadataError;
     use crate::pyproject::PyProjectToml;
     use crate::{ProjectWorkspace, RequiresDist};
 
@@ -225,6 +228,114 @@ mod test {
         "###);
     }
 
+    #[tokio::test]
+    async fn git_sources() {
+        let input = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "0.0.0"
+            dependencies = [
+              "tqdm",
+              "flask",
+              "anyio",
+              "idna",
+              "sniffio",
+            ]
+            [tool.uv.sources]
+            tqdm = { git = "https://github.com/tqdm/tqdm" }
+            flask = { git = "https://github.com/pallets/flask", tag = "3.0.0" }
+            anyio = { git = "https://github.com/agronholm/anyio", branch = "master", subdirectory = "src" }
+            idna = { git = "https://github.com/kjd/idna", rev = "7c8deb4fa6b11e0d1e6e0e9f5c0d3fa6ac3c0a10" }
+            sniffio = { git = "https://github.com/python-trio/sniffio", rev = "main" }
+        "#};
+
+        let requires_dist = requires_dist_from_pyproject_toml(input).await.unwrap();
+        let requirements = requires_dist
+            .requires_dist
+            .iter()
+            .map(|requirement| match &requirement.source {
+                RequirementSource::Git { reference, .. } => {
+                    format!("{requirement} ({reference:?})")
+                }
+                source => panic!("expected a Git source: {source:?}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_snapshot!(requirements, @r###"
+        tqdm @ git+https://github.com/tqdm/tqdm (DefaultBranch)
+        flask @ git+https://github.com/pallets/flask@3.0.0 (Tag("3.0.0"))
+        anyio @ git+https://github.com/agronholm/anyio@master#subdirectory=src (Branch("master"))
+        idna @ git+https://github.com/kjd/idna@7c8deb4fa6b11e0d1e6e0e9f5c0d3fa6ac3c0a10 (FullCommit("7c8deb4fa6b11e0d1e6e0e9f5c0d3fa6ac3c0a10"))
+        sniffio @ git+https://github.com/python-trio/sniffio@main (BranchOrTag("main"))
+        "###);
+    }
+
+    #[tokio::test]
+    async fn git_fragment() {
+        let input = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "0.0.0"
+            dependencies = [
+              "tqdm",
+            ]
+            [tool.uv.sources]
+            tqdm = { git = "https://github.com/tqdm/tqdm#subdirectory=src" }
+        "#};
+
+        assert_snapshot!(format_err(input).await, @r###"
+        error: Failed to parse entry for: `tqdm`
+          Caused by: Fragments are not allowed in URLs: `https://github.com/tqdm/tqdm#subdirectory=src`
+        "###);
+    }
+
+    #[tokio::test]
+    async fn url_sources() {
+        let input = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "0.0.0"
+            dependencies = [
+              "tqdm",
+              "flask",
+            ]
+            [tool.uv.sources]
+            tqdm = { url = "https://files.pythonhosted.org/packages/a5/d6/502a859bac4ad5e274255576cd3e15ca273cdb91731bc39fb840dd422ee9/tqdm-4.66.0-py3-none-any.whl", hash = "sha256:39502d7d9fbd8e6bc4b3e2be8f34fb4fd1d98d5bcf8ec2bb4e5c7a8ffd0db70b" }
+            flask = { url = "https://example.com/flask.tar.gz", subdirectory = "src" }
+        "#};
+
+        let requires_dist = requires_dist_from_pyproject_toml(input).await.unwrap();
+        let requirements = requires_dist
+            .requires_dist
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_snapshot!(requirements, @r###"
+        tqdm @ https://files.pythonhosted.org/packages/a5/d6/502a859bac4ad5e274255576cd3e15ca273cdb91731bc39fb840dd422ee9/tqdm-4.66.0-py3-none-any.whl
+        flask @ https://example.com/flask.tar.gz#subdirectory=src
+        "###);
+    }
+
+    #[tokio::test]
+    async fn invalid_url_hash() {
+        let input = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "0.0.0"
+            dependencies = [
+              "tqdm",
+            ]
+            [tool.uv.sources]
+            tqdm = { url = "https://example.com/tqdm-4.66.0-py3-none-any.whl", hash = "sha1:c0ffee" }
+        "#};
+
+        assert_snapshot!(format_err(input).await, @r###"
+        error: Failed to parse entry for: `tqdm`
+          Caused by: Unsupported hash algorithm: `sha1` (expected one of: `md5`, `sha256`, `sha384`, or `sha512`)
+        "###);
+    }
+
     #[tokio::test]
     async fn too_many_git_typo() {
         let input = indoc! {r#"
@@ -367,10 +478,103 @@ mod test {
 
         assert_snapshot!(format_err(input).await, @r###"
         error: Failed to parse entry for: `tqdm`
-          Caused by: Package is not included as workspace package in `tool.uv.workspace`
+          Caused by: `workspace = true` requires either a workspace member with that name, or an entry in the `tool.uv.sources` of the workspace root
         "###);
     }
 
+    #[tokio::test]
+    async fn inherited_workspace_sources() {
+        let root = tempfile::tempdir().unwrap();
+        fs_err::write(
+            root.path().join("pyproject.toml"),
+            indoc! {r#"
+                [project]
+                name = "albatross"
+                version = "0.1.0"
+                dependencies = []
+
+                [tool.uv.workspace]
+                members = ["packages/*"]
+
+                [tool.uv.sources]
+                tqdm = { git = "https://github.com/tqdm/tqdm", tag = "v4.66.0" }
+                seeds = { path = "vendor/seeds" }
+            "#},
+        )
+        .unwrap();
+        let members = [
+            (
+                "bird-feeder",
+                indoc! {r#"
+                    [project]
+                    name = "bird-feeder"
+                    version = "0.1.0"
+                    dependencies = ["tqdm", "seeds"]
+
+                    [tool.uv.sources]
+                    tqdm = { workspace = true }
+                    seeds = { workspace = true, editable = true }
+                "#},
+            ),
+            (
+                "feathers",
+                indoc! {r#"
+                    [project]
+                    name = "feathers"
+                    version = "0.1.0"
+                    dependencies = ["anyio"]
+
+                    [tool.uv.sources]
+                    anyio = { workspace = true }
+                "#},
+            ),
+        ];
+        for (name, contents) in members {
+            let member = root.path().join("packages").join(name);
+            fs_err::create_dir_all(&member).unwrap();
+            fs_err::write(member.join("pyproject.toml"), contents).unwrap();
+        }
+
+        let requires_dist = |name: &str| {
+            let member = root.path().join("packages").join(name);
+            async move {
+                let contents = fs_err::read_to_string(member.join("pyproject.toml"))?;
+                let project_workspace = ProjectWorkspace::from_project_root(&member, None).await?;
+                let requires_dist = pypi_types::RequiresDist::parse_pyproject_toml(&contents)?;
+                Ok::<_, anyhow::Error>(RequiresDist::from_project_workspace(
+                    requires_dist,
+                    &project_workspace,
+                    PreviewMode::Enabled,
+                )?)
+            }
+        };
+
+        // The sources are taken from the workspace root, with paths relative to the root.
+        let requires_dist = requires_dist("bird-feeder").await.unwrap();
+        let [tqdm, seeds] = requires_dist.requires_dist.as_slice() else {
+            panic!("expected two requirements");
+        };
+        assert_eq!(
+            tqdm.to_string(),
+            "tqdm @ git+https://github.com/tqdm/tqdm@v4.66.0"
+        );
+        let RequirementSource::Path { path, editable, .. } = &seeds.source else {
+            panic!("expected a path source: {seeds}");
+        };
+        assert_eq!(path, &root.path().join("vendor").join("seeds"));
+        assert!(editable);
+
+        // Without an entry in the workspace root, there's nothing to inherit.
+        let err = requires_dist("feathers").await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<MetadataError>(),
+            Some(MetadataError::LoweringError(
+                _,
+                LoweringError::MissingWorkspaceSource
+            ))
+        ));
+    }
+
     #[tokio::test]
     async fn cant_be_dynamic() {
         let input = indoc! {r#"
@@ -64,10 +64,109 @@ pub struct ToolUv {
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolUvWorkspace {
-    pub members: Option<Vec<SerdePattern>>,
+    /// Globs of member directories, relative to the workspace root. Globs prefixed with `!`
+    /// remove the directories matched by the preceding globs, e.g., `["packages/*",
+    /// "!packages/experimental-*"]`.
+    pub members: Option<Vec<MemberGlob>>,
+    /// Directories to exclude from the workspace, as globs relative to the workspace root, or
+    /// members to exclude, by package name.
     pub exclude: Option<Vec<SerdePattern>>,
+    /// Whether a member with a `requires-python` that is disjoint from the workspace root's is a
+    /// warning or an error.
+    pub disjoint_requires_python: Option<DisjointRequiresPython>,
+    /// Whether a project that is matched by `members`, but isn't part of the workspace, e.g.,
+    /// because it's nested in another project, is a warning or an error.
+    pub detached_members: Option<DetachedMembers>,
+}
+
+/// How to handle a workspace member with a `requires-python` that no Python version satisfies
+/// together with the `requires-python` of the workspace root.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum DisjointRequiresPython {
+    /// Warn about the member, but continue with the workspace.
+    #[default]
+    Warn,
+    /// Fail workspace discovery.
+    Error,
+}
+
+/// How to handle a project below the workspace root that is matched by the `members` globs, but
+/// isn't part of the workspace, without being excluded explicitly.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum DetachedMembers {
+    /// Warn about the project, but continue with it as a standalone project.
+    #[default]
+    Warn,
+    /// Fail workspace discovery.
+    Error,
+}
+
+/// A `tool.uv.workspace.members` entry.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged, deny_unknown_fields)]
+pub enum MemberGlob {
+    /// A glob of member directories.
+    ///
+    /// Example:
+    /// ```toml
+    /// members = ["packages/*"]
+    /// ```
+    Glob(SerdePattern),
+    /// A glob of member directories that are only members if a condition holds.
+    ///
+    /// Example:
+    /// ```toml
+    /// members = [{ path = "cuda/*", marker = "sys_platform == 'linux'", env = "WITH_CUDA" }]
+    /// ```
+    Conditional {
+        /// The glob of member directories.
+        path: SerdePattern,
+        /// A PEP 508 marker; the members are only installed in environments matching it.
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+        marker: Option<pep508_rs::MarkerTree>,
+        /// An environment variable; the members are only discovered if it is set to a non-empty
+        /// value.
+        env: Option<String>,
+    },
+}
+
+impl MemberGlob {
+    /// The glob of member directories.
+    pub fn glob(&self) -> &SerdePattern {
+        match self {
+            Self::Glob(glob) | Self::Conditional { path: glob, .. } => glob,
+        }
+    }
+
+    /// If the glob is a negation, i.e., prefixed with `!`, the glob of the directories it
+    /// removes from the members.
+    pub fn negated(&self) -> Option<&str> {
+        self.glob().as_str().strip_prefix('!')
+    }
+
+    /// The marker of the environments the members are installed in, if any.
+    pub fn marker(&self) -> Option<&pep508_rs::MarkerTree> {
+        match self {
+            Self::Glob(_) => None,
+            Self::Conditional { marker, .. } => marker.as_ref(),
+        }
+    }
+
+    /// The environment variable that must be set for the members to be discovered, if any.
+    pub fn env(&self) -> Option<&str> {
+        match self {
+            Self::Glob(_) => None,
+            Self::Conditional { env, .. } => env.as_deref(),
+        }
+    }
 }
 
 /// (De)serialize globs as strings.
@@ -110,8 +209,12 @@ pub enum Source {
         /// The path to the directory with the `pyproject.toml`, if it's not in the archive root.
         subdirectory: Option<String>,
         // Only one of the three may be used; we'll validate this later and emit a custom error.
+        /// The revision to check out, usually a commit hash, but branches, tags and named refs
+        /// (e.g., `refs/pull/1/head`) are accepted too.
         rev: Option<String>,
+        /// The tag to check out.
         tag: Option<String>,
+        /// The branch to check out.
         branch: Option<String>,
     },
     /// A remote `http://` or `https://` URL, either a wheel (`.whl`) or a source distribution
@@ -126,6 +229,9 @@ pub enum Source {
         /// For source distributions, the path to the directory with the `pyproject.toml`, if it's
         /// not in the archive root.
         subdirectory: Option<String>,
+        /// The expected hash of the archive, e.g., `sha256:<hash>`. When set, the archive is
+        /// rejected if its hash doesn't match.
+        hash: Option<String>,
     },
     /// The path to a dependency, either a wheel (a `.whl` file), source distribution (a `.zip` or
     /// `.tag.gz` file), or source tree (i.e., a directory containing a `pyproject.toml` or
@@ -156,6 +262,7 @@ pub enum Source {
         tag: Option<String>,
         branch: Option<String>,
         url: String,
+        hash: String,
         patch: String,
         index: String,
         workspace: bool,
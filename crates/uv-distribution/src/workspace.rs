@@ -1,42 +1,89 @@
 //! Resolve the current [`ProjectWorkspace`] or [`Workspace`].
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use glob::{glob, GlobError, PatternError};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::{debug, trace};
 
-use pep508_rs::VerbatimUrl;
-use pypi_types::{Requirement, RequirementSource};
+use distribution_types::PackageId;
+use pep440_rs::{Operator, Version, VersionSpecifiers};
+use pep508_rs::{MarkerTree, VerbatimUrl};
+use pypi_types::{HashDigest, HashError, Requirement, RequirementSource};
 use uv_fs::{absolutize_path, Simplified};
 use uv_normalize::PackageName;
 use uv_warnings::warn_user;
 
-use crate::pyproject::{Project, PyProjectToml, Source, ToolUvWorkspace};
+use crate::metadata::lowering::url_source_url;
+use crate::pyproject::{
+    DetachedMembers, DisjointRequiresPython, MemberGlob, Project, PyProjectToml, Source,
+    ToolUvWorkspace,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum WorkspaceError {
     // Workspace structure errors.
     #[error("No `pyproject.toml` found in current directory or any parent directory")]
     MissingPyprojectToml,
-    #[error("No `project` table found in: `{}`", _0.simplified_display())]
+    #[error("No `project` table found in: `{}`", _0.user_display())]
     MissingProject(PathBuf),
-    #[error("No workspace found for: `{}`", _0.simplified_display())]
+    #[error("No workspace found for: `{}`", _0.user_display())]
     MissingWorkspace(PathBuf),
     #[error("pyproject.toml section is declared as dynamic, but must be static: `{0}`")]
     DynamicNotAllowed(&'static str),
     #[error("Failed to find directories for glob: `{0}`")]
     Pattern(String, #[source] PatternError),
+    #[error(
+        "The `requires-python` of `{}` (`{member_requires_python}`) is disjoint from the `requires-python` of the workspace root `{}` (`{root_requires_python}`)",
+        member.user_display(),
+        root.user_display()
+    )]
+    DisjointRequiresPython {
+        member: PathBuf,
+        member_requires_python: VersionSpecifiers,
+        root: PathBuf,
+        root_requires_python: VersionSpecifiers,
+    },
+    #[error(
+        "Workspace member `{}` of the workspace at `{}` declares its own `tool.uv.workspace`, but nested workspaces are not supported",
+        inner.user_display(),
+        outer.user_display()
+    )]
+    NestedWorkspace { inner: PathBuf, outer: PathBuf },
+    #[error(
+        "Two workspace members are both named `{name}`: `{}` and `{}`",
+        first.user_display(),
+        second.user_display()
+    )]
+    DuplicatePackage {
+        name: PackageName,
+        first: PathBuf,
+        second: PathBuf,
+    },
+    #[error(
+        "The project at `{}` is matched by the `tool.uv.workspace.members` of the workspace at `{}`, but is not part of the workspace; add it to `tool.uv.workspace.exclude` if this is intended",
+        project.user_display(),
+        workspace.user_display()
+    )]
+    DetachedMember {
+        project: PathBuf,
+        workspace: PathBuf,
+    },
     // Syntax and other errors.
     #[error("Invalid glob in `tool.uv.workspace.members`: `{0}`")]
     Glob(String, #[source] GlobError),
+    #[error("Negated globs in `tool.uv.workspace.members` can't be conditional: `{0}`")]
+    ConditionalNegation(String),
     #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error("Failed to parse: `{}`", _0.user_display())]
     Toml(PathBuf, #[source] Box<toml::de::Error>),
     #[error("Failed to normalize workspace member path")]
     Normalize(#[source] std::io::Error),
+    #[error("Invalid `hash` in `tool.uv.sources` for: `{0}`")]
+    SourceHash(PackageName, #[source] HashError),
 }
 
 /// A workspace, consisting of a root directory and members. See [`ProjectWorkspace`].
@@ -51,6 +98,9 @@ pub struct Workspace {
     /// The sources table from the workspace `pyproject.toml`. It is overridden by the project
     /// sources.
     sources: BTreeMap<PackageName, Source>,
+    /// The fingerprint of the workspace structure at discovery time.
+    #[cfg_attr(test, serde(skip))]
+    fingerprint: WorkspaceFingerprint,
 }
 
 impl Workspace {
@@ -100,20 +150,23 @@ impl Workspace {
             if let Some(workspace) = explicit_root {
                 // We have found the explicit root immediately.
                 workspace
-            } else if pyproject_toml.project.is_none() {
+            } else if let Some(project) = &pyproject_toml.project {
+                if let Some(workspace) =
+                    find_workspace(&project_path, &project.name, stop_discovery_at).await?
+                {
+                    // We have found an explicit root above.
+                    workspace
+                } else {
+                    // Support implicit single project workspaces.
+                    (
+                        project_path.clone(),
+                        ToolUvWorkspace::default(),
+                        pyproject_toml.clone(),
+                    )
+                }
+            } else {
                 // Without a project, it can't be an implicit root
                 return Err(WorkspaceError::MissingProject(project_path));
-            } else if let Some(workspace) = find_workspace(&project_path, stop_discovery_at).await?
-            {
-                // We have found an explicit root above.
-                workspace
-            } else {
-                // Support implicit single project workspaces.
-                (
-                    project_path.clone(),
-                    ToolUvWorkspace::default(),
-                    pyproject_toml.clone(),
-                )
             };
 
         debug!(
@@ -130,6 +183,7 @@ impl Workspace {
                 root: project_path,
                 project,
                 pyproject_toml,
+                marker: None,
             });
         Self::collect_members(
             workspace_root,
@@ -157,33 +211,7 @@ impl Workspace {
     pub fn members_as_requirements(&self) -> Vec<Requirement> {
         self.packages
             .values()
-            .filter_map(|member| {
-                let project = member.pyproject_toml.project.as_ref()?;
-                // Extract the extras available in the project.
-                let extras = project
-                    .optional_dependencies
-                    .as_ref()
-                    .map(|optional_dependencies| {
-                        // It's a `BTreeMap` so the keys are sorted.
-                        optional_dependencies.keys().cloned().collect::<Vec<_>>()
-                    })
-                    .unwrap_or_default();
-
-                let url = VerbatimUrl::from_path(&member.root)
-                    .expect("path is valid URL")
-                    .with_given(member.root.to_string_lossy());
-                Some(Requirement {
-                    name: project.name.clone(),
-                    extras,
-                    marker: None,
-                    source: RequirementSource::Path {
-                        path: member.root.clone(),
-                        editable: true,
-                        url,
-                    },
-                    origin: None,
-                })
-            })
+            .map(|member| member.as_editable_requirement(member.root.to_string_lossy()))
             .collect()
     }
 
@@ -210,6 +238,44 @@ impl Workspace {
         &self.sources
     }
 
+    /// The hashes pinned by `url` entries in `tool.uv.sources`, across the workspace root and all
+    /// members, keyed by the URL of the lowered requirement.
+    pub fn source_hashes(&self) -> Result<FxHashMap<PackageId, Vec<HashDigest>>, WorkspaceError> {
+        let member_sources = self.packages.values().filter_map(|member| {
+            member
+                .pyproject_toml
+                .tool
+                .as_ref()
+                .and_then(|tool| tool.uv.as_ref())
+                .and_then(|uv| uv.sources.as_ref())
+        });
+
+        let mut hashes = FxHashMap::default();
+        for (name, source) in std::iter::once(&self.sources)
+            .chain(member_sources)
+            .flatten()
+        {
+            let Source::Url {
+                url,
+                subdirectory,
+                hash: Some(hash),
+            } = source
+            else {
+                continue;
+            };
+            let digest = HashDigest::from_str(hash)
+                .map_err(|err| WorkspaceError::SourceHash(name.clone(), err))?;
+            let url = url_source_url(url, subdirectory.as_deref());
+            hashes.insert(PackageId::from_url(&url), vec![digest]);
+        }
+        Ok(hashes)
+    }
+
+    /// The fingerprint of the workspace structure at the time it was discovered.
+    pub fn fingerprint(&self) -> &WorkspaceFingerprint {
+        &self.fingerprint
+    }
+
     /// Collect the workspace member projects from the `members` and `excludes` entries.
     async fn collect_members(
         workspace_root: PathBuf,
@@ -247,6 +313,7 @@ impl Workspace {
                         root: workspace_root.clone(),
                         project: project.clone(),
                         pyproject_toml,
+                        marker: None,
                     },
                 );
             };
@@ -260,71 +327,264 @@ impl Workspace {
             );
 
             seen.insert(root_member.root.clone());
+            if let Some(existing) = workspace_members.get(&root_member.project.name) {
+                return Err(WorkspaceError::DuplicatePackage {
+                    name: root_member.project.name,
+                    first: existing.root.clone(),
+                    second: root_member.root,
+                });
+            }
             workspace_members.insert(root_member.project.name.clone(), root_member);
         }
 
+        // The members matched by the workspace definition, for the fingerprint. Unlike the
+        // members, this excludes a current project that is not matched by the globs.
+        let mut fingerprint_roots = Vec::new();
+        if workspace_pyproject_toml.project.is_some() {
+            fingerprint_roots.push(workspace_root.clone());
+        }
+
         // Add all other workspace members.
-        for member_glob in workspace_definition.members.unwrap_or_default() {
-            let absolute_glob = workspace_root
-                .simplified()
-                .join(member_glob.as_str())
-                .to_string_lossy()
-                .to_string();
-            for member_root in glob(&absolute_glob)
-                .map_err(|err| WorkspaceError::Pattern(absolute_glob.to_string(), err))?
-            {
-                let member_root = member_root
-                    .map_err(|err| WorkspaceError::Glob(absolute_glob.to_string(), err))?;
-                if !seen.insert(member_root.clone()) {
-                    continue;
-                }
-                let member_root = absolutize_path(&member_root)
-                    .map_err(WorkspaceError::Normalize)?
-                    .to_path_buf();
+        for (member_root, member_glob) in
+            expand_member_globs(&workspace_root, &workspace_definition)?
+        {
+            let member_root = absolutize_path(&member_root)
+                .map_err(WorkspaceError::Normalize)?
+                .to_path_buf();
+            fingerprint_roots.push(member_root.clone());
+            if !seen.insert(member_root.clone()) {
+                continue;
+            }
 
-                trace!("Processing workspace member {}", member_root.user_display());
+            trace!("Processing workspace member {}", member_root.user_display());
 
-                // Read the member `pyproject.toml`.
-                let pyproject_path = member_root.join("pyproject.toml");
-                let contents = fs_err::tokio::read_to_string(&pyproject_path).await?;
-                let pyproject_toml: PyProjectToml = toml::from_str(&contents)
-                    .map_err(|err| WorkspaceError::Toml(pyproject_path, Box::new(err)))?;
+            // Read the member `pyproject.toml`.
+            let pyproject_path = member_root.join("pyproject.toml");
+            let contents = fs_err::tokio::read_to_string(&pyproject_path).await?;
+            let pyproject_toml: PyProjectToml = toml::from_str(&contents)
+                .map_err(|err| WorkspaceError::Toml(pyproject_path, Box::new(err)))?;
 
-                // Extract the package name.
-                let Some(project) = pyproject_toml.project.clone() else {
-                    return Err(WorkspaceError::MissingProject(member_root));
-                };
+            // Extract the package name.
+            let Some(project) = pyproject_toml.project.clone() else {
+                return Err(WorkspaceError::MissingProject(member_root));
+            };
 
+            // A member can't be the root of another workspace.
+            if pyproject_toml
+                .tool
+                .as_ref()
+                .and_then(|tool| tool.uv.as_ref())
+                .is_some_and(|uv| uv.workspace.is_some())
+            {
+                return Err(WorkspaceError::NestedWorkspace {
+                    inner: member_root,
+                    outer: workspace_root,
+                });
+            }
+
+            if is_excluded_by_name(&project.name, &workspace_definition) {
                 debug!(
-                    "Adding discovered workspace member: {}",
+                    "Skipping workspace member `{}`, it is excluded by name: {}",
+                    project.name,
                     member_root.simplified_display()
                 );
-                workspace_members.insert(
-                    project.name.clone(),
-                    WorkspaceMember {
-                        root: member_root.clone(),
-                        project,
-                        pyproject_toml,
-                    },
-                );
+                continue;
             }
+
+            if let Some(existing) = workspace_members.get(&project.name) {
+                return Err(WorkspaceError::DuplicatePackage {
+                    name: project.name,
+                    first: existing.root.clone(),
+                    second: member_root,
+                });
+            }
+
+            debug!(
+                "Adding discovered workspace member: {}",
+                member_root.simplified_display()
+            );
+            workspace_members.insert(
+                project.name.clone(),
+                WorkspaceMember {
+                    root: member_root.clone(),
+                    project,
+                    pyproject_toml,
+                    marker: member_glob.marker().cloned(),
+                },
+            );
         }
+
+        check_requires_python(
+            &workspace_root,
+            &workspace_pyproject_toml,
+            &workspace_members,
+            workspace_definition
+                .disjoint_requires_python
+                .unwrap_or_default(),
+        )?;
+
         let workspace_sources = workspace_pyproject_toml
             .tool
             .and_then(|tool| tool.uv)
             .and_then(|uv| uv.sources)
             .unwrap_or_default();
 
-        check_nested_workspaces(&workspace_root, stop_discovery_at);
+        check_nested_workspaces(
+            &workspace_root,
+            workspace_pyproject_toml
+                .project
+                .as_ref()
+                .map(|project| &project.name),
+            stop_discovery_at,
+        )?;
+
+        let fingerprint = WorkspaceFingerprint::new(
+            &workspace_definition,
+            &workspace_sources,
+            fingerprint_roots.iter().map(PathBuf::as_path),
+        );
 
         Ok(Workspace {
             root: workspace_root,
             packages: workspace_members,
             sources: workspace_sources,
+            fingerprint,
         })
     }
 }
 
+/// A fingerprint of the structure of a workspace, i.e., the workspace tables of the root
+/// `pyproject.toml` and the set of member directories.
+///
+/// Tools that cache state derived from a workspace, e.g., a lockfile, can persist the fingerprint
+/// and use [`WorkspaceFingerprint::has_changed`] to cheaply check whether the workspace needs to be
+/// discovered again. Changes to the members' own `pyproject.toml` files are not covered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WorkspaceFingerprint(String);
+
+impl WorkspaceFingerprint {
+    fn new<'a>(
+        definition: &ToolUvWorkspace,
+        sources: &BTreeMap<PackageName, Source>,
+        member_roots: impl Iterator<Item = &'a Path>,
+    ) -> Self {
+        let member_roots = member_roots.collect::<BTreeSet<_>>();
+        Self(cache_key::digest(&format!(
+            "{definition:?}{sources:?}{member_roots:?}"
+        )))
+    }
+
+    /// Compute the fingerprint of the workspace at the given root from the files on disk.
+    ///
+    /// Unlike [`Workspace::discover`], this only reads the root `pyproject.toml` and expands the
+    /// member globs.
+    pub async fn from_root(workspace_root: &Path) -> Result<Self, WorkspaceError> {
+        let workspace_root = absolutize_path(workspace_root)
+            .map_err(WorkspaceError::Normalize)?
+            .to_path_buf();
+
+        let pyproject_path = workspace_root.join("pyproject.toml");
+        let contents = fs_err::tokio::read_to_string(&pyproject_path).await?;
+        let pyproject_toml: PyProjectToml = toml::from_str(&contents)
+            .map_err(|err| WorkspaceError::Toml(pyproject_path, Box::new(err)))?;
+        let tool_uv = pyproject_toml.tool.and_then(|tool| tool.uv);
+        let definition = tool_uv
+            .as_ref()
+            .and_then(|uv| uv.workspace.clone())
+            .unwrap_or_default();
+        let sources = tool_uv.and_then(|uv| uv.sources).unwrap_or_default();
+
+        let mut member_roots = Vec::new();
+        if pyproject_toml.project.is_some() {
+            member_roots.push(workspace_root.clone());
+        }
+        for (member_root, _) in expand_member_globs(&workspace_root, &definition)? {
+            member_roots.push(
+                absolutize_path(&member_root)
+                    .map_err(WorkspaceError::Normalize)?
+                    .to_path_buf(),
+            );
+        }
+
+        Ok(Self::new(
+            &definition,
+            &sources,
+            member_roots.iter().map(PathBuf::as_path),
+        ))
+    }
+
+    /// Returns `true` if the workspace at the given root no longer matches this fingerprint.
+    pub async fn has_changed(&self, workspace_root: &Path) -> Result<bool, WorkspaceError> {
+        Ok(Self::from_root(workspace_root).await? != *self)
+    }
+
+    /// The fingerprint as a string, e.g., to persist it.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for WorkspaceFingerprint {
+    fn from(fingerprint: String) -> Self {
+        Self(fingerprint)
+    }
+}
+
+impl std::fmt::Display for WorkspaceFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Expand the `members` globs of a workspace definition into the matching directories.
+///
+/// Members gated on an environment variable are skipped if the variable is unset or empty.
+/// Negated globs remove the directories matched by the globs before them.
+fn expand_member_globs<'a>(
+    workspace_root: &Path,
+    definition: &'a ToolUvWorkspace,
+) -> Result<Vec<(PathBuf, &'a MemberGlob)>, WorkspaceError> {
+    let mut member_roots = Vec::new();
+    for member_glob in definition.members.iter().flatten() {
+        if let Some(negated) = member_glob.negated() {
+            if matches!(member_glob, MemberGlob::Conditional { .. }) {
+                return Err(WorkspaceError::ConditionalNegation(
+                    member_glob.glob().as_str().to_string(),
+                ));
+            }
+            let negated_roots = expand_glob(workspace_root, negated)?;
+            member_roots.retain(|(member_root, _)| !negated_roots.contains(member_root));
+            continue;
+        }
+        if let Some(var) = member_glob.env() {
+            if std::env::var_os(var).map_or(true, |value| value.is_empty()) {
+                debug!(
+                    "Skipping workspace members `{}`, `{var}` is not set",
+                    member_glob.glob().as_str()
+                );
+                continue;
+            }
+        }
+        for member_root in expand_glob(workspace_root, member_glob.glob().as_str())? {
+            member_roots.push((member_root, member_glob));
+        }
+    }
+    Ok(member_roots)
+}
+
+/// Expand a glob relative to the workspace root into the matching paths.
+fn expand_glob(workspace_root: &Path, pattern: &str) -> Result<Vec<PathBuf>, WorkspaceError> {
+    let absolute_glob = workspace_root
+        .simplified()
+        .join(pattern)
+        .to_string_lossy()
+        .to_string();
+    glob(&absolute_glob)
+        .map_err(|err| WorkspaceError::Pattern(absolute_glob.to_string(), err))?
+        .map(|path| path.map_err(|err| WorkspaceError::Glob(absolute_glob.to_string(), err)))
+        .collect()
+}
+
 /// A project in a workspace.
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(serde::Serialize))]
@@ -336,6 +596,10 @@ pub struct WorkspaceMember {
     project: Project,
     /// The `pyproject.toml` of the project, found at `<root>/pyproject.toml`.
     pyproject_toml: PyProjectToml,
+    /// The marker of the environments the member is installed in, from a conditional
+    /// `tool.uv.workspace.members` entry.
+    #[cfg_attr(test, serde(skip_serializing_if = "Option::is_none"))]
+    marker: Option<MarkerTree>,
 }
 
 impl WorkspaceMember {
@@ -354,6 +618,39 @@ impl WorkspaceMember {
     pub fn pyproject_toml(&self) -> &PyProjectToml {
         &self.pyproject_toml
     }
+
+    /// The marker of the environments the member is installed in, if the member is conditional.
+    pub fn marker(&self) -> Option<&MarkerTree> {
+        self.marker.as_ref()
+    }
+
+    /// An editable path requirement on the member, with all of its extras.
+    ///
+    /// `given` is the path as it should be shown to the user, e.g., in a lockfile.
+    fn as_editable_requirement(&self, given: impl Into<String>) -> Requirement {
+        // Extract the extras available in the project. It's a `BTreeMap` so the keys are sorted.
+        let extras = self
+            .project
+            .optional_dependencies
+            .as_ref()
+            .map(|optional_dependencies| optional_dependencies.keys().cloned().collect())
+            .unwrap_or_default();
+
+        let url = VerbatimUrl::from_path(&self.root)
+            .expect("path is valid URL")
+            .with_given(given);
+        Requirement {
+            name: self.project.name.clone(),
+            extras,
+            marker: self.marker.clone(),
+            source: RequirementSource::Path {
+                path: self.root.clone(),
+                editable: true,
+                url,
+            },
+            origin: None,
+        }
+    }
 }
 
 /// The current project and the workspace it is part of, with all of the workspace members.
@@ -539,6 +836,26 @@ impl ProjectWorkspace {
         &self.workspace().packages[&self.project_name]
     }
 
+    /// Returns all workspace members as editable requirements, with all of their extras, for
+    /// installing the whole workspace into the project environment.
+    ///
+    /// The given path of each requirement is the member root relative to the project root, e.g.,
+    /// `../seeds` for a sibling member, and `.` for the current project itself.
+    pub fn members_as_editable_requirements(&self) -> Vec<Requirement> {
+        self.workspace
+            .packages
+            .values()
+            .map(|member| {
+                let given = match pathdiff::diff_paths(&member.root, &self.project_root) {
+                    Some(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+                    Some(relative) => relative,
+                    None => member.root.clone(),
+                };
+                member.as_editable_requirement(given.to_string_lossy())
+            })
+            .collect()
+    }
+
     /// Find the workspace for a project.
     pub async fn from_project(
         project_path: &Path,
@@ -567,13 +884,14 @@ impl ProjectWorkspace {
         if workspace.is_none() {
             // The project isn't an explicit workspace root, check if we're a regular workspace
             // member by looking for an explicit workspace root above.
-            workspace = find_workspace(&project_path, stop_discovery_at).await?;
+            workspace = find_workspace(&project_path, &project.name, stop_discovery_at).await?;
         }
 
         let current_project = WorkspaceMember {
             root: project_path.clone(),
             project: project.clone(),
             pyproject_toml: project_pyproject_toml.clone(),
+            marker: None,
         };
 
         let Some((workspace_root, workspace_definition, workspace_pyproject_toml)) = workspace
@@ -582,6 +900,18 @@ impl ProjectWorkspace {
             // above it, so the project is an implicit workspace root identical to the project root.
             debug!("No workspace root found, using project root");
 
+            check_above(&project_path, &project.name, stop_discovery_at)?;
+
+            let fingerprint = WorkspaceFingerprint::new(
+                &ToolUvWorkspace::default(),
+                &project_pyproject_toml
+                    .tool
+                    .as_ref()
+                    .and_then(|tool| tool.uv.as_ref())
+                    .and_then(|uv| uv.sources.clone())
+                    .unwrap_or_default(),
+                std::iter::once(project_path.as_path()),
+            );
             let current_project_as_members =
                 BTreeMap::from_iter([(project.name.clone(), current_project)]);
             return Ok(Self {
@@ -593,6 +923,7 @@ impl ProjectWorkspace {
                     // There may be package sources, but we don't need to duplicate them into the
                     // workspace sources.
                     sources: BTreeMap::default(),
+                    fingerprint,
                 },
             });
         };
@@ -622,6 +953,7 @@ impl ProjectWorkspace {
 /// Find the workspace root above the current project, if any.
 async fn find_workspace(
     project_root: &Path,
+    project_name: &PackageName,
     stop_discovery_at: Option<&Path>,
 ) -> Result<Option<(PathBuf, ToolUvWorkspace, PyProjectToml)>, WorkspaceError> {
     // Skip 1 to ignore the current project itself.
@@ -655,7 +987,9 @@ async fn find_workspace(
             .and_then(|tool| tool.uv.as_ref())
             .and_then(|uv| uv.workspace.as_ref())
         {
-            if is_excluded_from_workspace(project_root, workspace_root, workspace)? {
+            if is_excluded_from_workspace(project_root, workspace_root, workspace)?
+                || is_excluded_by_name(project_name, workspace)
+            {
                 debug!(
                     "Found workspace root `{}`, but project is excluded.",
                     workspace_root.simplified_display()
@@ -697,7 +1031,7 @@ async fn find_workspace(
             // We require that a `project.toml` file either declares a workspace or a project.
             warn_user!(
                 "pyproject.toml does not contain `project` table: `{}`",
-                workspace_root.simplified_display()
+                workspace_root.user_display()
             );
             Ok(None)
         };
@@ -706,8 +1040,155 @@ async fn find_workspace(
     Ok(None)
 }
 
-/// Warn when the valid workspace is included in another workspace.
-fn check_nested_workspaces(inner_workspace_root: &Path, stop_discovery_at: Option<&Path>) {
+/// Check that a project without a workspace isn't matched by the `members` of a workspace above
+/// it.
+///
+/// A project can be detached from a workspace that lists it as a member, e.g., when it's nested
+/// in another project below the workspace root, or when a glob doesn't match what the user
+/// intended. Projects excluded explicitly, through `exclude` or a negated member glob, are fine.
+fn check_above(
+    project_root: &Path,
+    project_name: &PackageName,
+    stop_discovery_at: Option<&Path>,
+) -> Result<(), WorkspaceError> {
+    for workspace_root in project_root
+        .ancestors()
+        .take_while(|path| {
+            // Only walk up the given directory, if any.
+            stop_discovery_at
+                .map(|stop_discovery_at| stop_discovery_at != *path)
+                .unwrap_or(true)
+        })
+        .skip(1)
+    {
+        let pyproject_toml_path = workspace_root.join("pyproject.toml");
+        if !pyproject_toml_path.is_file() {
+            continue;
+        }
+        // Invalid `pyproject.toml` files are reported when discovering their own workspace.
+        let Ok(contents) = fs_err::read_to_string(&pyproject_toml_path) else {
+            continue;
+        };
+        let Ok(pyproject_toml) = toml::from_str::<PyProjectToml>(&contents) else {
+            continue;
+        };
+        let Some(workspace) = pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.workspace.as_ref())
+        else {
+            continue;
+        };
+
+        // Nested workspaces aren't supported, so only the first workspace above is relevant.
+        let is_detached = !is_excluded_by_name(project_name, workspace)
+            && !is_excluded_from_workspace(project_root, workspace_root, workspace)?
+            && expand_member_globs(workspace_root, workspace)?
+                .iter()
+                .any(|(member_root, _)| member_root == project_root);
+        if is_detached {
+            let err = WorkspaceError::DetachedMember {
+                project: project_root.to_path_buf(),
+                workspace: workspace_root.to_path_buf(),
+            };
+            match workspace.detached_members.unwrap_or_default() {
+                DetachedMembers::Warn => warn_user!("{err}"),
+                DetachedMembers::Error => return Err(err),
+            }
+        }
+        return Ok(());
+    }
+
+    Ok(())
+}
+
+/// Check that the `requires-python` of each workspace member overlaps with the `requires-python`
+/// of the workspace root.
+///
+/// Otherwise, no Python version can satisfy the entire workspace, and resolution would fail later
+/// with a confusing conflict.
+fn check_requires_python(
+    workspace_root: &Path,
+    workspace_pyproject_toml: &PyProjectToml,
+    members: &BTreeMap<PackageName, WorkspaceMember>,
+    policy: DisjointRequiresPython,
+) -> Result<(), WorkspaceError> {
+    // Virtual workspace roots don't have a `requires-python`.
+    let Some(root_requires_python) = workspace_pyproject_toml
+        .project
+        .as_ref()
+        .and_then(|project| project.requires_python.as_ref())
+    else {
+        return Ok(());
+    };
+
+    for member in members.values() {
+        if member.root == workspace_root {
+            continue;
+        }
+        let Some(member_requires_python) = member.project.requires_python.as_ref() else {
+            continue;
+        };
+        if !is_disjoint(root_requires_python, member_requires_python) {
+            continue;
+        }
+
+        let err = WorkspaceError::DisjointRequiresPython {
+            member: member.root.join("pyproject.toml"),
+            member_requires_python: member_requires_python.clone(),
+            root: workspace_root.join("pyproject.toml"),
+            root_requires_python: root_requires_python.clone(),
+        };
+        match policy {
+            DisjointRequiresPython::Warn => warn_user!("{err}"),
+            DisjointRequiresPython::Error => return Err(err),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if no version satisfies both sets of specifiers.
+///
+/// The bounds of the intersection of two sets of specifiers are given by the versions in the
+/// specifiers, so it suffices to check these versions, the versions directly above them, and a
+/// version below all of them.
+fn is_disjoint(left: &VersionSpecifiers, right: &VersionSpecifiers) -> bool {
+    let candidates = left
+        .iter()
+        .chain(right.iter())
+        .flat_map(|specifier| {
+            let version = specifier.version();
+            let release = version.release();
+            let above = if matches!(
+                specifier.operator(),
+                Operator::EqualStar | Operator::NotEqualStar
+            ) {
+                // For a prefix like `3.8.*`, the next prefix, e.g., `3.9`.
+                let (last, rest) = release.split_last().unwrap_or((&0, &[]));
+                Version::new(rest.iter().copied().chain([last + 1]))
+            } else {
+                // A version above `version` that is not a post-release of it, e.g., `3.8.0.0.1`.
+                Version::new(release.iter().copied().chain([0, 0, 1]))
+            };
+            [version.clone(), above.with_epoch(version.epoch())]
+        })
+        .chain([Version::new([0])]);
+
+    !candidates.any(|candidate| left.contains(&candidate) && right.contains(&candidate))
+}
+
+/// Reject a workspace that is a member of another workspace, and warn when it is below another
+/// workspace without being excluded from it.
+///
+/// Discovering the outer workspace rejects members that declare their own `tool.uv.workspace`;
+/// this applies the same rule when discovery starts inside the inner workspace.
+fn check_nested_workspaces(
+    inner_workspace_root: &Path,
+    inner_workspace_name: Option<&PackageName>,
+    stop_discovery_at: Option<&Path>,
+) -> Result<(), WorkspaceError> {
     for outer_workspace_root in inner_workspace_root
         .ancestors()
         .take_while(|path| {
@@ -730,7 +1211,7 @@ fn check_nested_workspaces(inner_workspace_root: &Path, stop_discovery_at: Optio
                     pyproject_toml_path.user_display(),
                     err
                 );
-                return;
+                return Ok(());
             }
         };
         let pyproject_toml: PyProjectToml = match toml::from_str(&contents) {
@@ -741,7 +1222,7 @@ fn check_nested_workspaces(inner_workspace_root: &Path, stop_discovery_at: Optio
                     pyproject_toml_path.user_display(),
                     err
                 );
-                return;
+                return Ok(());
             }
         };
 
@@ -763,9 +1244,30 @@ fn check_nested_workspaces(inner_workspace_root: &Path, stop_discovery_at: Optio
                         pyproject_toml_path.user_display(),
                         err
                     );
-                    return;
+                    return Ok(());
                 }
             };
+            let is_excluded = is_excluded
+                || inner_workspace_name.is_some_and(|name| is_excluded_by_name(name, workspace));
+            let is_member = match expand_member_globs(outer_workspace_root, workspace) {
+                Ok(member_roots) => member_roots
+                    .iter()
+                    .any(|(member_root, _)| member_root == inner_workspace_root),
+                Err(err) => {
+                    warn_user!(
+                        "Invalid pyproject.toml `{}`: {}",
+                        pyproject_toml_path.user_display(),
+                        err
+                    );
+                    return Ok(());
+                }
+            };
+            if is_member && !is_excluded {
+                return Err(WorkspaceError::NestedWorkspace {
+                    inner: inner_workspace_root.to_path_buf(),
+                    outer: outer_workspace_root.to_path_buf(),
+                });
+            }
             if !is_excluded {
                 warn_user!(
                     "Outer workspace including existing workspace, nested workspaces are not supported: `{}`",
@@ -775,43 +1277,266 @@ fn check_nested_workspaces(inner_workspace_root: &Path, stop_discovery_at: Optio
         }
 
         // We're in the examples or tests of another project (not a workspace), this is fine.
-        return;
+        return Ok(());
     }
+    Ok(())
+}
+
+/// Check if the package is excluded from the workspace by name, i.e., if an entry of
+/// `tool.uv.workspace.exclude` is its package name rather than a path glob.
+fn is_excluded_by_name(project_name: &PackageName, workspace: &ToolUvWorkspace) -> bool {
+    workspace
+        .exclude
+        .iter()
+        .flatten()
+        .filter_map(|exclude| PackageName::from_str(exclude.as_str()).ok())
+        .any(|name| name == *project_name)
 }
 
-/// Check if we're in the `tool.uv.workspace.excluded` of a workspace.
+/// Check if we're in the `tool.uv.workspace.excluded` of a workspace, or removed from its
+/// members by a negated glob.
 fn is_excluded_from_workspace(
     project_path: &Path,
     workspace_root: &Path,
     workspace: &ToolUvWorkspace,
 ) -> Result<bool, WorkspaceError> {
     for exclude_glob in workspace.exclude.iter().flatten() {
-        let absolute_glob = workspace_root
-            .simplified()
-            .join(exclude_glob.as_str())
-            .to_string_lossy()
-            .to_string();
-        for excluded_root in glob(&absolute_glob)
-            .map_err(|err| WorkspaceError::Pattern(absolute_glob.to_string(), err))?
+        if expand_glob(workspace_root, exclude_glob.as_str())?
+            .iter()
+            .any(|excluded_root| excluded_root == project_path)
         {
-            let excluded_root = excluded_root
-                .map_err(|err| WorkspaceError::Glob(absolute_glob.to_string(), err))?;
-            if excluded_root == project_path {
-                return Ok(true);
-            }
+            return Ok(true);
+        }
+    }
+
+    // The project is also excluded if the last `members` glob matching it is negated.
+    let mut negated = false;
+    for member_glob in workspace.members.iter().flatten() {
+        let pattern = member_glob
+            .negated()
+            .unwrap_or_else(|| member_glob.glob().as_str());
+        if expand_glob(workspace_root, pattern)?
+            .iter()
+            .any(|member_root| member_root == project_path)
+        {
+            negated = member_glob.negated().is_some();
         }
     }
-    Ok(false)
+    Ok(negated)
 }
 
 #[cfg(test)]
 #[cfg(unix)] // Avoid path escaping for the unit tests
 mod tests {
     use std::env;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
 
+    use indoc::formatdoc;
     use insta::assert_json_snapshot;
+    use tempfile::TempDir;
+
+    use uv_normalize::PackageName;
 
-    use crate::workspace::ProjectWorkspace;
+    use pep440_rs::VersionSpecifiers;
+    use pypi_types::RequirementSource;
+
+    use crate::workspace::{is_disjoint, ProjectWorkspace, WorkspaceError, WorkspaceFingerprint};
+
+    /// A declarative description of a workspace tree, written to a temporary directory.
+    ///
+    /// Use this instead of adding fixtures to `scripts/workspaces` for discovery regression tests.
+    #[derive(Debug, Default)]
+    struct TestWorkspace {
+        /// The `pyproject.toml` files to write, by directory relative to the root.
+        projects: Vec<(PathBuf, TestProject)>,
+        /// Directories to create without a `pyproject.toml`.
+        directories: Vec<PathBuf>,
+        /// Symlinks to create, as `(link, target)` relative to the root.
+        symlinks: Vec<(PathBuf, PathBuf)>,
+    }
+
+    /// A `pyproject.toml` in a [`TestWorkspace`].
+    #[derive(Debug, Default)]
+    struct TestProject {
+        /// The `project.name`, if any. Without a name, the `pyproject.toml` has no `[project]`.
+        name: Option<String>,
+        /// The `tool.uv.workspace.members`, if the project is a workspace root.
+        members: Option<Vec<String>>,
+        /// Conditional `tool.uv.workspace.members`, as inline TOML tables.
+        conditional_members: Vec<String>,
+        /// The `tool.uv.workspace.exclude` globs.
+        exclude: Vec<String>,
+        /// The `tool.uv.sources`, as a package name and an inline TOML table.
+        sources: Vec<(String, String)>,
+        /// The `project.requires-python`, defaults to `>=3.12`.
+        requires_python: Option<String>,
+        /// The `tool.uv.workspace.disjoint-requires-python` policy.
+        disjoint_requires_python: Option<String>,
+        /// The extras in `project.optional-dependencies`, each without any dependencies.
+        extras: Vec<String>,
+        /// The `tool.uv.workspace.detached-members` policy.
+        detached_members: Option<String>,
+    }
+
+    impl TestProject {
+        /// A project with the given name.
+        fn new(name: &str) -> Self {
+            Self {
+                name: Some(name.to_string()),
+                ..Self::default()
+            }
+        }
+
+        /// A virtual workspace root, i.e., a `pyproject.toml` without a `[project]`.
+        fn virtual_root(members: &[&str]) -> Self {
+            Self::default().members(members)
+        }
+
+        /// Declare the project as a workspace root with the given member globs.
+        fn members(mut self, members: &[&str]) -> Self {
+            self.members = Some(members.iter().map(ToString::to_string).collect());
+            self
+        }
+
+        /// Add a conditional member, e.g., `{ path = "gpu/*", env = "WITH_CUDA" }`.
+        fn conditional_member(mut self, member: &str) -> Self {
+            self.conditional_members.push(member.to_string());
+            self
+        }
+
+        /// Exclude the given globs from the workspace.
+        fn exclude(mut self, exclude: &[&str]) -> Self {
+            self.exclude = exclude.iter().map(ToString::to_string).collect();
+            self
+        }
+
+        /// Add a `tool.uv.sources` entry, e.g., `source("bird-feeder", "{ workspace = true }")`.
+        fn source(mut self, package: &str, source: &str) -> Self {
+            self.sources.push((package.to_string(), source.to_string()));
+            self
+        }
+
+        /// Set the `project.requires-python`.
+        fn requires_python(mut self, requires_python: &str) -> Self {
+            self.requires_python = Some(requires_python.to_string());
+            self
+        }
+
+        /// Set the `tool.uv.workspace.disjoint-requires-python` policy.
+        fn disjoint_requires_python(mut self, policy: &str) -> Self {
+            self.disjoint_requires_python = Some(policy.to_string());
+            self
+        }
+
+        /// Set the `tool.uv.workspace.detached-members` policy.
+        fn detached_members(mut self, policy: &str) -> Self {
+            self.detached_members = Some(policy.to_string());
+            self
+        }
+
+        /// Add (empty) extras to `project.optional-dependencies`.
+        fn extras(mut self, extras: &[&str]) -> Self {
+            self.extras = extras.iter().map(ToString::to_string).collect();
+            self
+        }
+
+        fn to_toml(&self) -> String {
+            let mut contents = String::new();
+            if let Some(name) = &self.name {
+                contents.push_str(&formatdoc! {r#"
+                    [project]
+                    name = "{name}"
+                    requires-python = "{requires_python}"
+                "#, requires_python = self.requires_python.as_deref().unwrap_or(">=3.12")});
+                if !self.extras.is_empty() {
+                    contents.push_str("[project.optional-dependencies]\n");
+                    for extra in &self.extras {
+                        contents.push_str(&format!("{extra} = []\n"));
+                    }
+                }
+            }
+            if let Some(members) = &self.members {
+                let members = members
+                    .iter()
+                    .map(|member| format!("{member:?}"))
+                    .chain(self.conditional_members.iter().cloned())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                contents.push_str(&formatdoc! {r#"
+                    [tool.uv.workspace]
+                    members = [{members}]
+                    exclude = {exclude:?}
+                "#, exclude = self.exclude});
+                if let Some(policy) = &self.disjoint_requires_python {
+                    contents.push_str(&format!("disjoint-requires-python = \"{policy}\"\n"));
+                }
+                if let Some(policy) = &self.detached_members {
+                    contents.push_str(&format!("detached-members = \"{policy}\"\n"));
+                }
+            }
+            if !self.sources.is_empty() {
+                contents.push_str("[tool.uv.sources]\n");
+                for (package, source) in &self.sources {
+                    contents.push_str(&format!("{package} = {source}\n"));
+                }
+            }
+            contents
+        }
+    }
+
+    impl TestWorkspace {
+        /// Add a `pyproject.toml` in the given directory, relative to the root.
+        fn project(mut self, path: impl AsRef<Path>, project: TestProject) -> Self {
+            self.projects.push((path.as_ref().to_path_buf(), project));
+            self
+        }
+
+        /// Add a directory without a `pyproject.toml`.
+        fn directory(mut self, path: impl AsRef<Path>) -> Self {
+            self.directories.push(path.as_ref().to_path_buf());
+            self
+        }
+
+        /// Add a symlink at `link` pointing to `target`, both relative to the root.
+        fn symlink(mut self, link: impl AsRef<Path>, target: impl AsRef<Path>) -> Self {
+            self.symlinks
+                .push((link.as_ref().to_path_buf(), target.as_ref().to_path_buf()));
+            self
+        }
+
+        /// Write the workspace tree to a new temporary directory.
+        fn build(&self) -> std::io::Result<TempDir> {
+            let root = TempDir::new()?;
+            for directory in &self.directories {
+                fs_err::create_dir_all(root.path().join(directory))?;
+            }
+            for (path, project) in &self.projects {
+                let directory = root.path().join(path);
+                fs_err::create_dir_all(&directory)?;
+                fs_err::write(directory.join("pyproject.toml"), project.to_toml())?;
+            }
+            for (link, target) in &self.symlinks {
+                fs_err::os::unix::fs::symlink(root.path().join(target), root.path().join(link))?;
+            }
+            Ok(root)
+        }
+    }
+
+    /// Discover the project at `path` in a [`TestWorkspace`], returning the member names.
+    async fn discover_members(
+        root: &TempDir,
+        path: &str,
+    ) -> Result<(ProjectWorkspace, Vec<String>), WorkspaceError> {
+        let project = ProjectWorkspace::discover(&root.path().join(path), None).await?;
+        let members = project
+            .workspace()
+            .packages()
+            .keys()
+            .map(ToString::to_string)
+            .collect();
+        Ok((project, members))
+    }
 
     async fn workspace_test(folder: &str) -> (ProjectWorkspace, String) {
         let root_dir = env::current_dir()
@@ -1042,4 +1767,636 @@ mod tests {
             "###);
         });
     }
+
+    #[tokio::test]
+    async fn root_workspace_from_member() {
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross")
+                    .members(&["packages/*"])
+                    .source("bird-feeder", "{ workspace = true }"),
+            )
+            .project("packages/bird-feeder", TestProject::new("bird-feeder"))
+            .project("packages/seeds", TestProject::new("seeds"))
+            .build()
+            .unwrap();
+
+        let (project, members) = discover_members(&root, "packages/seeds").await.unwrap();
+        assert_eq!(project.project_name().as_ref(), "seeds");
+        assert_eq!(project.workspace().root(), &root.path().to_path_buf());
+        assert_eq!(members, vec!["albatross", "bird-feeder", "seeds"]);
+        assert_eq!(
+            project
+                .workspace()
+                .sources()
+                .keys()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["bird-feeder"]
+        );
+    }
+
+    #[tokio::test]
+    async fn excluded_from_virtual_workspace() {
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::virtual_root(&["packages/*"]).exclude(&["packages/seeds"]),
+            )
+            .project("packages/albatross", TestProject::new("albatross"))
+            .project("packages/seeds", TestProject::new("seeds"))
+            .build()
+            .unwrap();
+
+        // The excluded project is its own single project workspace.
+        let (project, members) = discover_members(&root, "packages/seeds").await.unwrap();
+        assert_eq!(
+            project.workspace().root(),
+            &root.path().join("packages").join("seeds")
+        );
+        assert_eq!(members, vec!["seeds"]);
+    }
+
+    #[tokio::test]
+    async fn excluded_by_name() {
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::virtual_root(&["packages/*"]).exclude(&["Seeds"]),
+            )
+            .project("packages/albatross", TestProject::new("albatross"))
+            .project("packages/seeds", TestProject::new("seeds"))
+            .build()
+            .unwrap();
+
+        let (project, members) = discover_members(&root, "packages/albatross").await.unwrap();
+        assert_eq!(project.workspace().root(), &root.path().to_path_buf());
+        assert_eq!(members, vec!["albatross"]);
+
+        // The excluded project is its own single project workspace.
+        let (project, members) = discover_members(&root, "packages/seeds").await.unwrap();
+        assert_eq!(
+            project.workspace().root(),
+            &root.path().join("packages").join("seeds")
+        );
+        assert_eq!(members, vec!["seeds"]);
+    }
+
+    #[tokio::test]
+    async fn nested_workspace() {
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("albatross").members(&["packages/*"]))
+            .project(
+                "packages/bird-feeder",
+                TestProject::new("bird-feeder").members(&["nested/*"]),
+            )
+            .project(
+                "packages/bird-feeder/nested/seeds",
+                TestProject::new("seeds"),
+            )
+            .build()
+            .unwrap();
+
+        // Discovering from inside the inner workspace is rejected, like discovering from the outer
+        // workspace.
+        for path in ["packages/bird-feeder/nested/seeds", "packages/bird-feeder"] {
+            let err = discover_members(&root, path).await.unwrap_err();
+            let WorkspaceError::NestedWorkspace { inner, outer } = err else {
+                panic!("Expected a nested workspace error, got: {err:?}");
+            };
+            assert_eq!(inner, root.path().join("packages").join("bird-feeder"));
+            assert_eq!(outer, root.path());
+        }
+
+        // An inner workspace that is excluded from the outer workspace is its own workspace.
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross")
+                    .members(&["packages/*"])
+                    .exclude(&["packages/bird-feeder"]),
+            )
+            .project(
+                "packages/bird-feeder",
+                TestProject::new("bird-feeder").members(&["nested/*"]),
+            )
+            .project(
+                "packages/bird-feeder/nested/seeds",
+                TestProject::new("seeds"),
+            )
+            .build()
+            .unwrap();
+        let (project, members) = discover_members(&root, "packages/bird-feeder/nested/seeds")
+            .await
+            .unwrap();
+        assert_eq!(
+            project.workspace().root(),
+            &root.path().join("packages").join("bird-feeder")
+        );
+        assert_eq!(members, vec!["bird-feeder", "seeds"]);
+    }
+
+    #[tokio::test]
+    async fn nested_workspace_member() {
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("albatross").members(&["packages/*"]))
+            .project(
+                "packages/bird-feeder",
+                TestProject::new("bird-feeder").members(&["nested/*"]),
+            )
+            .project(
+                "packages/bird-feeder/nested/seeds",
+                TestProject::new("seeds"),
+            )
+            .build()
+            .unwrap();
+
+        let err = discover_members(&root, "").await.unwrap_err();
+        let WorkspaceError::NestedWorkspace { inner, outer } = err else {
+            panic!("Expected a nested workspace error, got: {err:?}");
+        };
+        assert_eq!(inner, root.path().join("packages").join("bird-feeder"));
+        assert_eq!(outer, root.path());
+    }
+
+    #[tokio::test]
+    async fn member_without_manifest() {
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("albatross").members(&["packages/*"]))
+            .project("packages/seeds", TestProject::new("seeds"))
+            .directory("packages/empty")
+            .build()
+            .unwrap();
+
+        let err = discover_members(&root, "").await.unwrap_err();
+        assert!(
+            matches!(err, WorkspaceError::Io(_)),
+            "Expected a missing `pyproject.toml` error, got: {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn symlinked_member() {
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("albatross").members(&["packages/*"]))
+            .project("elsewhere/seeds", TestProject::new("seeds"))
+            .directory("packages")
+            .symlink("packages/seeds", "elsewhere/seeds")
+            .build()
+            .unwrap();
+
+        let (project, members) = discover_members(&root, "").await.unwrap();
+        assert_eq!(members, vec!["albatross", "seeds"]);
+        assert_eq!(
+            project.workspace().packages()[&PackageName::from_str("seeds").unwrap()].root(),
+            &root.path().join("packages").join("seeds")
+        );
+    }
+
+    #[tokio::test]
+    async fn disjoint_requires_python() {
+        let workspace = |policy: Option<&str>| {
+            let mut root = TestProject::new("albatross")
+                .members(&["packages/*"])
+                .requires_python(">=3.12");
+            if let Some(policy) = policy {
+                root = root.disjoint_requires_python(policy);
+            }
+            TestWorkspace::default()
+                .project("", root)
+                .project(
+                    "packages/seeds",
+                    TestProject::new("seeds").requires_python(">=3.8,<3.11"),
+                )
+                .project(
+                    "packages/bird-feeder",
+                    TestProject::new("bird-feeder").requires_python(">=3.10"),
+                )
+                .build()
+                .unwrap()
+        };
+
+        // By default, disjoint members are only a warning.
+        let root = workspace(None);
+        let (_, members) = discover_members(&root, "").await.unwrap();
+        assert_eq!(members, vec!["albatross", "bird-feeder", "seeds"]);
+
+        let root = workspace(Some("error"));
+        let err = discover_members(&root, "").await.unwrap_err();
+        let WorkspaceError::DisjointRequiresPython {
+            member,
+            member_requires_python,
+            root: root_pyproject_toml,
+            root_requires_python,
+        } = err
+        else {
+            panic!("Expected a disjoint `requires-python` error, got: {err}");
+        };
+        assert_eq!(
+            member,
+            root.path()
+                .join("packages")
+                .join("seeds")
+                .join("pyproject.toml")
+        );
+        assert_eq!(member_requires_python.to_string(), ">=3.8, <3.11");
+        assert_eq!(root_pyproject_toml, root.path().join("pyproject.toml"));
+        assert_eq!(root_requires_python.to_string(), ">=3.12");
+    }
+
+    #[test]
+    fn requires_python_disjoint() {
+        let disjoint = |left: &str, right: &str| {
+            is_disjoint(
+                &VersionSpecifiers::from_str(left).unwrap(),
+                &VersionSpecifiers::from_str(right).unwrap(),
+            )
+        };
+        assert!(!disjoint(">=3.8", ">=3.12"));
+        assert!(!disjoint("<3.10", "<3.8"));
+        assert!(!disjoint(">=3.8,<=3.10", "==3.10"));
+        assert!(!disjoint(">=3.8", ">3.8"));
+        assert!(!disjoint(">=3.8,!=3.8.*", ">=3.8"));
+        assert!(!disjoint("~=3.11.2", "==3.11.*"));
+        assert!(disjoint(">=3.12", "<3.12"));
+        assert!(disjoint(">=3.8,<3.11", ">=3.12"));
+        assert!(disjoint("==3.10.*", "==3.11.*"));
+        assert!(disjoint(">=3.8,<3.9", "!=3.8.*"));
+        assert!(disjoint(">3.10", "<=3.10"));
+    }
+
+    #[tokio::test]
+    async fn conditional_members() {
+        // A variable that is unique to this test, since tests run concurrently.
+        let var = "UV_TEST_WORKSPACE_WITH_CUDA";
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross")
+                    .members(&["packages/*"])
+                    .conditional_member(&format!(r#"{{ path = "gpu/*", env = "{var}" }}"#))
+                    .conditional_member(
+                        r#"{ path = "linux/*", marker = "sys_platform == 'linux'" }"#,
+                    ),
+            )
+            .project("packages/seeds", TestProject::new("seeds"))
+            .project("gpu/cuda", TestProject::new("cuda"))
+            .project("linux/epoll", TestProject::new("epoll"))
+            .build()
+            .unwrap();
+
+        // Without the environment variable, the member is skipped.
+        let (project, members) = discover_members(&root, "").await.unwrap();
+        assert_eq!(members, vec!["albatross", "epoll", "seeds"]);
+
+        // Marker-conditional members are discovered, but only installed where the marker applies.
+        let markers = project
+            .workspace()
+            .members_as_requirements()
+            .into_iter()
+            .map(|requirement| {
+                (
+                    requirement.name.to_string(),
+                    requirement.marker.map(|marker| marker.to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            markers,
+            vec![
+                ("albatross".to_string(), None),
+                (
+                    "epoll".to_string(),
+                    Some("sys_platform == 'linux'".to_string())
+                ),
+                ("seeds".to_string(), None),
+            ]
+        );
+
+        env::set_var(var, "1");
+        let result = discover_members(&root, "").await;
+        env::remove_var(var);
+        let (_, members) = result.unwrap();
+        assert_eq!(members, vec!["albatross", "cuda", "epoll", "seeds"]);
+    }
+
+    #[tokio::test]
+    async fn fingerprint() {
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("albatross").members(&["packages/*"]))
+            .project("packages/seeds", TestProject::new("seeds"))
+            .build()
+            .unwrap();
+
+        let (project, _) = discover_members(&root, "").await.unwrap();
+        let workspace = project.workspace();
+        let fingerprint = workspace.fingerprint().clone();
+        assert_eq!(
+            WorkspaceFingerprint::from_root(workspace.root())
+                .await
+                .unwrap(),
+            fingerprint
+        );
+        assert!(!fingerprint.has_changed(workspace.root()).await.unwrap());
+
+        // Adding a member changes the fingerprint.
+        let member = root.path().join("packages").join("feathers");
+        fs_err::create_dir_all(&member).unwrap();
+        fs_err::write(
+            member.join("pyproject.toml"),
+            TestProject::new("feathers").to_toml(),
+        )
+        .unwrap();
+        assert!(fingerprint.has_changed(workspace.root()).await.unwrap());
+
+        // A fresh discovery agrees with the new fingerprint.
+        let (project, _) = discover_members(&root, "").await.unwrap();
+        assert!(!project
+            .workspace()
+            .fingerprint()
+            .has_changed(workspace.root())
+            .await
+            .unwrap());
+
+        // Implicit single project workspaces have a fingerprint as well.
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("bird-feeder"))
+            .build()
+            .unwrap();
+        let (project, _) = discover_members(&root, "").await.unwrap();
+        assert!(!project
+            .workspace()
+            .fingerprint()
+            .has_changed(project.workspace().root())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn editable_requirements() {
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross")
+                    .members(&["packages/*"])
+                    .extras(&["wings", "beak"]),
+            )
+            .project("packages/seeds", TestProject::new("seeds"))
+            .project("packages/bird-feeder", TestProject::new("bird-feeder"))
+            .build()
+            .unwrap();
+
+        // Member roots are given relative to the current project.
+        let (project, _) = discover_members(&root, "packages/seeds").await.unwrap();
+        let requirements = project
+            .members_as_editable_requirements()
+            .into_iter()
+            .map(|requirement| {
+                let RequirementSource::Path {
+                    path,
+                    editable,
+                    url,
+                } = requirement.source
+                else {
+                    panic!("expected a path requirement: {requirement}");
+                };
+                assert!(editable);
+                assert!(path.is_absolute());
+                (
+                    requirement.name.to_string(),
+                    requirement
+                        .extras
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>(),
+                    url.given().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            requirements,
+            vec![
+                (
+                    "albatross".to_string(),
+                    vec!["beak".to_string(), "wings".to_string()],
+                    "../..".to_string()
+                ),
+                (
+                    "bird-feeder".to_string(),
+                    vec![],
+                    "../bird-feeder".to_string()
+                ),
+                ("seeds".to_string(), vec![], ".".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn source_hashes() {
+        let wheel = "https://example.com/wheels/seeds-1.0.0-py3-none-any.whl";
+        let sdist = "https://example.com/sdists/feathers-1.0.0.tar.gz";
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross")
+                    .members(&["packages/*"])
+                    .source("seeds", &format!(r#"{{ url = "{wheel}", hash = "sha256:c0ffee" }}"#)),
+            )
+            .project(
+                "packages/bird-feeder",
+                TestProject::new("bird-feeder")
+                    .source(
+                        "feathers",
+                        &format!(
+                            r#"{{ url = "{sdist}", subdirectory = "python", hash = "sha512:f00d" }}"#
+                        ),
+                    )
+                    .source("anyio", r#"{ url = "https://example.com/anyio-4.0.0.tar.gz" }"#),
+            )
+            .build()
+            .unwrap();
+
+        let (project, _) = discover_members(&root, "").await.unwrap();
+        let hashes = project.workspace().source_hashes().unwrap();
+        let mut hashes = hashes
+            .into_iter()
+            .map(|(id, digests)| {
+                (
+                    id.to_string(),
+                    digests.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        hashes.sort();
+        assert_eq!(
+            hashes,
+            vec![
+                (
+                    format!("{sdist}#subdirectory=python"),
+                    vec!["sha512:f00d".to_string()]
+                ),
+                (wheel.to_string(), vec!["sha256:c0ffee".to_string()]),
+            ]
+        );
+
+        // Invalid hashes are rejected.
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross").source(
+                    "seeds",
+                    &format!(r#"{{ url = "{wheel}", hash = "c0ffee" }}"#),
+                ),
+            )
+            .build()
+            .unwrap();
+        let (project, _) = discover_members(&root, "").await.unwrap();
+        assert!(matches!(
+            project.workspace().source_hashes(),
+            Err(WorkspaceError::SourceHash(name, _)) if name.as_ref() == "seeds"
+        ));
+    }
+
+    #[tokio::test]
+    async fn negated_members() {
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross").members(&[
+                    "packages/*",
+                    "!packages/experimental-*",
+                    "packages/experimental-stable",
+                ]),
+            )
+            .project("packages/seeds", TestProject::new("seeds"))
+            .project("packages/experimental-wings", TestProject::new("wings"))
+            .project("packages/experimental-stable", TestProject::new("stable"))
+            .build()
+            .unwrap();
+
+        // Negated globs remove the members matched before them, later globs can add them again.
+        let (_, members) = discover_members(&root, "").await.unwrap();
+        assert_eq!(members, vec!["albatross", "seeds", "stable"]);
+
+        // A negated project is not part of the workspace.
+        let (project, members) = discover_members(&root, "packages/experimental-wings")
+            .await
+            .unwrap();
+        assert_eq!(members, vec!["wings"]);
+        assert_eq!(
+            project.workspace().root(),
+            &root.path().join("packages").join("experimental-wings")
+        );
+        let (project, _) = discover_members(&root, "packages/experimental-stable")
+            .await
+            .unwrap();
+        assert_eq!(project.workspace().root(), root.path());
+
+        // Negations can't be conditional.
+        let root = TestWorkspace::default()
+            .project(
+                "",
+                TestProject::new("albatross")
+                    .members(&["packages/*"])
+                    .conditional_member(r#"{ path = "!packages/seeds", env = "NO_SEEDS" }"#),
+            )
+            .project("packages/seeds", TestProject::new("seeds"))
+            .build()
+            .unwrap();
+        let err = discover_members(&root, "").await.unwrap_err();
+        assert!(
+            matches!(err, WorkspaceError::ConditionalNegation(glob) if glob == "!packages/seeds")
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_member() {
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("albatross").members(&["packages/*"]))
+            .project("packages/seeds", TestProject::new("seeds"))
+            .project("packages/seeds-v2", TestProject::new("seeds"))
+            .build()
+            .unwrap();
+
+        let err = discover_members(&root, "").await.unwrap_err();
+        let WorkspaceError::DuplicatePackage {
+            name,
+            first,
+            second,
+        } = err
+        else {
+            panic!("expected a duplicate package error: {err}");
+        };
+        assert_eq!(name.as_ref(), "seeds");
+        assert_eq!(first, root.path().join("packages").join("seeds"));
+        assert_eq!(second, root.path().join("packages").join("seeds-v2"));
+
+        // A member can't reuse the name of the workspace root either.
+        let root = TestWorkspace::default()
+            .project("", TestProject::new("albatross").members(&["packages/*"]))
+            .project("packages/albatross", TestProject::new("albatross"))
+            .build()
+            .unwrap();
+        let err = discover_members(&root, "").await.unwrap_err();
+        assert!(
+            matches!(err, WorkspaceError::DuplicatePackage { .. }),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn detached_member() {
+        let workspace = |root: TestProject| {
+            TestWorkspace::default()
+                .project("", root)
+                .project("packages/seeds", TestProject::new("seeds"))
+                .project("packages/seeds/examples/demo", TestProject::new("demo"))
+                .build()
+                .unwrap()
+        };
+        let members = ["packages/*", "packages/seeds/examples/*"];
+
+        // The example is matched by the workspace, but it's nested in another project, so it's
+        // detached from the workspace.
+        let root = workspace(TestProject::new("albatross").members(&members));
+        let (project, members_of_demo) = discover_members(&root, "packages/seeds/examples/demo")
+            .await
+            .unwrap();
+        assert_eq!(members_of_demo, vec!["demo"]);
+        assert_eq!(
+            project.workspace().root(),
+            &root.path().join("packages/seeds/examples/demo")
+        );
+
+        let root = workspace(
+            TestProject::new("albatross")
+                .members(&members)
+                .detached_members("error"),
+        );
+        let err = discover_members(&root, "packages/seeds/examples/demo")
+            .await
+            .unwrap_err();
+        let WorkspaceError::DetachedMember {
+            project,
+            workspace: workspace_root,
+        } = err
+        else {
+            panic!("expected a detached member error: {err}");
+        };
+        assert_eq!(project, root.path().join("packages/seeds/examples/demo"));
+        assert_eq!(workspace_root, root.path());
+
+        // Excluding the project explicitly is fine.
+        let root = workspace(
+            TestProject::new("albatross")
+                .members(&members)
+                .exclude(&["packages/seeds/examples/*"])
+                .detached_members("error"),
+        );
+        let (_, members_of_demo) = discover_members(&root, "packages/seeds/examples/demo")
+            .await
+            .unwrap();
+        assert_eq!(members_of_demo, vec!["demo"]);
+    }
 }
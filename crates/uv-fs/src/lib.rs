@@ -1,5 +1,7 @@
 use std::fmt::Display;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use fs2::FileExt;
 use tempfile::NamedTempFile;
@@ -23,8 +25,6 @@ mod path;
 /// If the file path is `-`, then contents are read from stdin instead.
 #[cfg(feature = "tokio")]
 pub async fn read_to_string_transcode(path: impl AsRef<Path>) -> std::io::Result<String> {
-    use std::io::Read;
-
     use encoding_rs_io::DecodeReaderBytes;
 
     let path = path.as_ref();
@@ -246,25 +246,35 @@ pub fn files(path: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
 }
 
 /// A file lock that is automatically released when dropped.
+///
+/// The holder of the lock records its process ID in the lock file, such that processes waiting
+/// for the lock can report which process holds it.
 #[derive(Debug)]
 pub struct LockedFile(fs_err::File);
 
 impl LockedFile {
+    /// The initial interval between attempts to acquire a contended lock with a timeout.
+    const INITIAL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    /// The maximum interval between attempts to acquire a contended lock with a timeout.
+    const MAX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
     pub fn acquire(path: impl AsRef<Path>, resource: impl Display) -> Result<Self, std::io::Error> {
-        let file = fs_err::File::create(path.as_ref())?;
+        let file = Self::open(path.as_ref())?;
         trace!("Checking lock for `{resource}`");
         match file.file().try_lock_exclusive() {
             Ok(()) => {
                 debug!("Acquired lock for `{resource}`");
-                Ok(Self(file))
+                Ok(Self::record_holder(file))
             }
             Err(err) => {
                 // Log error code and enum kind to help debugging more exotic failures
                 debug!("Try lock error, waiting for exclusive lock: {:?}", err);
                 warn_user!(
-                    "Waiting to acquire lock for {} (lockfile: {})",
+                    "Waiting to acquire lock for {} (lockfile: {}{})",
                     resource,
                     path.user_display(),
+                    held_by(&file),
                 );
                 file.file().lock_exclusive().map_err(|err| {
                     // Not an fs_err method, we need to build our own path context
@@ -273,10 +283,109 @@ impl LockedFile {
                         format!("Could not lock {}: {}", path.as_ref().user_display(), err),
                     )
                 })?;
-                Ok(Self(file))
+                Ok(Self::record_holder(file))
+            }
+        }
+    }
+
+    /// Acquire the lock at the given path, waiting for at most the given timeout.
+    ///
+    /// Stale locks are never broken: the lock is held by the operating system, which releases it
+    /// when the holder exits (or, if the lock file was inherited by a subprocess, once all
+    /// processes sharing it have exited).
+    ///
+    /// Returns an error of kind [`std::io::ErrorKind::TimedOut`] if the lock could not be acquired
+    /// within the timeout.
+    pub fn acquire_with_timeout(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+        timeout: Duration,
+    ) -> Result<Self, std::io::Error> {
+        let file = Self::open(path.as_ref())?;
+        let start = Instant::now();
+        let mut interval = Self::INITIAL_POLL_INTERVAL;
+        let mut warned = false;
+        trace!("Checking lock for `{resource}`");
+        loop {
+            let err = match file.file().try_lock_exclusive() {
+                Ok(()) => {
+                    debug!("Acquired lock for `{resource}`");
+                    return Ok(Self::record_holder(file));
+                }
+                Err(err) => err,
+            };
+
+            if !warned {
+                // Log error code and enum kind to help debugging more exotic failures
+                debug!("Try lock error, waiting for exclusive lock: {:?}", err);
+                warn_user!(
+                    "Waiting to acquire lock for {} (lockfile: {}{})",
+                    resource,
+                    path.user_display(),
+                    held_by(&file),
+                );
+                warned = true;
             }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!(
+                        "Timed out after {:.1}s waiting to acquire lock for {resource} (lockfile: {}{})",
+                        timeout.as_secs_f32(),
+                        path.user_display(),
+                        held_by(&file),
+                    ),
+                ));
+            }
+            std::thread::sleep(interval.min(timeout - elapsed));
+            interval = (interval * 2).min(Self::MAX_POLL_INTERVAL);
         }
     }
+
+    /// Open the lock file at the given path, creating it if necessary.
+    ///
+    /// The file isn't truncated, since it contains the process ID of the current holder.
+    fn open(path: &Path) -> Result<fs_err::File, std::io::Error> {
+        fs_err::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+    }
+
+    /// Record the current process as the holder of the lock.
+    fn record_holder(mut file: fs_err::File) -> Self {
+        let result = file
+            .set_len(0)
+            .and_then(|()| file.write_all(std::process::id().to_string().as_bytes()));
+        if let Err(err) = result {
+            // The process ID is only used for diagnostics.
+            debug!(
+                "Failed to record process ID in lockfile {}: {err}",
+                file.path().user_display()
+            );
+        }
+        Self(file)
+    }
+}
+
+/// Describe the holder of a lock as recorded in the lock file, e.g., `, held by process 42`, or
+/// return an empty string if it's unknown.
+///
+/// On Windows, the lock prevents other processes from reading the file, so the holder is unknown.
+fn held_by(file: &fs_err::File) -> String {
+    let mut contents = String::new();
+    let mut file = file;
+    if file.seek(SeekFrom::Start(0)).is_err() || file.read_to_string(&mut contents).is_err() {
+        return String::new();
+    }
+    match contents.trim().parse::<u32>() {
+        Ok(pid) => format!(", held by process {pid}"),
+        Err(_) => String::new(),
+    }
 }
 
 impl Drop for LockedFile {
@@ -290,3 +399,32 @@ impl Drop for LockedFile {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::LockedFile;
+
+    #[test]
+    fn lock_timeout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(".lock");
+
+        let lock = LockedFile::acquire(&path, "test").unwrap();
+        let err = LockedFile::acquire_with_timeout(&path, "test", Duration::from_millis(100))
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        if cfg!(unix) {
+            // The holder is reported.
+            assert!(
+                err.to_string()
+                    .contains(&format!("held by process {}", std::process::id())),
+                "{err}"
+            );
+        }
+
+        drop(lock);
+        LockedFile::acquire_with_timeout(&path, "test", Duration::ZERO).unwrap();
+    }
+}
@@ -17,6 +17,18 @@ pub static CANONICAL_CWD: Lazy<PathBuf> = Lazy::new(|| {
         .expect("The current directory must be canonicalized")
 });
 
+/// How [`Simplified::user_display_with`] renders paths.
+///
+/// Errors and warnings are always rendered for humans, with [`Simplified::user_display`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PathDisplayMode {
+    /// Render paths relative to the current working directory, where possible.
+    #[default]
+    Human,
+    /// Render absolute paths, for output that is consumed by scripts.
+    Porcelain,
+}
+
 pub trait Simplified {
     /// Simplify a [`Path`].
     ///
@@ -37,6 +49,12 @@ pub trait Simplified {
     /// Like [`simplified_display`], but relativizes the path against the current working directory.
     fn user_display(&self) -> impl std::fmt::Display;
 
+    /// Render a [`Path`] for display in the given [`PathDisplayMode`].
+    ///
+    /// In [`PathDisplayMode::Human`], this is equivalent to [`user_display`]. In
+    /// [`PathDisplayMode::Porcelain`], the simplified absolute path is rendered instead.
+    fn user_display_with(&self, mode: PathDisplayMode) -> impl std::fmt::Display;
+
     /// Render a [`Path`] for user-facing display, where the [`Path`] is relative to a base path.
     ///
     /// If the [`Path`] is not relative to the base path, will attempt to relativize the path
@@ -75,6 +93,20 @@ impl<T: AsRef<Path>> Simplified for T {
         path.display()
     }
 
+    fn user_display_with(&self, mode: PathDisplayMode) -> impl std::fmt::Display {
+        match mode {
+            PathDisplayMode::Human => Either::Left(self.user_display()),
+            PathDisplayMode::Porcelain => {
+                let path = dunce::simplified(self.as_ref());
+                if path.is_absolute() {
+                    Either::Right(path.display().to_string())
+                } else {
+                    Either::Right(CWD.simplified().join(path).display().to_string())
+                }
+            }
+        }
+    }
+
     fn user_display_from(&self, base: impl AsRef<Path>) -> impl std::fmt::Display {
         let path = dunce::simplified(self.as_ref());
 
@@ -363,6 +395,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_porcelain_display() {
+        assert_eq!(
+            Path::new("foo/bar")
+                .user_display_with(PathDisplayMode::Porcelain)
+                .to_string(),
+            CWD.simplified().join("foo/bar").display().to_string()
+        );
+        let absolute = CWD.simplified().join("foo");
+        assert_eq!(
+            absolute
+                .user_display_with(PathDisplayMode::Porcelain)
+                .to_string(),
+            absolute.display().to_string()
+        );
+        assert_eq!(
+            absolute
+                .user_display_with(PathDisplayMode::Human)
+                .to_string(),
+            "foo"
+        );
+    }
+
     #[test]
     fn test_normalize_path() {
         let path = Path::new("/a/b/../c/./d");
@@ -1,9 +1,8 @@
+use std::hash::BuildHasherDefault;
 use std::iter::Flatten;
 use std::path::PathBuf;
-use std::{collections::BTreeSet, hash::BuildHasherDefault};
 
 use anyhow::{Context, Result};
-use fs_err as fs;
 use rustc_hash::{FxHashMap, FxHashSet};
 use url::Url;
 
@@ -43,64 +42,26 @@ impl SitePackages {
         let mut by_name = FxHashMap::default();
         let mut by_url = FxHashMap::default();
 
-        for site_packages in venv.site_packages() {
-            // Read the site-packages directory.
-            let site_packages = match fs::read_dir(site_packages) {
-                Ok(site_packages) => {
-                    // Collect sorted directory paths; `read_dir` is not stable across platforms
-                    let dist_likes: BTreeSet<_> = site_packages
-                        .filter_map(|read_dir| match read_dir {
-                            Ok(entry) => match entry.file_type() {
-                                Ok(file_type) => (file_type.is_dir()
-                                    || entry.path().extension().map_or(false, |ext| {
-                                        ext == "egg-link" || ext == "egg-info"
-                                    }))
-                                .then_some(Ok(entry.path())),
-                                Err(err) => Some(Err(err)),
-                            },
-                            Err(err) => Some(Err(err)),
-                        })
-                        .collect::<Result<_, std::io::Error>>()?;
-                    dist_likes
-                }
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                    return Ok(Self {
-                        venv: venv.clone(),
-                        distributions,
-                        by_name,
-                        by_url,
-                    });
-                }
-                Err(err) => return Err(err).context("Failed to read site-packages directory"),
-            };
+        // Index all installed packages by name.
+        for dist_info in venv.installed_distributions()? {
+            let idx = distributions.len();
 
-            // Index all installed packages by name.
-            for path in site_packages {
-                let Some(dist_info) = InstalledDist::try_from_path(&path)
-                    .with_context(|| format!("Failed to read metadata: from {}", path.display()))?
-                else {
-                    continue;
-                };
+            // Index the distribution by name.
+            by_name
+                .entry(dist_info.name().clone())
+                .or_insert_with(Vec::new)
+                .push(idx);
 
-                let idx = distributions.len();
-
-                // Index the distribution by name.
-                by_name
-                    .entry(dist_info.name().clone())
+            // Index the distribution by URL.
+            if let InstalledDist::Url(dist) = &dist_info {
+                by_url
+                    .entry(dist.url.clone())
                     .or_insert_with(Vec::new)
                     .push(idx);
-
-                // Index the distribution by URL.
-                if let InstalledDist::Url(dist) = &dist_info {
-                    by_url
-                        .entry(dist.url.clone())
-                        .or_insert_with(Vec::new)
-                        .push(idx);
-                }
-
-                // Add the distribution to the database.
-                distributions.push(Some(dist_info));
             }
+
+            // Add the distribution to the database.
+            distributions.push(Some(dist_info));
         }
 
         Ok(Self {
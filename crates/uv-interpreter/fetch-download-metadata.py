@@ -98,12 +98,16 @@ def parse_filename(filename):
     version, triple = match.groups()
     if triple.endswith("-full"):
         triple = triple[:-5]
+    # Free-threaded builds prefix the flavor, e.g., `freethreaded+pgo+lto`
+    freethreaded = "-freethreaded" in triple
+    if freethreaded:
+        triple = triple.replace("-freethreaded+", "-").replace("-freethreaded", "")
     match = _suffix_re.match(triple)
     if match is not None:
         triple, suffix = match.groups()
     else:
         suffix = None
-    return (version, triple, suffix)
+    return (version, triple, suffix, freethreaded)
 
 
 def normalize_triple(triple):
@@ -196,17 +200,21 @@ def find():
                 info = parse_filename(base_name)
                 if info is None:
                     continue
-                py_ver, triple, flavor = info
+                py_ver, triple, flavor, freethreaded = info
                 if "-static" in triple or (flavor and "noopt" in flavor):
                     continue
                 triple = normalize_triple(triple)
                 if triple is None:
                     continue
-                results.setdefault(py_ver, []).append((triple, flavor, url))
+                results.setdefault((py_ver, freethreaded), []).append(
+                    (triple, flavor, url)
+                )
 
     # Collapse CPython variants to a single URL flavor per triple
-    cpython_results: dict[tuple[int, int, int], dict[tuple[str, str, str], str]] = {}
-    for py_ver, choices in results.items():
+    cpython_results: dict[
+        tuple[tuple[int, int, int], bool], dict[tuple[str, str, str], str]
+    ] = {}
+    for (py_ver, freethreaded), choices in results.items():
         urls = {}
         for triple, flavor, url in sorted(choices, key=_sort_by_flavor_preference):
             triple = tuple(triple.split("-"))
@@ -214,13 +222,13 @@ def find():
             if triple in urls:
                 continue
             urls[triple] = url
-        cpython_results[tuple(map(int, py_ver.split(".")))] = urls
+        cpython_results[(tuple(map(int, py_ver.split("."))), freethreaded)] = urls
 
     # Collect variants across interpreter kinds
     # TODO(zanieb): Note we only support CPython downloads at this time
     #               but this will include PyPy chain in the future.
     final_results = {}
-    for interpreter, py_ver, choices in sorted(
+    for interpreter, (py_ver, freethreaded), choices in sorted(
         chain(
             (("cpython",) + x for x in cpython_results.items()),
         ),
@@ -231,9 +239,11 @@ def find():
         # Sort by the remaining information for determinism
         # This groups download metadata in triple component order
         for (arch, operating_system, libc), url in sorted(choices.items()):
-            key = "%s-%s.%s.%s-%s-%s-%s" % (
+            # Free-threaded builds are keyed with a `t` suffix on the version, e.g., `3.13.0t`
+            key = "%s-%s.%s.%s%s-%s-%s-%s" % (
                 interpreter,
                 *py_ver,
+                "t" if freethreaded else "",
                 operating_system,
                 arch,
                 libc,
@@ -249,6 +259,7 @@ def find():
                 "major": py_ver[0],
                 "minor": py_ver[1],
                 "patch": py_ver[2],
+                "free_threaded": freethreaded,
                 "url": url,
                 "sha256": sha256,
             }
@@ -12,6 +12,21 @@ import platform
 import struct
 import sysconfig
 
+# Startup hooks, e.g., a `sitecustomize` module, may print to stdout before we do, so the JSON
+# payload is preceded by a marker that the caller searches for.
+RESULT_MARKER = "__UV_INTERPRETER_INFO__"
+
+# A startup hook may also have removed `sys.argv`, which parts of the standard library expect.
+if not hasattr(sys, "argv"):
+    sys.argv = [""]
+
+
+def emit(payload):
+    # Write to the original stdout, in case a startup hook replaced `sys.stdout`.
+    stdout = sys.__stdout__ or sys.stdout
+    stdout.write(RESULT_MARKER + json.dumps(payload) + "\n")
+    stdout.flush()
+
 
 def format_full_version(info):
     version = "{0.major}.{0.minor}.{0.micro}".format(info)
@@ -22,14 +37,12 @@ def format_full_version(info):
 
 
 if sys.version_info[0] < 3:
-    print(
-        json.dumps(
-            {
-                "result": "error",
-                "kind": "unsupported_python_version",
-                "python_version": format_full_version(sys.version_info),
-            }
-        )
+    emit(
+        {
+            "result": "error",
+            "kind": "unsupported_python_version",
+            "python_version": format_full_version(sys.version_info),
+        }
     )
     sys.exit(0)
 
@@ -107,6 +120,40 @@ def get_major_minor_version() -> str:
     return "{}.{}".format(*sys.version_info)
 
 
+def has_distutils() -> bool:
+    """Return whether `distutils` can be imported.
+
+    `distutils` was removed in Python 3.12, and Debian and Ubuntu ship it separately, as
+    `python3-distutils`, leaving only a stub `distutils` package behind if it's not installed.
+    """
+    import importlib.util
+    import warnings
+
+    try:
+        with warnings.catch_warnings():  # disable warning for PEP-632
+            warnings.simplefilter("ignore")
+            return importlib.util.find_spec("distutils.command.install") is not None
+    except ImportError:
+        return False
+
+
+def get_missing_stdlib_modules():
+    """Return the standard library modules used to create and seed virtual environments that are
+    missing from the interpreter.
+
+    Debian and Ubuntu ship `ensurepip` and `distutils` separately, as `python3-venv` and
+    `python3-distutils`. `distutils` is not reported on Python 3.12 and later, which removed it.
+    """
+    import importlib.util
+
+    missing = []
+    if importlib.util.find_spec("ensurepip") is None:
+        missing.append("ensurepip")
+    if sys.version_info < (3, 12) and not has_distutils():
+        missing.append("distutils")
+    return missing
+
+
 def get_virtualenv():
     """Return the expected Scheme for virtualenvs created by this interpreter.
 
@@ -125,6 +172,10 @@ def get_virtualenv():
         # mangled `local/bin` / etc. names for the default prefix
         # intentionally select `posix_prefix` which is the unaltered posix-like paths
         sysconfig_scheme = "posix_prefix"
+    elif not has_distutils():
+        # Without `distutils`, e.g., on debian / ubuntu without `python3-distutils`, the
+        # `distutils` scheme below can't be computed; use the unaltered default scheme instead
+        sysconfig_scheme = "nt" if os.name == "nt" else "posix_prefix"
     else:
         sysconfig_scheme = None
 
@@ -409,7 +460,9 @@ def get_scheme():
         getattr(sysconfig, "_PIP_USE_SYSCONFIG", sys.version_info >= (3, 10))
     )
 
-    if use_sysconfig:
+    # Debian and Ubuntu ship `distutils` separately, as `python3-distutils`. Without it, the
+    # `distutils` scheme can't be computed, so use `sysconfig` rather than failing.
+    if use_sysconfig or not has_distutils():
         return get_sysconfig_scheme()
     else:
         return get_distutils_scheme()
@@ -444,14 +497,12 @@ def get_operating_system_and_architecture():
 
     if operating_system == "linux":
         if sys.version_info < (3, 7):
-            print(
-                json.dumps(
-                    {
-                        "result": "error",
-                        "kind": "unsupported_python_version",
-                        "python_version": format_full_version(sys.version_info),
-                    }
-                )
+            emit(
+                {
+                    "result": "error",
+                    "kind": "unsupported_python_version",
+                    "python_version": format_full_version(sys.version_info),
+                }
             )
             sys.exit(0)
 
@@ -476,7 +527,7 @@ def get_operating_system_and_architecture():
                 "minor": glibc_version[1],
             }
         else:
-            print(json.dumps({"result": "error", "kind": "libc_not_found"}))
+            emit({"result": "error", "kind": "libc_not_found"})
             sys.exit(0)
     elif operating_system == "win":
         operating_system = {
@@ -515,19 +566,84 @@ def get_operating_system_and_architecture():
             "release": version,
         }
     else:
-        print(
-            json.dumps(
-                {
-                    "result": "error",
-                    "kind": "unknown_operating_system",
-                    "operating_system": operating_system,
-                }
-            )
+        emit(
+            {
+                "result": "error",
+                "kind": "unknown_operating_system",
+                "operating_system": operating_system,
+            }
         )
         sys.exit(0)
     return {"os": operating_system, "arch": architecture}
 
 
+def get_sysconfig_vars():
+    """Return the `sysconfig` variables used to build and link native extensions.
+
+    Variables that aren't defined on the platform, e.g., `LDLIBRARY` on Windows, are `None`.
+    """
+
+    def get(name):
+        value = sysconfig.get_config_var(name)
+        return None if value is None or value == "" else str(value)
+
+    return {
+        "ext_suffix": get("EXT_SUFFIX"),
+        "soabi": get("SOABI"),
+        # `sys.abiflags` is only defined on POSIX; `ABIFLAGS` is also defined on Windows as of
+        # Python 3.13, e.g., `t` for free-threaded builds.
+        "abiflags": getattr(sys, "abiflags", None) or get("ABIFLAGS"),
+        "multiarch": get("MULTIARCH"),
+        "libdir": get("LIBDIR"),
+        "ldlibrary": get("LDLIBRARY"),
+        "includepy": get("INCLUDEPY"),
+        "py_enable_shared": bool(sysconfig.get_config_var("Py_ENABLE_SHARED")),
+    }
+
+
+def get_build_info():
+    """Return how the interpreter was built, as reported by `platform`."""
+    build_number, build_date = platform.python_build()
+    return {
+        "build_number": build_number or None,
+        "build_date": build_date or None,
+        "compiler": platform.python_compiler() or None,
+    }
+
+
+def get_user_site():
+    """Return the PEP 370 user site-packages and scripts directories.
+
+    Returns `None` if the interpreter doesn't support a user scheme, e.g., on Emscripten.
+
+    The query script runs in isolated mode, which disables the user site, so this reports the
+    directories regardless of whether the user site is enabled.
+    """
+    try:
+        import site
+
+        site_packages = site.getusersitepackages()
+    except (AttributeError, ImportError, KeyError):
+        return None
+
+    preferred_scheme = getattr(sysconfig, "get_preferred_scheme", None)
+    if preferred_scheme:
+        scheme = preferred_scheme("user")
+    elif sys.platform == "darwin" and sysconfig.get_config_var("PYTHONFRAMEWORK"):
+        scheme = "osx_framework_user"
+    else:
+        scheme = f"{os.name}_user"
+    if scheme not in sysconfig.get_scheme_names():
+        return None
+
+    return {
+        "site_packages": site_packages,
+        "scripts": sysconfig.get_path(
+            "scripts", scheme, vars={"userbase": site.getuserbase()}
+        ),
+    }
+
+
 def main() -> None:
     markers = {
         "implementation_name": implementation_name,
@@ -561,8 +677,14 @@ def main() -> None:
         # Determine if the interpreter is 32-bit or 64-bit.
         # https://github.com/python/cpython/blob/b228655c227b2ca298a8ffac44d14ce3d22f6faa/Lib/venv/__init__.py#L136
         "pointer_size": "64" if sys.maxsize > 2**32 else "32",
+        # The source revision of the build, e.g., the CPython git commit hash, if known.
+        "build_revision": platform.python_revision() or None,
+        "build_info": get_build_info(),
+        "sysconfig_vars": get_sysconfig_vars(),
+        "user_site": get_user_site(),
+        "missing_stdlib_modules": get_missing_stdlib_modules(),
     }
-    print(json.dumps(interpreter_info))
+    emit(interpreter_info)
 
 
 if __name__ == "__main__":
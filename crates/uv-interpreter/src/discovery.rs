@@ -1,28 +1,35 @@
 use itertools::Itertools;
+use once_cell::sync::Lazy;
 use thiserror::Error;
 use tracing::{debug, instrument, trace};
 use uv_cache::Cache;
+#[cfg(feature = "downloads")]
+use uv_client::BaseClient;
 use uv_configuration::PreviewMode;
 use uv_fs::Simplified;
-use uv_warnings::warn_user_once;
 use which::which;
 
+use crate::downloads::PythonDownload;
+#[cfg(feature = "downloads")]
+use crate::downloads::{FetchOptions, PythonDownloadRequest};
 use crate::implementation::{ImplementationName, LenientImplementationName};
 use crate::interpreter::Error as InterpreterError;
-use crate::managed::InstalledToolchains;
+use crate::managed::{InstalledToolchains, Toolchain, ToolchainPin};
+use crate::named::NamedEnvironments;
 use crate::py_launcher::py_list_paths;
 use crate::virtualenv::{
     conda_prefix_from_env, virtualenv_from_env, virtualenv_from_working_dir,
     virtualenv_python_executable,
 };
+use crate::warnings::{warn, Warning};
 use crate::{Interpreter, PythonVersion};
-use std::borrow::Cow;
 
 use std::collections::HashSet;
 use std::fmt::{self, Formatter};
 use std::num::ParseIntError;
+use std::sync::Arc;
 use std::{env, io};
-use std::{path::Path, path::PathBuf, str::FromStr};
+use std::{path::Component, path::Path, path::PathBuf, str::FromStr};
 
 /// A request to find a Python interpreter.
 ///
@@ -44,6 +51,13 @@ pub enum InterpreterRequest {
     Implementation(ImplementationName),
     /// A Python implementation name and version e.g. `pypy3.8` or `pypy@3.8`
     ImplementationVersion(ImplementationName, VersionRequest),
+    /// The name of an environment in the [`NamedEnvironments`] directory, e.g. `env:myproj`
+    NamedEnvironment(String),
+    /// Multiple acceptable requests in order of preference e.g. `3.12|3.11`
+    ///
+    /// Only requests that are resolved by searching interpreter sources (i.e., versions and
+    /// implementations) are allowed as alternatives.
+    Alternatives(Vec<InterpreterRequest>),
 }
 
 /// The sources to consider when finding a Python interpreter.
@@ -67,6 +81,14 @@ pub enum VersionRequest {
     Major(u8),
     MajorMinor(u8, u8),
     MajorMinorPatch(u8, u8, u8),
+    /// The newest available version, e.g., `latest`.
+    Latest,
+    /// The newest available version with the given major version, e.g., `3.*`.
+    MajorWildcard(u8),
+    /// A free-threaded build of the given minor or patch version, e.g., `3.13t` or `3.13.1t`.
+    ///
+    /// Builds with the GIL enabled never satisfy this request.
+    FreeThreaded(u8, u8, Option<u8>),
 }
 
 /// The policy for discovery of "system" Python interpreters.
@@ -83,6 +105,67 @@ pub enum SystemPython {
     Required,
 }
 
+/// The policy for installing a managed toolchain when it could satisfy an interpreter request.
+///
+/// Defaults to [`ToolchainFetch::Never`], so that non-interactive usage never downloads a
+/// toolchain implicitly.
+#[derive(Clone, Default)]
+pub enum ToolchainFetch {
+    /// Never install a managed toolchain.
+    #[default]
+    Never,
+    /// Install a managed toolchain without confirmation.
+    Always,
+    /// Ask the given callback before installing a managed toolchain, e.g., to prompt the user.
+    Confirm(Arc<dyn ConfirmToolchainFetch>),
+}
+
+/// A callback deciding whether a managed toolchain should be installed.
+///
+/// Implemented for closures, e.g., `|request, download| prompt(...)`.
+pub trait ConfirmToolchainFetch: Send + Sync {
+    /// Return `true` if the `download` should be installed to satisfy the `request`.
+    fn confirm(&self, request: &InterpreterRequest, download: &PythonDownload) -> bool;
+}
+
+/// The reason a Python interpreter does not satisfy an [`InterpreterRequest`].
+///
+/// See [`InterpreterRequest::explain_mismatch`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum MismatchReason {
+    /// The interpreter's version does not match the requested version.
+    #[error("Found Python {found} but Python {requested} was requested")]
+    Version {
+        requested: VersionRequest,
+        found: pep440_rs::Version,
+    },
+    /// The interpreter's implementation does not match the requested implementation.
+    #[error("Found {found} but {requested} was requested")]
+    Implementation {
+        requested: ImplementationName,
+        found: String,
+    },
+    /// The interpreter was built for a different architecture than the current machine.
+    #[error("Found an interpreter for {found} but the current architecture is {expected}")]
+    Architecture {
+        expected: crate::platform::Arch,
+        found: platform_tags::Arch,
+    },
+    /// The interpreter's executable is missing or not executable.
+    #[error("Python executable at `{}` is not executable", _0.user_display())]
+    NotExecutable(PathBuf),
+}
+
+/// An error parsing a [`VersionRequest`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum VersionRequestError {
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+    /// A free-threaded build was requested without a minor version, e.g., `3t`.
+    #[error("Free-threaded requests require a minor version, e.g., `3.13t`, but found `{0}`")]
+    FreeThreadedWithoutMinor(String),
+}
+
 /// The result of an interpreter search.
 ///
 /// Returned by [`find_interpreter`].
@@ -101,10 +184,14 @@ pub enum InterpreterNotFound {
     NoMatchingImplementation(SourceSelector, ImplementationName),
     /// No Python installations with the requested implementation name and version were found.
     NoMatchingImplementationVersion(SourceSelector, ImplementationName, VersionRequest),
+    /// No Python installations matching any of the requested alternatives were found.
+    NoMatchingAlternatives(SourceSelector, Vec<InterpreterRequest>),
     /// The requested file path does not exist.
     FileNotFound(PathBuf),
     /// The requested directory path does not exist.
     DirectoryNotFound(PathBuf),
+    /// The requested named environment does not exist.
+    NamedEnvironmentNotFound(String, PathBuf),
     /// No Python executables could be found in the requested directory.
     ExecutableNotFoundInDirectory(PathBuf, PathBuf),
     /// The Python executable name could not be found in the search path (i.e. PATH).
@@ -141,7 +228,14 @@ pub enum InterpreterSource {
     ManagedToolchain,
     /// The interpreter invoked uv i.e. via `python -m uv ...`
     ParentInterpreter,
-    // TODO(zanieb): Add support for fetching the interpreter from a remote source
+    /// A Windows executable was found in the search path while running in WSL, e.g., in `/mnt/c`
+    ///
+    /// These are only used if selected explicitly, since they spawn Windows processes.
+    WslWindowsSearchPath,
+    /// A managed toolchain was downloaded because no interpreter satisfied the request
+    ///
+    /// See [`find_or_fetch_best_interpreter`].
+    FetchedToolchain,
 }
 
 #[derive(Error, Debug)]
@@ -161,6 +255,10 @@ pub enum Error {
     #[error(transparent)]
     VirtualEnv(#[from] crate::virtualenv::Error),
 
+    /// An error was encountered when looking up a named environment.
+    #[error(transparent)]
+    NamedEnvironment(#[from] crate::named::Error),
+
     /// An error was encountered when using the `py` launcher on Windows.
     #[error(transparent)]
     PyLauncher(#[from] crate::py_launcher::Error),
@@ -175,6 +273,7 @@ pub enum Error {
 ///
 /// - The spawning interpreter
 /// - The active environment
+/// - The managed toolchain pinned in a `.python-version` file
 /// - A discovered environment (e.g. `.venv`)
 /// - Installed managed toolchains
 /// - The search path (i.e. PATH)
@@ -188,12 +287,17 @@ pub enum Error {
 /// be included. However, the caller MUST query the returned executables to ensure they satisfy the request;
 /// this function does not guarantee that the executables provide any particular version. See
 /// [`find_interpreter`] instead.
+///
+/// If no [`InstalledToolchains`] are provided, the toolchain directory is read from the environment.
 fn python_executables<'a>(
     version: Option<&'a VersionRequest>,
     implementation: Option<&'a ImplementationName>,
     sources: &SourceSelector,
+    toolchains: Option<&'a InstalledToolchains>,
 ) -> impl Iterator<Item = Result<(InterpreterSource, PathBuf), Error>> + 'a {
     // Note we are careful to ensure the iterator chain is lazy to avoid unnecessary work
+    let search_path = sources.contains(InterpreterSource::SearchPath);
+    let wsl_windows_search_path = sources.contains(InterpreterSource::WslWindowsSearchPath);
 
     // (1) The parent interpreter
     sources.contains(InterpreterSource::ParentInterpreter).then(||
@@ -219,7 +323,20 @@ fn python_executables<'a>(
             .map(|path| Ok((InterpreterSource::CondaPrefix, path)))
         ).into_iter().flatten()
     )
-    // (4) A discovered environment
+    // (4) The pinned managed toolchain, which takes precedence over the environments and
+    // interpreters found on the system, but not over an activated environment
+    .chain(
+        sources.contains(InterpreterSource::ManagedToolchain).then(move ||
+            find_pinned_toolchain(toolchains)
+            .map(|toolchain|
+                toolchain
+                .filter(|toolchain| version.map_or(true, |version| version.matches_toolchain(toolchain)))
+                .map(|toolchain| (InterpreterSource::ManagedToolchain, toolchain.executable()))
+            )
+            .transpose()
+        ).into_iter().flatten()
+    )
+    // (5) A discovered environment
     .chain(
         sources.contains(InterpreterSource::DiscoveredEnvironment).then(||
             std::iter::once(
@@ -234,19 +351,22 @@ fn python_executables<'a>(
             ).flatten_ok()
         ).into_iter().flatten()
     )
-    // (5) Managed toolchains
+    // (6) Managed toolchains
     .chain(
         sources.contains(InterpreterSource::ManagedToolchain).then(move ||
             std::iter::once(
-                InstalledToolchains::from_settings().map_err(Error::from).and_then(|installed_toolchains| {
+                toolchains
+                .map_or_else(InstalledToolchains::from_settings, |toolchains| Ok(toolchains.clone()))
+                .map_err(Error::from)
+                .and_then(|installed_toolchains| {
                     debug!("Searching for managed toolchains at `{}`", installed_toolchains.root().user_display());
-                    let toolchains = installed_toolchains.find_matching_current_platform()?;
+                    let mut toolchains: Vec<_> = installed_toolchains.find_matching_current_platform()?.collect();
+                    // Prefer toolchains with the GIL enabled, if a request is satisfied by either
+                    toolchains.sort_by_key(Toolchain::free_threaded);
                     // Check that the toolchain version satisfies the request to avoid unnecessary interpreter queries later
                     Ok(
                         toolchains.into_iter().filter(move |toolchain|
-                            version.is_none() || version.is_some_and(|version|
-                                version.matches_version(toolchain.python_version())
-                            )
+                            version.map_or(true, |version| version.matches_toolchain(toolchain))
                         )
                         .inspect(|toolchain| debug!("Found managed toolchain `{toolchain}`"))
                         .map(|toolchain| (InterpreterSource::ManagedToolchain, toolchain.executable()))
@@ -255,14 +375,24 @@ fn python_executables<'a>(
             ).flatten_ok()
         ).into_iter().flatten()
     )
-    // (6) The search path
+    // (7) The search path, which may include Windows executables when running in WSL
     .chain(
-        sources.contains(InterpreterSource::SearchPath).then(move ||
+        (search_path || wsl_windows_search_path).then(move ||
             python_executables_from_search_path(version, implementation)
-            .map(|path| Ok((InterpreterSource::SearchPath, path))),
+            .map(|path| (search_path_source(&path), path))
+            .filter(move |(source, path)| match source {
+                InterpreterSource::WslWindowsSearchPath => {
+                    if !wsl_windows_search_path {
+                        debug!("Skipping Windows executable in WSL: `{}`", path.display());
+                    }
+                    wsl_windows_search_path
+                }
+                _ => search_path,
+            })
+            .map(Ok),
         ).into_iter().flatten()
     )
-    // (7) The `py` launcher (windows only)
+    // (8) The `py` launcher (windows only)
     // TODO(konstin): Implement <https://peps.python.org/pep-0514/> to read python installations from the registry instead.
     .chain(
         (sources.contains(InterpreterSource::PyLauncher) && cfg!(windows)).then(||
@@ -283,6 +413,46 @@ fn python_executables<'a>(
     )
 }
 
+/// Find the installed managed toolchain pinned in a `.python-version` file, if any.
+///
+/// A pin that can't be read is ignored with a warning rather than failing discovery, as is a pin
+/// of a toolchain that is not installed.
+fn find_pinned_toolchain(
+    toolchains: Option<&InstalledToolchains>,
+) -> Result<Option<Toolchain>, Error> {
+    let pin = match crate::current_dir()
+        .map_err(crate::managed::Error::from)
+        .and_then(|dir| ToolchainPin::find(&dir))
+    {
+        Ok(Some(pin)) => pin,
+        Ok(None) => return Ok(None),
+        Err(err) => {
+            warn(&Warning::InvalidToolchainPin(err.to_string()));
+            return Ok(None);
+        }
+    };
+    let installed_toolchains = toolchains
+        .map_or_else(InstalledToolchains::from_settings, |toolchains| {
+            Ok(toolchains.clone())
+        })?;
+    let Some(toolchain) = installed_toolchains
+        .find_matching_current_platform()?
+        .find(|toolchain| toolchain.key() == pin.key())
+    else {
+        warn(&Warning::MissingPinnedToolchain {
+            key: pin.key().to_string(),
+            path: pin.path().to_path_buf(),
+        });
+        return Ok(None);
+    };
+    debug!(
+        "Preferring toolchain `{}` pinned in `{}`",
+        pin.key(),
+        pin.path().user_display()
+    );
+    Ok(Some(toolchain))
+}
+
 /// Lazily iterate over Python executables in the `PATH`.
 ///
 /// The [`VersionRequest`] and [`ImplementationName`] are used to determine the possible
@@ -302,9 +472,19 @@ fn python_executables_from_search_path<'a>(
     let search_path =
         env::var_os("UV_TEST_PYTHON_PATH").unwrap_or(env::var_os("PATH").unwrap_or_default());
 
+    // On Windows, each name is tried with every extension in `PATHEXT`, e.g., `python3.exe` and
+    // `python3.cmd`.
+    let extensions = search_path_extensions(env::var("PATHEXT").ok().as_deref());
     let possible_names: Vec<_> = version
-        .unwrap_or(&VersionRequest::Any)
-        .possible_names(implementation)
+        .copied()
+        .unwrap_or_default()
+        .executable_stems(implementation, false)
+        .into_iter()
+        .flat_map(|stem| {
+            extensions
+                .iter()
+                .map(move |extension| format!("{stem}{extension}"))
+        })
         .collect();
 
     trace!(
@@ -319,8 +499,6 @@ fn python_executables_from_search_path<'a>(
         .into_iter()
         .filter(|dir| dir.is_dir())
         .flat_map(move |dir| {
-            // Clone the directory for second closure
-            let dir_clone = dir.clone();
             trace!(
                 "Checking `PATH` directory for interpreters: {}",
                 dir.display()
@@ -337,21 +515,80 @@ fn python_executables_from_search_path<'a>(
                 })
                 .filter(|path| !is_windows_store_shim(path))
                 .inspect(|path| trace!("Found possible Python executable: {}", path.display()))
-                .chain(
-                    // TODO(zanieb): Consider moving `python.bat` into `possible_names` to avoid a chain
-                    cfg!(windows)
-                        .then(move || {
-                            which::which_in_global("python.bat", Some(&dir_clone))
-                                .into_iter()
-                                .flatten()
-                                .collect::<Vec<_>>()
-                        })
-                        .into_iter()
-                        .flatten(),
-                )
         })
 }
 
+/// Return the [`InterpreterSource`] for an executable found in the search path.
+///
+/// When running in WSL, the Windows `PATH` is usually appended to the Linux `PATH`. Executables
+/// on the mounted Windows drives (e.g., `/mnt/c/Python312/python.exe`) spawn Windows processes,
+/// which cannot be used to create environments for Linux, so they are tagged separately.
+fn search_path_source(path: &Path) -> InterpreterSource {
+    if *IS_WSL && is_windows_drive_mount(path) {
+        InterpreterSource::WslWindowsSearchPath
+    } else {
+        InterpreterSource::SearchPath
+    }
+}
+
+/// Whether we're running in the Windows Subsystem for Linux.
+static IS_WSL: Lazy<bool> = Lazy::new(|| {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    if env::var_os("WSL_DISTRO_NAME").is_some() || env::var_os("WSL_INTEROP").is_some() {
+        return true;
+    }
+    if Path::new("/proc/sys/fs/binfmt_misc/WSLInterop").exists() {
+        return true;
+    }
+    fs_err::read_to_string("/proc/sys/kernel/osrelease")
+        .is_ok_and(|release| release.to_ascii_lowercase().contains("microsoft"))
+});
+
+/// Returns `true` if the path is on a Windows drive mounted by WSL, e.g., `/mnt/c/...`.
+///
+/// Only the default mount root (`/mnt`) is supported.
+fn is_windows_drive_mount(path: &Path) -> bool {
+    let mut components = path.components();
+    if components.next() != Some(Component::RootDir)
+        || components.next() != Some(Component::Normal("mnt".as_ref()))
+    {
+        return false;
+    }
+    match components.next() {
+        Some(Component::Normal(drive)) => drive.to_str().is_some_and(|drive| {
+            drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic())
+        }),
+        _ => false,
+    }
+}
+
+/// Return the extensions to try for each executable name when searching the `PATH`.
+///
+/// On Windows, these are the extensions in `PATHEXT` (e.g., `.COM;.EXE;.BAT;.CMD`), such that
+/// wrapper scripts like the `.cmd` shims installed by Scoop or Chocolatey are found. Elsewhere,
+/// executables do not have an extension.
+fn search_path_extensions(pathext: Option<&str>) -> Vec<String> {
+    if !cfg!(windows) {
+        return vec![String::new()];
+    }
+    let extensions: Vec<_> = pathext
+        .unwrap_or_default()
+        .split(';')
+        .map(str::trim)
+        .filter(|extension| extension.starts_with('.'))
+        .map(str::to_ascii_lowercase)
+        .unique()
+        .collect();
+    if extensions.is_empty() {
+        // The default `PATHEXT`, limited to the extensions used by Python executables and shims.
+        [".exe", ".bat", ".cmd"].map(ToString::to_string).to_vec()
+    } else {
+        extensions
+    }
+}
+
 /// Lazily iterate over all discoverable Python interpreters.
 ///
 ///See [`python_executables`] for more information on discovery.
@@ -360,22 +597,42 @@ fn python_interpreters<'a>(
     implementation: Option<&'a ImplementationName>,
     system: SystemPython,
     sources: &SourceSelector,
+    toolchains: Option<&'a InstalledToolchains>,
     cache: &'a Cache,
 ) -> impl Iterator<Item = Result<(InterpreterSource, Interpreter), Error>> + 'a {
-    python_executables(version, implementation, sources)
+    python_interpreters_from_executables(
+        python_executables(version, implementation, sources, toolchains),
+        system,
+        cache,
+    )
+}
+
+/// Lazily query the given Python executables, filtering the interpreters to conform to the
+/// [`SystemPython`] policy.
+fn python_interpreters_from_executables<'a>(
+    executables: impl Iterator<Item = Result<(InterpreterSource, PathBuf), Error>> + 'a,
+    system: SystemPython,
+    cache: &'a Cache,
+) -> impl Iterator<Item = Result<(InterpreterSource, Interpreter), Error>> + 'a {
+    executables
         .map(|result| match result {
-            Ok((source, path)) => Interpreter::query(&path, cache)
-                .map(|interpreter| (source, interpreter))
-                .inspect(|(source, interpreter)| {
-                    debug!(
-                        "Found {} {} at `{}` ({source})",
-                        LenientImplementationName::from(interpreter.implementation_name()),
-                        interpreter.python_full_version(),
-                        path.display()
-                    );
-                })
-                .map_err(Error::from)
-                .inspect_err(|err| debug!("{err}")),
+            Ok((source, path)) => match source {
+                // Managed toolchains record their interpreter info when they're installed
+                InterpreterSource::ManagedToolchain => {
+                    crate::managed::query_interpreter(&path, cache)
+                }
+                _ => Interpreter::query(&path, cache),
+            }
+            .map(|interpreter| (source, interpreter))
+            .inspect(|(source, interpreter)| {
+                debug!(
+                    "Found {} at `{}` ({source})",
+                    interpreter.implementation_display(),
+                    path.display()
+                );
+            })
+            .map_err(Error::from)
+            .inspect_err(|err| debug!("{err}")),
             Err(err) => Err(err),
         })
         .filter(move |result| match result {
@@ -440,7 +697,8 @@ fn should_stop_discovery(err: &Error) -> bool {
             | InterpreterError::SpawnFailed { .. } => true,
             InterpreterError::QueryScript { path, .. }
             | InterpreterError::UnexpectedResponse { path, .. }
-            | InterpreterError::StatusCode { path, .. } => {
+            | InterpreterError::StatusCode { path, .. }
+            | InterpreterError::Unusable { path, .. } => {
                 trace!("Skipping bad interpreter at {}", path.display());
                 false
             }
@@ -449,6 +707,39 @@ fn should_stop_discovery(err: &Error) -> bool {
     }
 }
 
+/// Select an interpreter that matches the `predicate`.
+///
+/// Returns the first matching interpreter or, if `newest` is set, the matching interpreter with
+/// the highest Python version. Ties are broken by discovery order.
+///
+/// The first critical error is returned, even if a match was already found.
+fn select_interpreter(
+    interpreters: impl Iterator<Item = Result<(InterpreterSource, Interpreter), Error>>,
+    newest: bool,
+    predicate: impl Fn(&Interpreter) -> bool,
+) -> Result<Option<(InterpreterSource, Interpreter)>, Error> {
+    let mut selected: Option<(InterpreterSource, Interpreter)> = None;
+    for result in interpreters {
+        let (source, interpreter) = match result {
+            Ok(found) => found,
+            Err(err) if should_stop_discovery(&err) => return Err(err),
+            Err(_) => continue,
+        };
+        if !predicate(&interpreter) {
+            continue;
+        }
+        if !newest {
+            return Ok(Some((source, interpreter)));
+        }
+        if selected.as_ref().map_or(true, |(_, selected)| {
+            interpreter.python_version() > selected.python_version()
+        }) {
+            selected = Some((source, interpreter));
+        }
+    }
+    Ok(selected)
+}
+
 /// Find an interpreter that satisfies the given request.
 ///
 /// If an error is encountered while locating or inspecting a candidate interpreter,
@@ -458,6 +749,33 @@ pub fn find_interpreter(
     system: SystemPython,
     sources: &SourceSelector,
     cache: &Cache,
+) -> Result<InterpreterResult, Error> {
+    find_interpreter_in(request, system, sources, None, cache)
+}
+
+/// Find an interpreter that satisfies the given request, using the managed toolchains in the given
+/// directory rather than the one configured by `UV_TOOLCHAIN_DIR` or the user's data directory.
+///
+/// This allows toolchains to be kept in a project-specific location, e.g., inside a monorepo or
+/// the output tree of a hermetic build system.
+///
+/// See [`find_interpreter`] for more details on interpreter discovery.
+pub fn find_interpreter_with_toolchains(
+    request: &InterpreterRequest,
+    system: SystemPython,
+    sources: &SourceSelector,
+    toolchains: &InstalledToolchains,
+    cache: &Cache,
+) -> Result<InterpreterResult, Error> {
+    find_interpreter_in(request, system, sources, Some(toolchains), cache)
+}
+
+fn find_interpreter_in(
+    request: &InterpreterRequest,
+    system: SystemPython,
+    sources: &SourceSelector,
+    toolchains: Option<&InstalledToolchains>,
+    cache: &Cache,
 ) -> Result<InterpreterResult, Error> {
     let result = match request {
         InterpreterRequest::File(path) => {
@@ -504,6 +822,35 @@ pub fn find_interpreter(
                 interpreter: Interpreter::query(executable, cache)?,
             }
         }
+        InterpreterRequest::NamedEnvironment(name) => {
+            debug!("Checking for Python interpreter in {request}");
+            if !sources.contains(InterpreterSource::ProvidedPath) {
+                return Err(Error::SourceNotSelected(
+                    request.clone(),
+                    InterpreterSource::ProvidedPath,
+                    sources.clone(),
+                ));
+            }
+            let environments = NamedEnvironments::from_settings()?;
+            let Some(path) = environments.find(name)? else {
+                return Ok(InterpreterResult::Err(
+                    InterpreterNotFound::NamedEnvironmentNotFound(
+                        name.clone(),
+                        environments.root().to_path_buf(),
+                    ),
+                ));
+            };
+            let executable = virtualenv_python_executable(&path);
+            if !executable.try_exists()? {
+                return Ok(InterpreterResult::Err(
+                    InterpreterNotFound::ExecutableNotFoundInDirectory(path, executable),
+                ));
+            }
+            DiscoveredInterpreter {
+                source: InterpreterSource::ProvidedPath,
+                interpreter: Interpreter::query(executable, cache)?,
+            }
+        }
         InterpreterRequest::ExecutableName(name) => {
             debug!("Searching for Python interpreter with {request}");
             if !sources.contains(InterpreterSource::SearchPath) {
@@ -525,18 +872,24 @@ pub fn find_interpreter(
         }
         InterpreterRequest::Implementation(implementation) => {
             debug!("Searching for a {request} interpreter in {sources}");
-            let Some((source, interpreter)) =
-                python_interpreters(None, Some(implementation), system, sources, cache)
-                    .find(|result| {
-                        match result {
-                            // Return the first critical error or matching interpreter
-                            Err(err) => should_stop_discovery(err),
-                            Ok((_source, interpreter)) => {
-                                interpreter.implementation_name() == implementation.as_str()
-                            }
-                        }
-                    })
-                    .transpose()?
+            let Some((source, interpreter)) = python_interpreters(
+                None,
+                Some(implementation),
+                system,
+                sources,
+                toolchains,
+                cache,
+            )
+            .find(|result| {
+                match result {
+                    // Return the first critical error or matching interpreter
+                    Err(err) => should_stop_discovery(err),
+                    Ok((_source, interpreter)) => {
+                        interpreter.implementation_name() == implementation.as_str()
+                    }
+                }
+            })
+            .transpose()?
             else {
                 return Ok(InterpreterResult::Err(
                     InterpreterNotFound::NoMatchingImplementation(sources.clone(), *implementation),
@@ -549,19 +902,21 @@ pub fn find_interpreter(
         }
         InterpreterRequest::ImplementationVersion(implementation, version) => {
             debug!("Searching for {request} in {sources}");
-            let Some((source, interpreter)) =
-                python_interpreters(Some(version), Some(implementation), system, sources, cache)
-                    .find(|result| {
-                        match result {
-                            // Return the first critical error or matching interpreter
-                            Err(err) => should_stop_discovery(err),
-                            Ok((_source, interpreter)) => {
-                                version.matches_interpreter(interpreter)
-                                    && interpreter.implementation_name() == implementation.as_str()
-                            }
-                        }
-                    })
-                    .transpose()?
+            let Some((source, interpreter)) = select_interpreter(
+                python_interpreters(
+                    Some(version),
+                    Some(implementation),
+                    system,
+                    sources,
+                    toolchains,
+                    cache,
+                ),
+                version.prefers_newest(),
+                |interpreter| {
+                    version.matches_interpreter(interpreter)
+                        && interpreter.implementation_name() == implementation.as_str()
+                },
+            )?
             else {
                 // TODO(zanieb): Peek if there are any interpreters with the requested implementation
                 //               to improve the error message e.g. using `NoMatchingImplementation` instead
@@ -578,10 +933,76 @@ pub fn find_interpreter(
                 interpreter,
             }
         }
+        InterpreterRequest::Alternatives(requests) => {
+            debug!("Searching for {request} in {sources}");
+            // Query each candidate executable once, even if it is found for multiple alternatives
+            let mut seen = HashSet::new();
+            let executables = requests
+                .iter()
+                .flat_map(|request| {
+                    python_executables(
+                        request.version(),
+                        request.implementation(),
+                        sources,
+                        toolchains,
+                    )
+                })
+                .filter(move |result| match result {
+                    Ok((_source, path)) => seen.insert(path.clone()),
+                    Err(_) => true,
+                });
+
+            // Track the most preferred match; stop early if the first alternative is satisfied,
+            // unless it asks for the newest matching version
+            let stop_at_first = requests
+                .first()
+                .and_then(InterpreterRequest::version)
+                .map_or(true, |version| !version.prefers_newest());
+            let mut best: Option<(usize, InterpreterSource, Interpreter)> = None;
+            for result in python_interpreters_from_executables(executables, system, cache) {
+                let (source, interpreter) = match result {
+                    Ok(found) => found,
+                    // Return the first critical error
+                    Err(err) if should_stop_discovery(&err) => return Err(err),
+                    Err(_) => continue,
+                };
+                let Some(rank) = requests
+                    .iter()
+                    .position(|request| request.satisfied_by(&interpreter))
+                else {
+                    continue;
+                };
+                let is_better = best
+                    .as_ref()
+                    .map_or(true, |(best_rank, _, best_interpreter)| {
+                        rank < *best_rank
+                            || (rank == *best_rank
+                                && requests[rank]
+                                    .version()
+                                    .is_some_and(|version| version.prefers_newest())
+                                && interpreter.python_version() > best_interpreter.python_version())
+                    });
+                if is_better {
+                    best = Some((rank, source, interpreter));
+                    if rank == 0 && stop_at_first {
+                        break;
+                    }
+                }
+            }
+            let Some((_rank, source, interpreter)) = best else {
+                return Ok(InterpreterResult::Err(
+                    InterpreterNotFound::NoMatchingAlternatives(sources.clone(), requests.clone()),
+                ));
+            };
+            DiscoveredInterpreter {
+                source,
+                interpreter,
+            }
+        }
         InterpreterRequest::Any => {
             debug!("Searching for Python interpreter in {sources}");
             let Some((source, interpreter)) =
-                python_interpreters(None, None, system, sources, cache)
+                python_interpreters(None, None, system, sources, toolchains, cache)
                     .find(|result| {
                         match result {
                             // Return the first critical error or interpreter
@@ -602,16 +1023,11 @@ pub fn find_interpreter(
         }
         InterpreterRequest::Version(version) => {
             debug!("Searching for {request} in {sources}");
-            let Some((source, interpreter)) =
-                python_interpreters(Some(version), None, system, sources, cache)
-                    .find(|result| {
-                        match result {
-                            // Return the first critical error or matching interpreter
-                            Err(err) => should_stop_discovery(err),
-                            Ok((_source, interpreter)) => version.matches_interpreter(interpreter),
-                        }
-                    })
-                    .transpose()?
+            let Some((source, interpreter)) = select_interpreter(
+                python_interpreters(Some(version), None, system, sources, toolchains, cache),
+                version.prefers_newest(),
+                |interpreter| version.matches_interpreter(interpreter),
+            )?
             else {
                 let err = if matches!(version, VersionRequest::Any) {
                     InterpreterNotFound::NoPythonInstallation(sources.clone(), Some(*version))
@@ -672,12 +1088,169 @@ pub fn find_best_interpreter(
     // Determine if we should be allowed to look outside of virtual environments.
     let sources = SourceSelector::from_settings(system, preview);
 
+    if let Some(result) = find_matching_interpreter(request, system, &sources, None, cache)? {
+        return Ok(result);
+    }
+    find_any_interpreter(system, &sources, None, cache)
+}
+
+/// Find the best-matching Python interpreter, downloading a managed toolchain if no interpreter
+/// satisfies the request.
+///
+/// Like [`find_best_interpreter`], but before falling back to any available version, a managed
+/// toolchain matching the request is installed if the [`ToolchainFetch`] policy allows it. The
+/// toolchain is returned with [`InterpreterSource::FetchedToolchain`] as its source.
+///
+/// Downloads are only considered if the selected sources include managed toolchains, i.e., in
+/// preview mode and if system interpreters are allowed.
+#[cfg(feature = "downloads")]
+pub async fn find_or_fetch_best_interpreter(
+    request: &InterpreterRequest,
+    system: SystemPython,
+    preview: PreviewMode,
+    fetch: &ToolchainFetch,
+    client: &BaseClient,
+    options: &FetchOptions,
+    cache: &Cache,
+) -> Result<InterpreterResult, Error> {
+    find_or_fetch_best_interpreter_in(
+        request, system, preview, fetch, client, options, None, cache,
+    )
+    .await
+}
+
+/// Find the best-matching Python interpreter, downloading a managed toolchain if no interpreter
+/// satisfies the request, using the managed toolchains in the given directory rather than the one
+/// configured by `UV_TOOLCHAIN_DIR` or the user's data directory.
+///
+/// Toolchains are both discovered in and installed into the given directory.
+///
+/// See [`find_or_fetch_best_interpreter`] for more details.
+#[cfg(feature = "downloads")]
+#[allow(clippy::too_many_arguments)]
+pub async fn find_or_fetch_best_interpreter_with_toolchains(
+    request: &InterpreterRequest,
+    system: SystemPython,
+    preview: PreviewMode,
+    fetch: &ToolchainFetch,
+    client: &BaseClient,
+    options: &FetchOptions,
+    toolchains: &InstalledToolchains,
+    cache: &Cache,
+) -> Result<InterpreterResult, Error> {
+    find_or_fetch_best_interpreter_in(
+        request,
+        system,
+        preview,
+        fetch,
+        client,
+        options,
+        Some(toolchains),
+        cache,
+    )
+    .await
+}
+
+#[cfg(feature = "downloads")]
+#[instrument(skip_all, fields(request))]
+#[allow(clippy::too_many_arguments)]
+async fn find_or_fetch_best_interpreter_in(
+    request: &InterpreterRequest,
+    system: SystemPython,
+    preview: PreviewMode,
+    fetch: &ToolchainFetch,
+    client: &BaseClient,
+    options: &FetchOptions,
+    toolchains: Option<&InstalledToolchains>,
+    cache: &Cache,
+) -> Result<InterpreterResult, Error> {
+    debug!("Starting interpreter discovery for {}", request);
+
+    let sources = SourceSelector::from_settings(system, preview);
+
+    if let Some(result) = find_matching_interpreter(request, system, &sources, toolchains, cache)? {
+        return Ok(result);
+    }
+
+    if fetch.is_enabled() && sources.contains(InterpreterSource::FetchedToolchain) {
+        let toolchains = toolchains
+            .map_or_else(InstalledToolchains::from_settings, |toolchains| {
+                Ok(toolchains.clone())
+            })?;
+        if let Some(interpreter) =
+            fetch_toolchain(request, fetch, client, options, &toolchains, cache).await?
+        {
+            return Ok(Ok(DiscoveredInterpreter {
+                source: InterpreterSource::FetchedToolchain,
+                interpreter,
+            }));
+        }
+    }
+
+    find_any_interpreter(system, &sources, toolchains, cache)
+}
+
+/// Install a managed toolchain for the first of the requested alternatives with a known download,
+/// if the [`ToolchainFetch`] policy allows it.
+///
+/// Returns `None` if there's no download for the request, e.g., for executable paths, or if the
+/// installation was declined.
+#[cfg(feature = "downloads")]
+async fn fetch_toolchain(
+    request: &InterpreterRequest,
+    fetch: &ToolchainFetch,
+    client: &BaseClient,
+    options: &FetchOptions,
+    toolchains: &InstalledToolchains,
+    cache: &Cache,
+) -> Result<Option<Interpreter>, Error> {
+    let requests = match request {
+        InterpreterRequest::Alternatives(requests) => requests.as_slice(),
+        request => std::slice::from_ref(request),
+    };
+    for request in requests {
+        let Some(download_request) = request.download_request() else {
+            continue;
+        };
+        let download_request = download_request.fill()?;
+        let Some(download) = PythonDownload::from_request(&download_request) else {
+            debug!("No managed toolchain download found for {request}");
+            continue;
+        };
+        if !fetch.allows(request, download) {
+            continue;
+        }
+
+        debug!("Installing managed toolchain `{download}` for {request}");
+        let toolchain = crate::managed::install(
+            download_request,
+            &crate::platform::Platform::from_env().map_err(crate::managed::Error::from)?,
+            toolchains,
+            client,
+            options,
+        )
+        .await?;
+        return Ok(Some(toolchain.interpreter(cache)?));
+    }
+    Ok(None)
+}
+
+/// Find an interpreter that satisfies the request, with a relaxed patch version if necessary.
+///
+/// Returns `None` if no interpreter matches.
+fn find_matching_interpreter(
+    request: &InterpreterRequest,
+    system: SystemPython,
+    sources: &SourceSelector,
+    toolchains: Option<&InstalledToolchains>,
+    cache: &Cache,
+) -> Result<Option<InterpreterResult>, Error> {
     // First, check for an exact match (or the first available version if no Python versfion was provided)
     debug!("Looking for exact match for request {request}");
-    let result = find_interpreter(request, system, &sources, cache)?;
+    let result = find_interpreter_in(request, system, sources, toolchains, cache)?;
     if let Ok(ref found) = result {
         warn_on_unsupported_python(found.interpreter());
-        return Ok(result);
+        return Ok(Some(result));
     }
 
     // If that fails, and a specific patch version was requested try again allowing a
@@ -693,22 +1266,58 @@ pub fn find_best_interpreter(
         InterpreterRequest::ImplementationVersion(implementation, version) => Some(
             InterpreterRequest::ImplementationVersion(*implementation, (*version).without_patch()),
         ),
+        InterpreterRequest::Alternatives(requests) => {
+            if requests
+                .iter()
+                .any(|request| matches!(request.version(), Some(version) if version.has_patch()))
+            {
+                Some(InterpreterRequest::Alternatives(
+                    requests
+                        .iter()
+                        .map(|request| match request {
+                            InterpreterRequest::Version(version) => {
+                                InterpreterRequest::Version(version.without_patch())
+                            }
+                            InterpreterRequest::ImplementationVersion(implementation, version) => {
+                                InterpreterRequest::ImplementationVersion(
+                                    *implementation,
+                                    version.without_patch(),
+                                )
+                            }
+                            request => request.clone(),
+                        })
+                        .collect(),
+                ))
+            } else {
+                None
+            }
+        }
         _ => None,
     } {
         debug!("Looking for relaxed patch version {request}");
-        let result = find_interpreter(&request, system, &sources, cache)?;
+        let result = find_interpreter_in(&request, system, sources, toolchains, cache)?;
         if let Ok(ref found) = result {
             warn_on_unsupported_python(found.interpreter());
-            return Ok(result);
+            return Ok(Some(result));
         }
     }
 
+    Ok(None)
+}
+
+/// Find an interpreter with any version, used if the requested version cannot be fulfilled.
+fn find_any_interpreter(
+    system: SystemPython,
+    sources: &SourceSelector,
+    toolchains: Option<&InstalledToolchains>,
+    cache: &Cache,
+) -> Result<InterpreterResult, Error> {
     // If a Python version was requested but cannot be fulfilled, just take any version
     debug!("Looking for Python interpreter with any version");
     let request = InterpreterRequest::Any;
-    Ok(find_interpreter(
+    Ok(find_interpreter_in(
         // TODO(zanieb): Add a dedicated `Default` variant to `InterpreterRequest`
-        &request, system, &sources, cache,
+        &request, system, sources, toolchains, cache,
     )?
     .map_err(|err| {
         // Use a more general error in this case since we looked for multiple versions
@@ -724,10 +1333,9 @@ pub fn find_best_interpreter(
 fn warn_on_unsupported_python(interpreter: &Interpreter) {
     // Warn on usage with an unsupported Python version
     if interpreter.python_tuple() < (3, 8) {
-        warn_user_once!(
-            "uv is only compatible with Python 3.8+, found Python {}.",
-            interpreter.python_version()
-        );
+        warn(&Warning::UnsupportedPython(
+            interpreter.python_version().clone(),
+        ));
     }
 }
 
@@ -870,6 +1478,17 @@ impl InterpreterRequest {
     ///
     /// This cannot fail, which means weird inputs will be parsed as [`InterpreterRequest::File`] or [`InterpreterRequest::ExecutableName`].
     pub fn parse(value: &str) -> Self {
+        // e.g. `env:myproj`
+        if let Some(name) = value.strip_prefix("env:") {
+            return Self::NamedEnvironment(name.to_string());
+        }
+        // e.g. `3.12|3.11` or `pypy3.10|3.11`
+        if value.contains('|') {
+            let requests: Vec<_> = value.split('|').map(str::trim).map(Self::parse).collect();
+            if requests.iter().all(Self::is_alternative) {
+                return Self::Alternatives(requests);
+            }
+        }
         // e.g. `3.12.1`
         if let Ok(version) = VersionRequest::from_str(value) {
             return Self::Version(version);
@@ -942,96 +1561,276 @@ impl InterpreterRequest {
         // e.g. foo.exe
         Self::ExecutableName(value.to_string())
     }
-}
 
-impl VersionRequest {
-    pub(crate) fn default_names(self) -> [Option<Cow<'static, str>>; 4] {
-        let (python, python3, extension) = if cfg!(windows) {
-            (
-                Cow::Borrowed("python.exe"),
-                Cow::Borrowed("python3.exe"),
-                ".exe",
-            )
-        } else {
-            (Cow::Borrowed("python"), Cow::Borrowed("python3"), "")
+    /// Return true if the request can be used as one of [`InterpreterRequest::Alternatives`].
+    fn is_alternative(&self) -> bool {
+        matches!(
+            self,
+            Self::Version(_) | Self::Implementation(_) | Self::ImplementationVersion(..)
+        )
+    }
+
+    /// Return the requested Python version, if any.
+    fn version(&self) -> Option<&VersionRequest> {
+        match self {
+            Self::Version(version) | Self::ImplementationVersion(_, version) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// Return the requested Python implementation, if any.
+    fn implementation(&self) -> Option<&ImplementationName> {
+        match self {
+            Self::Implementation(implementation)
+            | Self::ImplementationVersion(implementation, _) => Some(implementation),
+            _ => None,
+        }
+    }
+
+    /// Return the managed toolchain download request that satisfies this request, if any.
+    ///
+    /// Requests for paths and executable names, and for versions without a known download, e.g.,
+    /// `2`, cannot be satisfied by a download.
+    #[cfg(feature = "downloads")]
+    fn download_request(&self) -> Option<PythonDownloadRequest> {
+        let (version, free_threaded) = match self.version().copied().unwrap_or_default() {
+            VersionRequest::Any | VersionRequest::Latest => (None, false),
+            VersionRequest::Major(3) | VersionRequest::MajorWildcard(3) => (None, false),
+            VersionRequest::Major(_) | VersionRequest::MajorWildcard(_) => return None,
+            VersionRequest::MajorMinor(major, minor) => (Some(format!("{major}.{minor}")), false),
+            VersionRequest::MajorMinorPatch(major, minor, patch) => {
+                (Some(format!("{major}.{minor}.{patch}")), false)
+            }
+            VersionRequest::FreeThreaded(major, minor, None) => {
+                (Some(format!("{major}.{minor}")), true)
+            }
+            VersionRequest::FreeThreaded(major, minor, Some(patch)) => {
+                (Some(format!("{major}.{minor}.{patch}")), true)
+            }
+        };
+        let version = match version {
+            Some(version) => Some(PythonVersion::from_str(&version).ok()?),
+            None => None,
         };
+        match self {
+            Self::Any
+            | Self::Version(_)
+            | Self::Implementation(_)
+            | Self::ImplementationVersion(..) => Some(
+                PythonDownloadRequest::new(
+                    version,
+                    self.implementation().copied(),
+                    None,
+                    None,
+                    None,
+                )
+                .with_free_threaded(free_threaded),
+            ),
+            Self::Directory(_)
+            | Self::File(_)
+            | Self::ExecutableName(_)
+            | Self::NamedEnvironment(_)
+            | Self::Alternatives(_) => None,
+        }
+    }
 
+    /// Check if a discovered interpreter satisfies a version or implementation request.
+    ///
+    /// Requests for paths and executable names are satisfied by any interpreter, since they are
+    /// not matched against interpreter metadata.
+    fn satisfied_by(&self, interpreter: &Interpreter) -> bool {
         match self {
-            Self::Any => [Some(python3), Some(python), None, None],
-            Self::Major(major) => [
-                Some(Cow::Owned(format!("python{major}{extension}"))),
-                Some(python),
-                None,
-                None,
-            ],
-            Self::MajorMinor(major, minor) => [
-                Some(Cow::Owned(format!("python{major}.{minor}{extension}"))),
-                Some(Cow::Owned(format!("python{major}{extension}"))),
-                Some(python),
-                None,
-            ],
-            Self::MajorMinorPatch(major, minor, patch) => [
-                Some(Cow::Owned(format!(
-                    "python{major}.{minor}.{patch}{extension}",
-                ))),
-                Some(Cow::Owned(format!("python{major}.{minor}{extension}"))),
-                Some(Cow::Owned(format!("python{major}{extension}"))),
-                Some(python),
-            ],
+            Self::Version(version) => version.matches_interpreter(interpreter),
+            Self::Implementation(implementation) => {
+                interpreter.implementation_name() == implementation.as_str()
+            }
+            Self::ImplementationVersion(implementation, version) => {
+                version.matches_interpreter(interpreter)
+                    && interpreter.implementation_name() == implementation.as_str()
+            }
+            Self::Alternatives(requests) => requests
+                .iter()
+                .any(|request| request.satisfied_by(interpreter)),
+            Self::Any
+            | Self::Directory(_)
+            | Self::File(_)
+            | Self::ExecutableName(_)
+            | Self::NamedEnvironment(_) => true,
         }
     }
 
-    pub(crate) fn possible_names<'a>(
-        &'a self,
-        implementation: Option<&'a ImplementationName>,
-    ) -> impl Iterator<Item = Cow<'static, str>> + 'a {
+    /// Explain why a Python interpreter does not satisfy the request.
+    ///
+    /// Returns an empty list if the interpreter satisfies the request. Along with mismatches of
+    /// the requested version and implementation, interpreters that were built for a different
+    /// architecture than the current machine or whose executable is not executable are reported.
+    ///
+    /// For [`InterpreterRequest::Alternatives`], the reasons of all alternatives are returned if
+    /// none of them are satisfied.
+    pub fn explain_mismatch(&self, interpreter: &Interpreter) -> Vec<MismatchReason> {
+        let mut reasons = self.request_mismatches(interpreter);
+
+        if let Ok(expected) = crate::platform::Arch::from_env() {
+            let found = interpreter.platform().arch();
+            // The architectures are defined in separate crates, but share their names.
+            if expected.to_string() != found.to_string() {
+                reasons.push(MismatchReason::Architecture { expected, found });
+            }
+        }
+
+        if !is_executable(interpreter.sys_executable()) {
+            reasons.push(MismatchReason::NotExecutable(
+                interpreter.sys_executable().to_path_buf(),
+            ));
+        }
+
+        reasons
+    }
+
+    /// Return the requested versions and implementations the interpreter does not match.
+    ///
+    /// See [`InterpreterRequest::explain_mismatch`].
+    fn request_mismatches(&self, interpreter: &Interpreter) -> Vec<MismatchReason> {
+        match self {
+            Self::Version(version) => version.mismatch(interpreter).into_iter().collect(),
+            Self::Implementation(implementation) => {
+                implementation_mismatch(*implementation, interpreter)
+                    .into_iter()
+                    .collect()
+            }
+            Self::ImplementationVersion(implementation, version) => {
+                implementation_mismatch(*implementation, interpreter)
+                    .into_iter()
+                    .chain(version.mismatch(interpreter))
+                    .collect()
+            }
+            Self::Alternatives(requests) => {
+                let mut reasons = Vec::new();
+                for request in requests {
+                    let mismatches = request.request_mismatches(interpreter);
+                    if mismatches.is_empty() {
+                        return Vec::new();
+                    }
+                    for reason in mismatches {
+                        if !reasons.contains(&reason) {
+                            reasons.push(reason);
+                        }
+                    }
+                }
+                reasons
+            }
+            Self::Any
+            | Self::Directory(_)
+            | Self::File(_)
+            | Self::ExecutableName(_)
+            | Self::NamedEnvironment(_) => Vec::new(),
+        }
+    }
+}
+
+/// Return a [`MismatchReason`] if the interpreter is not of the requested implementation.
+fn implementation_mismatch(
+    implementation: ImplementationName,
+    interpreter: &Interpreter,
+) -> Option<MismatchReason> {
+    (interpreter.implementation_name() != implementation.as_str()).then(|| {
+        MismatchReason::Implementation {
+            requested: implementation,
+            found: LenientImplementationName::from(interpreter.implementation_name()).to_string(),
+        }
+    })
+}
+
+/// Check if the path is a file that can be executed.
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs_err::metadata(path)
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+/// Check if the path is a file that can be executed.
+///
+/// On Windows, there is no executable permission, so any file is considered executable.
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+impl VersionRequest {
+    /// Return the names of the executables that may provide the requested version, in order of
+    /// preference.
+    ///
+    /// For example, for Python 3.12 on Unix, this is `python3.12`, `python3`, and `python`. On
+    /// Windows, the names include the `.exe` extension.
+    ///
+    /// If an implementation is provided, its names are preferred over the default names, e.g.,
+    /// `pypy3.12` before `python3.12`. If `free_threaded` is set, only the names of free-threaded
+    /// builds are returned, e.g., `python3.13t` and `python3t`.
+    ///
+    /// These are the names used to search the `PATH` during interpreter discovery.
+    pub fn executable_names(
+        self,
+        implementation: Option<&ImplementationName>,
+        free_threaded: bool,
+    ) -> Vec<String> {
+        let extension = std::env::consts::EXE_SUFFIX;
+        self.executable_stems(implementation, free_threaded)
+            .into_iter()
+            .map(|stem| format!("{stem}{extension}"))
+            .collect()
+    }
+
+    /// Return the executable names without an extension, e.g., `python3.12`.
+    ///
+    /// See [`VersionRequest::executable_names`].
+    fn executable_stems(
+        self,
+        implementation: Option<&ImplementationName>,
+        free_threaded: bool,
+    ) -> Vec<String> {
         implementation
+            .map(|implementation| implementation.as_str())
             .into_iter()
-            .flat_map(move |implementation| {
-                let extension = std::env::consts::EXE_SUFFIX;
-                let name = implementation.as_str();
-                let (python, python3) = if extension.is_empty() {
-                    (Cow::Borrowed(name), Cow::Owned(format!("{name}3")))
-                } else {
-                    (
-                        Cow::Owned(format!("{name}{extension}")),
-                        Cow::Owned(format!("{name}3{extension}")),
-                    )
-                };
+            .chain(std::iter::once("python"))
+            .flat_map(|name| self.executable_stems_for(name, free_threaded))
+            .collect()
+    }
 
-                match self {
-                    Self::Any => [Some(python3), Some(python), None, None],
-                    Self::Major(major) => [
-                        Some(Cow::Owned(format!("{name}{major}{extension}"))),
-                        Some(python),
-                        None,
-                        None,
-                    ],
-                    Self::MajorMinor(major, minor) => [
-                        Some(Cow::Owned(format!("{name}{major}.{minor}{extension}"))),
-                        Some(Cow::Owned(format!("{name}{major}{extension}"))),
-                        Some(python),
-                        None,
-                    ],
-                    Self::MajorMinorPatch(major, minor, patch) => [
-                        Some(Cow::Owned(format!(
-                            "{name}{major}.{minor}.{patch}{extension}",
-                        ))),
-                        Some(Cow::Owned(format!("{name}{major}.{minor}{extension}"))),
-                        Some(Cow::Owned(format!("{name}{major}{extension}"))),
-                        Some(python),
-                    ],
-                }
-            })
-            .chain(self.default_names())
-            .flatten()
+    /// Return the executable names for a single implementation name, e.g., `python` or `pypy`.
+    fn executable_stems_for(self, name: &str, free_threaded: bool) -> Vec<String> {
+        let free_threaded = free_threaded || self.is_free_threaded();
+        let suffix = if free_threaded { "t" } else { "" };
+
+        let mut stems = match self {
+            Self::Any | Self::Latest => vec![format!("{name}3{suffix}")],
+            Self::Major(major) | Self::MajorWildcard(major) => {
+                vec![format!("{name}{major}{suffix}")]
+            }
+            Self::MajorMinor(major, minor) | Self::FreeThreaded(major, minor, None) => vec![
+                format!("{name}{major}.{minor}{suffix}"),
+                format!("{name}{major}{suffix}"),
+            ],
+            Self::MajorMinorPatch(major, minor, patch)
+            | Self::FreeThreaded(major, minor, Some(patch)) => vec![
+                format!("{name}{major}.{minor}.{patch}{suffix}"),
+                format!("{name}{major}.{minor}{suffix}"),
+                format!("{name}{major}{suffix}"),
+            ],
+        };
+        // There is no unversioned name for free-threaded builds.
+        if !free_threaded {
+            stems.push(name.to_string());
+        }
+        stems
     }
 
     /// Check if a interpreter matches the requested Python version.
     fn matches_interpreter(self, interpreter: &Interpreter) -> bool {
         match self {
-            Self::Any => true,
-            Self::Major(major) => interpreter.python_major() == major,
+            Self::Any | Self::Latest => true,
+            Self::Major(major) | Self::MajorWildcard(major) => interpreter.python_major() == major,
             Self::MajorMinor(major, minor) => {
                 (interpreter.python_major(), interpreter.python_minor()) == (major, minor)
             }
@@ -1042,26 +1841,57 @@ impl VersionRequest {
                     interpreter.python_patch(),
                 ) == (major, minor, patch)
             }
+            Self::FreeThreaded(major, minor, patch) => {
+                interpreter.gil_disabled()
+                    && (interpreter.python_major(), interpreter.python_minor()) == (major, minor)
+                    && patch.map_or(true, |patch| interpreter.python_patch() == patch)
+            }
         }
     }
 
+    /// Return a [`MismatchReason`] if the interpreter does not match the requested Python version.
+    fn mismatch(self, interpreter: &Interpreter) -> Option<MismatchReason> {
+        (!self.matches_interpreter(interpreter)).then(|| MismatchReason::Version {
+            requested: self,
+            found: interpreter.python_version().clone(),
+        })
+    }
+
     fn matches_version(self, version: &PythonVersion) -> bool {
         match self {
-            Self::Any => true,
-            Self::Major(major) => version.major() == major,
+            Self::Any | Self::Latest => true,
+            Self::Major(major) | Self::MajorWildcard(major) => version.major() == major,
             Self::MajorMinor(major, minor) => (version.major(), version.minor()) == (major, minor),
             Self::MajorMinorPatch(major, minor, patch) => {
                 (version.major(), version.minor(), version.patch()) == (major, minor, Some(patch))
             }
+            Self::FreeThreaded(major, minor, patch) => {
+                (version.major(), version.minor()) == (major, minor)
+                    && patch.map_or(true, |patch| version.patch() == Some(patch))
+            }
         }
     }
 
+    /// Check if a managed toolchain matches the requested Python version.
+    ///
+    /// Free-threaded requests only match free-threaded toolchains. Requests for a specific version
+    /// only match toolchains with the GIL enabled, while [`VersionRequest::Any`] matches both.
+    pub(crate) fn matches_toolchain(self, toolchain: &Toolchain) -> bool {
+        let matches_build = match self {
+            Self::Any => true,
+            Self::FreeThreaded(..) => toolchain.free_threaded(),
+            _ => !toolchain.free_threaded(),
+        };
+        matches_build && self.matches_version(toolchain.python_version())
+    }
+
     fn matches_major_minor(self, major: u8, minor: u8) -> bool {
         match self {
-            Self::Any => true,
-            Self::Major(self_major) => self_major == major,
+            Self::Any | Self::Latest => true,
+            Self::Major(self_major) | Self::MajorWildcard(self_major) => self_major == major,
             Self::MajorMinor(self_major, self_minor) => (self_major, self_minor) == (major, minor),
-            Self::MajorMinorPatch(self_major, self_minor, _) => {
+            Self::MajorMinorPatch(self_major, self_minor, _)
+            | Self::FreeThreaded(self_major, self_minor, _) => {
                 (self_major, self_minor) == (major, minor)
             }
         }
@@ -1074,9 +1904,22 @@ impl VersionRequest {
             Self::Major(..) => false,
             Self::MajorMinor(..) => false,
             Self::MajorMinorPatch(..) => true,
+            Self::Latest => false,
+            Self::MajorWildcard(..) => false,
+            Self::FreeThreaded(_, _, patch) => patch.is_some(),
         }
     }
 
+    /// Return true if a free-threaded build is requested.
+    pub(crate) fn is_free_threaded(self) -> bool {
+        matches!(self, Self::FreeThreaded(..))
+    }
+
+    /// Return true if the newest matching interpreter should be used, rather than the first.
+    pub(crate) fn prefers_newest(self) -> bool {
+        matches!(self, Self::Latest | Self::MajorWildcard(..))
+    }
+
     /// Return a new `VersionRequest` without the patch version.
     #[must_use]
     fn without_patch(self) -> Self {
@@ -1085,14 +1928,38 @@ impl VersionRequest {
             Self::Major(major) => Self::Major(major),
             Self::MajorMinor(major, minor) => Self::MajorMinor(major, minor),
             Self::MajorMinorPatch(major, minor, _) => Self::MajorMinor(major, minor),
+            Self::Latest => Self::Latest,
+            Self::MajorWildcard(major) => Self::MajorWildcard(major),
+            Self::FreeThreaded(major, minor, _) => Self::FreeThreaded(major, minor, None),
         }
     }
 }
 
 impl FromStr for VersionRequest {
-    type Err = ParseIntError;
+    type Err = VersionRequestError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // e.g. `latest`
+        if s == "latest" {
+            return Ok(VersionRequest::Latest);
+        }
+        // e.g. `3.*`
+        if let Some(major) = s.strip_suffix(".*") {
+            return Ok(VersionRequest::MajorWildcard(major.parse::<u8>()?));
+        }
+        // e.g. `3.13t`
+        if let Some(version) = s.strip_suffix('t') {
+            return match VersionRequest::from_str(version)? {
+                VersionRequest::MajorMinor(major, minor) => {
+                    Ok(VersionRequest::FreeThreaded(major, minor, None))
+                }
+                VersionRequest::MajorMinorPatch(major, minor, patch) => {
+                    Ok(VersionRequest::FreeThreaded(major, minor, Some(patch)))
+                }
+                _ => Err(VersionRequestError::FreeThreadedWithoutMinor(s.to_string())),
+            };
+        }
+
         let versions = s
             .splitn(3, '.')
             .map(str::parse::<u8>)
@@ -1128,6 +1995,10 @@ impl fmt::Display for VersionRequest {
             Self::MajorMinorPatch(major, minor, patch) => {
                 write!(f, "{major}.{minor}.{patch}")
             }
+            Self::Latest => f.write_str("latest"),
+            Self::MajorWildcard(major) => write!(f, "{major}.*"),
+            Self::FreeThreaded(major, minor, None) => write!(f, "{major}.{minor}t"),
+            Self::FreeThreaded(major, minor, Some(patch)) => write!(f, "{major}.{minor}.{patch}t"),
         }
     }
 }
@@ -1143,10 +2014,15 @@ impl SourceSelector {
     /// Return true if this selector includes the given [`InterpreterSource`].
     fn contains(&self, source: InterpreterSource) -> bool {
         match self {
-            Self::All(preview) => {
-                // Always return `true` except for `ManagedToolchain` which requires preview mode
-                source != InterpreterSource::ManagedToolchain || preview.is_enabled()
-            }
+            Self::All(preview) => match source {
+                // `ManagedToolchain` requires preview mode
+                InterpreterSource::ManagedToolchain | InterpreterSource::FetchedToolchain => {
+                    preview.is_enabled()
+                }
+                // Windows executables in WSL must be selected explicitly
+                InterpreterSource::WslWindowsSearchPath => false,
+                _ => true,
+            },
             Self::System(preview) => {
                 [
                     InterpreterSource::ProvidedPath,
@@ -1157,8 +2033,10 @@ impl SourceSelector {
                 ]
                 .contains(&source)
                     // Allow `ManagedToolchain` in preview
-                    || (source == InterpreterSource::ManagedToolchain
-                        && preview.is_enabled())
+                    || (matches!(
+                        source,
+                        InterpreterSource::ManagedToolchain | InterpreterSource::FetchedToolchain
+                    ) && preview.is_enabled())
             }
             Self::VirtualEnv => [
                 InterpreterSource::DiscoveredEnvironment,
@@ -1194,6 +2072,54 @@ impl SourceSelector {
     }
 }
 
+impl ToolchainFetch {
+    /// Create a [`ToolchainFetch::Confirm`] policy from a callback.
+    pub fn confirm_with(
+        callback: impl Fn(&InterpreterRequest, &PythonDownload) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self::Confirm(Arc::new(callback))
+    }
+
+    /// Returns true if managed toolchains may be installed, possibly after confirmation.
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::Never)
+    }
+
+    /// Decide whether the `download` should be installed to satisfy the `request`.
+    pub fn allows(&self, request: &InterpreterRequest, download: &PythonDownload) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Always => true,
+            Self::Confirm(callback) => {
+                let confirmed = callback.confirm(request, download);
+                if !confirmed {
+                    debug!("Installation of managed toolchain `{download}` was declined");
+                }
+                confirmed
+            }
+        }
+    }
+}
+
+impl<F> ConfirmToolchainFetch for F
+where
+    F: Fn(&InterpreterRequest, &PythonDownload) -> bool + Send + Sync,
+{
+    fn confirm(&self, request: &InterpreterRequest, download: &PythonDownload) -> bool {
+        self(request, download)
+    }
+}
+
+impl fmt::Debug for ToolchainFetch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Never => f.write_str("Never"),
+            Self::Always => f.write_str("Always"),
+            Self::Confirm(_) => f.write_str("Confirm(..)"),
+        }
+    }
+}
+
 impl SystemPython {
     /// Returns true if a system Python is allowed.
     pub fn is_allowed(&self) -> bool {
@@ -1214,12 +2140,16 @@ impl fmt::Display for InterpreterRequest {
             Self::Directory(path) => write!(f, "directory `{}`", path.user_display()),
             Self::File(path) => write!(f, "path `{}`", path.user_display()),
             Self::ExecutableName(name) => write!(f, "executable name `{name}`"),
+            Self::NamedEnvironment(name) => write!(f, "named environment `{name}`"),
             Self::Implementation(implementation) => {
                 write!(f, "{implementation}")
             }
             Self::ImplementationVersion(implementation, version) => {
                 write!(f, "{implementation} {version}")
             }
+            Self::Alternatives(requests) => {
+                f.write_str(&requests.iter().map(ToString::to_string).join(" or "))
+            }
         }
     }
 }
@@ -1235,6 +2165,8 @@ impl fmt::Display for InterpreterSource {
             Self::PyLauncher => f.write_str("`py` launcher output"),
             Self::ManagedToolchain => f.write_str("managed toolchains"),
             Self::ParentInterpreter => f.write_str("parent interpreter"),
+            Self::WslWindowsSearchPath => f.write_str("Windows search path"),
+            Self::FetchedToolchain => f.write_str("downloaded managed toolchain"),
         }
     }
 }
@@ -1263,6 +2195,13 @@ impl fmt::Display for InterpreterNotFound {
                     "No interpreter found for {implementation} {version} in {sources}"
                 )
             }
+            Self::NoMatchingAlternatives(sources, requests) => {
+                write!(
+                    f,
+                    "No interpreter found for {} in {sources}",
+                    requests.iter().map(ToString::to_string).join(" or ")
+                )
+            }
             Self::FileNotFound(path) => write!(
                 f,
                 "Requested interpreter path `{}` does not exist",
@@ -1273,6 +2212,11 @@ impl fmt::Display for InterpreterNotFound {
                 "Requested interpreter directory `{}` does not exist",
                 path.user_display()
             ),
+            Self::NamedEnvironmentNotFound(name, root) => write!(
+                f,
+                "No environment named `{name}` found in `{}`",
+                root.user_display()
+            ),
             Self::ExecutableNotFoundInDirectory(directory, executable) => {
                 write!(
                     f,
@@ -1366,15 +2310,20 @@ impl DiscoveredInterpreter {
 #[cfg(test)]
 mod tests {
 
-    use std::{path::PathBuf, str::FromStr};
+    use std::{
+        path::{Path, PathBuf},
+        str::FromStr,
+    };
 
     use test_log::test;
 
     use assert_fs::{prelude::*, TempDir};
 
     use crate::{
-        discovery::{InterpreterRequest, VersionRequest},
+        discovery::{InterpreterRequest, ToolchainFetch, VersionRequest, VersionRequestError},
+        downloads::PythonDownload,
         implementation::ImplementationName,
+        managed::Toolchain,
     };
 
     #[test]
@@ -1420,6 +2369,32 @@ mod tests {
             InterpreterRequest::parse("pypy310"),
             InterpreterRequest::ExecutableName("pypy310".to_string())
         );
+        assert_eq!(
+            InterpreterRequest::parse("env:myproj"),
+            InterpreterRequest::NamedEnvironment("myproj".to_string())
+        );
+        assert_eq!(
+            InterpreterRequest::parse("3.12|3.11"),
+            InterpreterRequest::Alternatives(vec![
+                InterpreterRequest::Version(VersionRequest::from_str("3.12").unwrap()),
+                InterpreterRequest::Version(VersionRequest::from_str("3.11").unwrap()),
+            ])
+        );
+        assert_eq!(
+            InterpreterRequest::parse("pypy3.10 | 3.11"),
+            InterpreterRequest::Alternatives(vec![
+                InterpreterRequest::ImplementationVersion(
+                    ImplementationName::PyPy,
+                    VersionRequest::from_str("3.10").unwrap()
+                ),
+                InterpreterRequest::Version(VersionRequest::from_str("3.11").unwrap()),
+            ])
+        );
+        assert_eq!(
+            InterpreterRequest::parse("3.12|foo"),
+            InterpreterRequest::ExecutableName("3.12|foo".to_string()),
+            "Alternatives must all be versions or implementations"
+        );
 
         let tempdir = TempDir::new().unwrap();
         assert_eq!(
@@ -1445,6 +2420,124 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "downloads")]
+    fn download_request() {
+        let download_request = |request: &str| {
+            InterpreterRequest::parse(request)
+                .download_request()
+                .map(|request| request.to_string())
+        };
+        assert_eq!(download_request("3.12").as_deref(), Some("3.12"));
+        assert_eq!(
+            download_request("cpython3.12").as_deref(),
+            Some("cpython-3.12")
+        );
+        assert_eq!(download_request("3.13t").as_deref(), Some("3.13t"));
+        assert_eq!(download_request("3").as_deref(), Some(""));
+        assert_eq!(download_request("2"), None);
+        assert_eq!(download_request("./foo"), None);
+    }
+
+    #[test]
+    fn toolchain_fetch_policy() {
+        let request = InterpreterRequest::parse("3.12");
+        let download = PythonDownload::from_key("cpython-3.12.3-linux-x86_64-gnu").unwrap();
+
+        assert!(!ToolchainFetch::default().allows(&request, download));
+        assert!(ToolchainFetch::Always.allows(&request, download));
+
+        let policy = ToolchainFetch::confirm_with(|request, download| {
+            *request == InterpreterRequest::parse("3.12")
+                && download.to_string() == "cpython-3.12.3-linux-x86_64-gnu"
+        });
+        assert!(policy.is_enabled());
+        assert!(policy.allows(&request, download));
+        assert!(!policy.allows(&InterpreterRequest::parse("3.11"), download));
+    }
+
+    #[test]
+    fn executable_names() {
+        let names = |version: &str, implementation, free_threaded| {
+            VersionRequest::from_str(version)
+                .unwrap()
+                .executable_names(implementation, free_threaded)
+                .into_iter()
+                .map(|name| {
+                    name.strip_suffix(std::env::consts::EXE_SUFFIX)
+                        .unwrap()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("latest", None, false), ["python3", "python"]);
+        assert_eq!(names("3", None, false), ["python3", "python"]);
+        assert_eq!(
+            names("3.12", None, false),
+            ["python3.12", "python3", "python"]
+        );
+        assert_eq!(
+            names("3.12.1", None, false),
+            ["python3.12.1", "python3.12", "python3", "python"]
+        );
+        assert_eq!(
+            names("3.10", Some(&ImplementationName::PyPy), false),
+            [
+                "pypy3.10",
+                "pypy3",
+                "pypy",
+                "python3.10",
+                "python3",
+                "python"
+            ]
+        );
+        assert_eq!(names("3.13", None, true), ["python3.13t", "python3t"]);
+        assert_eq!(names("3.13t", None, false), ["python3.13t", "python3t"]);
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn search_path_extensions() {
+        assert_eq!(
+            super::search_path_extensions(Some(".COM;.EXE;.BAT;.CMD;.VBS")),
+            [".com", ".exe", ".bat", ".cmd", ".vbs"]
+        );
+        assert_eq!(
+            super::search_path_extensions(Some(".EXE; .exe;;.CMD")),
+            [".exe", ".cmd"]
+        );
+        assert_eq!(
+            super::search_path_extensions(None),
+            [".exe", ".bat", ".cmd"]
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn search_path_extensions() {
+        assert_eq!(super::search_path_extensions(Some(".EXE;.CMD")), [""]);
+    }
+
+    #[test]
+    fn is_windows_drive_mount() {
+        assert!(super::is_windows_drive_mount(Path::new(
+            "/mnt/c/Python312/python.exe"
+        )));
+        assert!(super::is_windows_drive_mount(Path::new(
+            "/mnt/D/Users/ferris/AppData/Local/Microsoft/WindowsApps/python3"
+        )));
+        assert!(!super::is_windows_drive_mount(Path::new(
+            "/mnt/data/bin/python3"
+        )));
+        assert!(!super::is_windows_drive_mount(Path::new(
+            "/usr/bin/python3"
+        )));
+        assert!(!super::is_windows_drive_mount(Path::new(
+            "mnt/c/python.exe"
+        )));
+    }
+
     #[test]
     fn version_request_from_str() {
         assert_eq!(VersionRequest::from_str("3"), Ok(VersionRequest::Major(3)));
@@ -1457,5 +2550,51 @@ mod tests {
             Ok(VersionRequest::MajorMinorPatch(3, 12, 1))
         );
         assert!(VersionRequest::from_str("1.foo.1").is_err());
+        assert_eq!(
+            VersionRequest::from_str("latest"),
+            Ok(VersionRequest::Latest)
+        );
+        assert_eq!(
+            VersionRequest::from_str("3.*"),
+            Ok(VersionRequest::MajorWildcard(3))
+        );
+        assert!(VersionRequest::from_str("3.12.*").is_err());
+        assert!(VersionRequest::from_str("*").is_err());
+        assert_eq!(
+            VersionRequest::from_str("3.13t"),
+            Ok(VersionRequest::FreeThreaded(3, 13, None))
+        );
+        assert_eq!(
+            VersionRequest::from_str("3.13.1t"),
+            Ok(VersionRequest::FreeThreaded(3, 13, Some(1)))
+        );
+        assert_eq!(
+            VersionRequest::FreeThreaded(3, 13, None).to_string(),
+            "3.13t"
+        );
+        assert_eq!(
+            VersionRequest::from_str("3t"),
+            Err(VersionRequestError::FreeThreadedWithoutMinor(
+                "3t".to_string()
+            ))
+        );
+        assert!(VersionRequest::from_str("3.*t").is_err());
+    }
+
+    #[test]
+    fn version_request_matches_toolchain() {
+        let gil =
+            Toolchain::new(PathBuf::from("/toolchains/cpython-3.13.0-linux-x86_64-gnu")).unwrap();
+        let free_threaded = Toolchain::new(PathBuf::from(
+            "/toolchains/cpython-3.13.0t-linux-x86_64-gnu",
+        ))
+        .unwrap();
+
+        assert!(VersionRequest::Any.matches_toolchain(&gil));
+        assert!(VersionRequest::Any.matches_toolchain(&free_threaded));
+        assert!(VersionRequest::MajorMinor(3, 13).matches_toolchain(&gil));
+        assert!(!VersionRequest::MajorMinor(3, 13).matches_toolchain(&free_threaded));
+        assert!(!VersionRequest::FreeThreaded(3, 13, None).matches_toolchain(&gil));
+        assert!(VersionRequest::FreeThreaded(3, 13, None).matches_toolchain(&free_threaded));
     }
 }
@@ -1,19 +1,41 @@
-use std::fmt::Display;
+use std::borrow::Cow;
+use std::fmt::{self, Display};
 use std::io;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
+#[cfg(feature = "downloads")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use crate::implementation::{Error as ImplementationError, ImplementationName};
-use crate::platform::{Arch, Error as PlatformError, Libc, Os};
+use crate::managed::Reporter;
+#[cfg(feature = "downloads")]
+use crate::managed::{lock_path, make_relocatable, write_manifest, ToolchainMetadata};
+use crate::platform::{Arch, Error as PlatformError, Libc, Os, Platform};
 use crate::PythonVersion;
 use thiserror::Error;
+#[cfg(feature = "downloads")]
 use uv_client::BetterReqwestError;
 
+#[cfg(feature = "downloads")]
+use base64::{prelude::BASE64_STANDARD, Engine};
+#[cfg(feature = "downloads")]
 use futures::TryStreamExt;
 
-use tokio_util::compat::FuturesAsyncReadCompatExt;
+#[cfg(feature = "downloads")]
+use pypi_types::{HashAlgorithm, HashDigest};
+#[cfg(feature = "downloads")]
+use tokio::io::{AsyncRead, AsyncSeekExt, AsyncWriteExt, ReadBuf};
+#[cfg(feature = "downloads")]
 use tracing::debug;
 use url::Url;
+#[cfg(feature = "downloads")]
+use uv_extract::hash::{HashReader, Hasher};
+#[cfg(feature = "downloads")]
+use uv_fs::LockedFile;
 use uv_fs::Simplified;
 
 #[derive(Error, Debug)]
@@ -26,10 +48,16 @@ pub enum Error {
     ImplementationError(#[from] ImplementationError),
     #[error("invalid python version: {0}")]
     InvalidPythonVersion(String),
+    #[cfg(feature = "downloads")]
     #[error("download failed")]
     NetworkError(#[from] BetterReqwestError),
+    #[cfg(feature = "downloads")]
     #[error("download failed")]
     NetworkMiddlewareError(#[source] anyhow::Error),
+    #[cfg(feature = "downloads")]
+    #[error("download was interrupted")]
+    DownloadInterrupted(#[source] reqwest::Error),
+    #[cfg(feature = "downloads")]
     #[error(transparent)]
     ExtractError(#[from] uv_extract::Error),
     #[error("invalid download url")]
@@ -48,8 +76,63 @@ pub enum Error {
         #[source]
         err: io::Error,
     },
+    #[error("failed to remove toolchain directory: {0}", dir.user_display())]
+    RemoveError {
+        dir: PathBuf,
+        #[source]
+        err: io::Error,
+    },
+    #[error("failed to read toolchain metadata: {0}", path.user_display())]
+    MetadataError {
+        path: PathBuf,
+        #[source]
+        err: serde_json::Error,
+    },
+    #[error("invalid toolchain alias: {0}")]
+    InvalidAlias(String),
+    #[error("toolchain is not installed: {0}")]
+    NotInstalled(String),
+    #[error("toolchain has no manifest of its files: {}", _0.user_display())]
+    MissingManifest(PathBuf),
     #[error("failed to parse toolchain directory name: {0}")]
     NameError(String),
+    #[error("failed to read download stream")]
+    HashExhaustion(#[source] io::Error),
+    #[error(
+        "hash mismatch for `{installation}`\n\nexpected:\n  {expected}\n\ncomputed:\n  {actual}"
+    )]
+    HashMismatch {
+        installation: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("invalid Ed25519 public key, expected 32 base64-encoded bytes: {0}")]
+    InvalidPublicKey(String),
+    #[error("signature verification failed for `{installation}`: {reason}")]
+    SignatureVerification {
+        installation: String,
+        reason: String,
+    },
+    #[error("no signature found for archive, expected it at: {}", _0.user_display())]
+    MissingSignature(PathBuf),
+    #[error("download url is not a python-build-standalone release and cannot be mirrored: {0}")]
+    MirrorUnsupported(String),
+    #[error("not a known toolchain archive: {}", _0.user_display())]
+    UnknownArchive(PathBuf),
+    #[error("no download found for request: {0}")]
+    NoDownloadFound(String),
+    #[error("toolchain does not contain a Python executable at: {}", _0.user_display())]
+    MissingExecutable(PathBuf),
+    #[error("refusing to replace `{}`, which is not a shim for a managed toolchain", _0.user_display())]
+    ShimCollision(PathBuf),
+    #[error("failed to create shim at: {0}", path.user_display())]
+    ShimError {
+        path: PathBuf,
+        #[source]
+        err: io::Error,
+    },
+    #[error("failed to create trampoline for shim")]
+    TrampolineError(#[from] install_wheel_rs::Error),
 }
 
 #[derive(Debug, PartialEq)]
@@ -62,6 +145,8 @@ pub struct PythonDownload {
     major: u8,
     minor: u8,
     patch: u8,
+    /// Whether the download is a free-threaded build, i.e., with the GIL disabled.
+    free_threaded: bool,
     url: &'static str,
     sha256: Option<&'static str>,
 }
@@ -73,6 +158,7 @@ pub struct PythonDownloadRequest {
     arch: Option<Arch>,
     os: Option<Os>,
     libc: Option<Libc>,
+    free_threaded: bool,
 }
 
 impl PythonDownloadRequest {
@@ -89,6 +175,7 @@ impl PythonDownloadRequest {
             arch,
             os,
             libc,
+            free_threaded: false,
         }
     }
 
@@ -116,6 +203,15 @@ impl PythonDownloadRequest {
         self
     }
 
+    /// Request a free-threaded build, e.g., `3.13t`.
+    ///
+    /// Free-threaded and regular builds never satisfy each other's requests.
+    #[must_use]
+    pub fn with_free_threaded(mut self, free_threaded: bool) -> Self {
+        self.free_threaded = free_threaded;
+        self
+    }
+
     pub fn fill(mut self) -> Result<Self, Error> {
         if self.implementation.is_none() {
             self.implementation = Some(ImplementationName::CPython);
@@ -127,24 +223,64 @@ impl PythonDownloadRequest {
             self.os = Some(Os::from_env()?);
         }
         if self.libc.is_none() {
-            self.libc = Some(Libc::from_env());
+            self.libc = Some(Libc::from_env()?);
         }
         Ok(self)
     }
 }
 
+impl Display for PythonDownloadRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(implementation) = self.implementation {
+            parts.push(implementation.as_str().to_string());
+        }
+        if let Some(version) = &self.version {
+            if self.free_threaded {
+                parts.push(format!("{version}t"));
+            } else {
+                parts.push(version.to_string());
+            }
+        }
+        if let Some(os) = &self.os {
+            parts.push(os.to_string().to_lowercase());
+        }
+        if let Some(arch) = self.arch {
+            parts.push(arch.to_string());
+        }
+        if let Some(libc) = self.libc {
+            parts.push(libc.to_string());
+        }
+        write!(f, "{}", parts.join("-"))
+    }
+}
+
 impl FromStr for PythonDownloadRequest {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // TODO(zanieb): Implement parsing of additional request parts
+        // e.g. `3.13t`
+        let (s, free_threaded) = match s.strip_suffix('t') {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
         let version = PythonVersion::from_str(s).map_err(Error::InvalidPythonVersion)?;
-        Ok(Self::new(Some(version), None, None, None, None))
+        Ok(Self::new(Some(version), None, None, None, None).with_free_threaded(free_threaded))
     }
 }
 
 include!("downloads.inc");
 
+/// The base URL of the python-build-standalone releases, which can be replaced with a mirror.
+const PYTHON_BUILD_STANDALONE_BASE_URL: &str =
+    "https://github.com/indygreg/python-build-standalone/releases/download";
+
+/// The number of times an interrupted download is resumed before giving up.
+#[cfg(feature = "downloads")]
+const DOWNLOAD_RETRIES: u32 = 3;
+
+#[cfg(feature = "downloads")]
 pub enum DownloadResult {
     AlreadyAvailable(PathBuf),
     Fetched(PathBuf),
@@ -168,11 +304,19 @@ impl PythonDownload {
                     continue;
                 }
             }
+            if let Some(libc) = &request.libc {
+                if download.libc != *libc {
+                    continue;
+                }
+            }
             if let Some(implementation) = &request.implementation {
                 if download.implementation != *implementation {
                     continue;
                 }
             }
+            if download.free_threaded != request.free_threaded {
+                continue;
+            }
             if let Some(version) = &request.version {
                 if download.major != version.major() {
                     continue;
@@ -191,21 +335,98 @@ impl PythonDownload {
         None
     }
 
+    /// Iterate over all known downloads, newest versions first.
+    pub fn iter_all() -> impl Iterator<Item = &'static PythonDownload> {
+        PYTHON_DOWNLOADS.iter()
+    }
+
+    /// The key of the download, e.g., `cpython-3.12.3-linux-x86_64-gnu`.
+    pub fn key(&self) -> &str {
+        self.key
+    }
+
+    pub fn implementation(&self) -> ImplementationName {
+        self.implementation
+    }
+
+    /// The platform the download was built for.
+    pub fn platform(&self) -> Platform {
+        Platform::new(self.os.clone(), self.arch, self.libc)
+    }
+
     pub fn url(&self) -> &str {
         self.url
     }
 
+    /// The filename of the archive, e.g.,
+    /// `cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-install_only.tar.gz`.
+    pub fn archive_filename(&self) -> Cow<'_, str> {
+        let filename = self.url.rsplit('/').next().unwrap_or(self.url);
+        urlencoding::decode(filename).unwrap_or(Cow::Borrowed(filename))
+    }
+
+    /// Return the [`PythonDownload`] with the given archive filename, if it exists.
+    pub fn from_archive_filename(filename: &str) -> Option<&'static PythonDownload> {
+        PYTHON_DOWNLOADS
+            .iter()
+            .find(|download| download.archive_filename() == filename)
+    }
+
+    /// The URL to download the archive from, replacing the python-build-standalone release base
+    /// URL with the given mirror, if any.
+    ///
+    /// For example, with the mirror `https://example.com/pbs`, the archive
+    /// `https://github.com/indygreg/python-build-standalone/releases/download/20240415/cpython-3.12.3.tar.zst`
+    /// is downloaded from `https://example.com/pbs/20240415/cpython-3.12.3.tar.zst`.
+    pub fn download_url(&self, mirror: Option<&str>) -> Result<Url, Error> {
+        let Some(mirror) = mirror else {
+            return Ok(Url::parse(self.url)?);
+        };
+        let Some(suffix) = self.url.strip_prefix(PYTHON_BUILD_STANDALONE_BASE_URL) else {
+            return Err(Error::MirrorUnsupported(self.url.to_string()));
+        };
+        Ok(Url::parse(&format!(
+            "{}/{}",
+            mirror.trim_end_matches('/'),
+            suffix.trim_start_matches('/')
+        ))?)
+    }
+
     pub fn sha256(&self) -> Option<&str> {
         self.sha256
     }
 
+    /// Whether the download is a free-threaded build, i.e., with the GIL disabled.
+    pub fn free_threaded(&self) -> bool {
+        self.free_threaded
+    }
+
+    pub fn python_version(&self) -> PythonVersion {
+        PythonVersion::from_str(&format!("{}.{}.{}", self.major, self.minor, self.patch))
+            .expect("Python downloads should always have valid versions")
+    }
+}
+
+#[cfg(feature = "downloads")]
+impl PythonDownload {
     /// Download and extract
     pub async fn fetch(
         &self,
         client: &uv_client::BaseClient,
         parent_path: &Path,
     ) -> Result<DownloadResult, Error> {
-        let url = Url::parse(self.url)?;
+        self.fetch_with_options(client, parent_path, &FetchOptions::from_env())
+            .await
+    }
+
+    /// Download and extract with the given [`FetchOptions`].
+    pub async fn fetch_with_options(
+        &self,
+        client: &uv_client::BaseClient,
+        parent_path: &Path,
+        options: &FetchOptions,
+    ) -> Result<DownloadResult, Error> {
+        let url = self.download_url(options.mirror.as_deref())?;
         let path = parent_path.join(self.key).clone();
 
         // If it already exists, return it
@@ -213,26 +434,222 @@ impl PythonDownload {
             return Ok(DownloadResult::AlreadyAvailable(path));
         }
 
+        // Wait for any concurrent installation of the toolchain, and reuse its result
+        let _lock = self.lock(parent_path).await?;
+        if path.is_dir() {
+            debug!("Toolchain `{self}` was installed concurrently");
+            return Ok(DownloadResult::AlreadyAvailable(path));
+        }
+
         let filename = url.path_segments().unwrap().last().unwrap();
-        let response = client.get(url.clone()).send().await?;
 
-        // Ensure the request was successful.
-        response.error_for_status_ref()?;
+        // Download the archive to a `.part` file next to the toolchains, such that an interrupted
+        // download can be resumed.
+        let part = parent_path.join(format!("{filename}.part"));
+        let reporter = options.reporter.as_deref();
+        self.download_archive(client, &url, &part, reporter).await?;
+
+        // Fetch the detached signature published next to the archive.
+        let signature = match &options.signature_policy {
+            SignaturePolicy::ChecksumOnly => None,
+            SignaturePolicy::Require(verifier) => {
+                let url = Url::parse(&format!("{url}{}", verifier.suffix()))?;
+                debug!("Downloading signature from {url}");
+                let signature = client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .bytes()
+                    .await?;
+                Some((verifier.as_ref(), signature))
+            }
+        };
+        let signature = signature
+            .as_ref()
+            .map(|(verifier, signature)| (*verifier, signature.as_ref()));
+
+        if let Err(err) = self
+            .unpack(
+                &part,
+                filename,
+                url.as_str(),
+                parent_path,
+                signature,
+                reporter,
+            )
+            .await
+        {
+            // The archive may be corrupt, so resuming the download from it would fail again.
+            // Without a known digest, corruption is only detected when the archive is extracted.
+            if let Err(err) = fs_err::tokio::remove_file(&part).await {
+                debug!("Failed to remove partial download: {err}");
+            }
+            return Err(err);
+        }
+
+        // The archive is no longer needed once it's installed.
+        fs_err::tokio::remove_file(&part).await?;
+
+        Ok(DownloadResult::Fetched(path))
+    }
+
+    /// Install a pre-downloaded archive of this toolchain, e.g., for offline installation.
+    ///
+    /// The archive is verified and extracted in the same way as a downloaded archive, reporting
+    /// the progress to the reporter, if any. If the policy requires a signature, it's read from
+    /// next to the archive, e.g.,
+    /// `cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-install_only.tar.gz.sig`.
+    pub async fn install_archive(
+        &self,
+        archive: &Path,
+        parent_path: &Path,
+        policy: &SignaturePolicy,
+        reporter: Option<&dyn Reporter>,
+    ) -> Result<DownloadResult, Error> {
+        let path = parent_path.join(self.key);
+
+        // If it already exists, return it
+        if path.is_dir() {
+            return Ok(DownloadResult::AlreadyAvailable(path));
+        }
+
+        // Wait for any concurrent installation of the toolchain, and reuse its result
+        let _lock = self.lock(parent_path).await?;
+        if path.is_dir() {
+            debug!("Toolchain `{self}` was installed concurrently");
+            return Ok(DownloadResult::AlreadyAvailable(path));
+        }
+
+        let filename = archive
+            .file_name()
+            .and_then(|filename| filename.to_str())
+            .ok_or_else(|| Error::UnknownArchive(archive.to_path_buf()))?;
+        let signature = match policy {
+            SignaturePolicy::ChecksumOnly => None,
+            SignaturePolicy::Require(verifier) => {
+                let path = archive.with_file_name(format!("{filename}{}", verifier.suffix()));
+                let signature = match fs_err::tokio::read(&path).await {
+                    Ok(signature) => signature,
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                        return Err(Error::MissingSignature(path));
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+                Some((verifier.as_ref(), signature))
+            }
+        };
+        let signature = signature
+            .as_ref()
+            .map(|(verifier, signature)| (*verifier, signature.as_slice()));
+        self.unpack(
+            archive,
+            filename,
+            self.url(),
+            parent_path,
+            signature,
+            reporter,
+        )
+        .await?;
+
+        Ok(DownloadResult::Fetched(path))
+    }
+
+    /// Acquire an exclusive lock for installing this toolchain into the parent directory.
+    ///
+    /// Installations of the same toolchain by parallel processes are serialized, such that they
+    /// don't share partial downloads or replace each other's results.
+    async fn lock(&self, parent_path: &Path) -> Result<LockedFile, Error> {
+        fs_err::tokio::create_dir_all(parent_path)
+            .await
+            .map_err(Error::DownloadDirError)?;
+        let path = lock_path(parent_path, self.key);
+        let key = self.key;
+        let lock = tokio::task::spawn_blocking(move || LockedFile::acquire(path, key))
+            .await
+            .map_err(io::Error::from)??;
+        Ok(lock)
+    }
+
+    /// Verify the archive and extract it into the toolchain directory.
+    ///
+    /// The `filename` determines the archive format. If a verifier is given, the archive must
+    /// match the detached signature before it's extracted. The `url` the archive was downloaded
+    /// from, e.g., from a mirror, is recorded in the [`ToolchainMetadata`].
+    async fn unpack(
+        &self,
+        archive: &Path,
+        filename: &str,
+        url: &str,
+        parent_path: &Path,
+        signature: Option<(&dyn VerifySignature, &[u8])>,
+        reporter: Option<&dyn Reporter>,
+    ) -> Result<(), Error> {
+        let path = parent_path.join(self.key);
 
-        // Download and extract into a temporary directory.
+        // Extract into a temporary directory.
         let temp_dir = tempfile::tempdir_in(parent_path).map_err(Error::DownloadDirError)?;
 
-        debug!(
-            "Downloading {url} to temporary location {}",
-            temp_dir.path().display()
-        );
-        let reader = response
-            .bytes_stream()
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
-            .into_async_read();
+        let mut hashers = self
+            .sha256
+            .into_iter()
+            .map(|_| Hasher::from(HashAlgorithm::Sha256))
+            .collect::<Vec<_>>();
+        let mut file = fs_err::tokio::File::open(archive).await?;
+        let size = file.metadata().await?.len();
+        let mut hasher = HashReader::new(&mut file, &mut hashers);
 
-        debug!("Extracting {filename}");
-        uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
+        match signature {
+            None => {
+                // Hash the archive as it is extracted.
+                debug!("Extracting {filename}");
+                let progress =
+                    reporter.map(|reporter| (reporter, reporter.on_extract_start(self, size)));
+                let mut reader = ProgressReader::new(&mut hasher, progress);
+                uv_extract::stream::archive(&mut reader, filename, temp_dir.path()).await?;
+
+                // Exhaust the reader to hash any trailing bytes the extractor didn't consume.
+                hasher.finish().await.map_err(Error::HashExhaustion)?;
+                if let Some((reporter, id)) = progress {
+                    reporter.on_extract_complete(self, id);
+                }
+
+                // Verify the digest before the toolchain is persisted; on mismatch, the temporary
+                // directory is removed when it's dropped.
+                let progress = reporter.map(|reporter| (reporter, reporter.on_verify_start(self)));
+                self.verify_hash(hashers)?;
+                if let Some((reporter, id)) = progress {
+                    reporter.on_verify_complete(self, id);
+                }
+            }
+            Some((verifier, signature)) => {
+                // The signature covers the archive, so it must be verified before it's extracted.
+                let progress = reporter.map(|reporter| (reporter, reporter.on_verify_start(self)));
+                hasher.finish().await.map_err(Error::HashExhaustion)?;
+                self.verify_hash(hashers)?;
+
+                debug!("Verifying signature of {filename}");
+                verifier
+                    .verify(self, archive, signature)
+                    .map_err(|reason| Error::SignatureVerification {
+                        installation: self.key.to_string(),
+                        reason,
+                    })?;
+                if let Some((reporter, id)) = progress {
+                    reporter.on_verify_complete(self, id);
+                }
+
+                debug!("Extracting {filename}");
+                file.seek(io::SeekFrom::Start(0)).await?;
+                let progress =
+                    reporter.map(|reporter| (reporter, reporter.on_extract_start(self, size)));
+                let mut reader = ProgressReader::new(&mut file, progress);
+                uv_extract::stream::archive(&mut reader, filename, temp_dir.path()).await?;
+                if let Some((reporter, id)) = progress {
+                    reporter.on_extract_complete(self, id);
+                }
+            }
+        }
 
         // Extract the top-level directory.
         let extracted = match uv_extract::strip_component(temp_dir.path()) {
@@ -241,6 +658,14 @@ impl PythonDownload {
             Err(err) => return Err(err.into()),
         };
 
+        // Make the toolchain independent of its location, before its files are recorded
+        make_relocatable(&extracted)?;
+
+        // Record the metadata and the digests of the files of the toolchain, such that they're
+        // persisted with it
+        write_manifest(&extracted)?;
+        ToolchainMetadata::from_download(self, url, &extracted).write(&extracted)?;
+
         // Persist it to the target
         debug!("Moving {} to {}", extracted.display(), path.user_display());
         fs_err::tokio::rename(extracted, &path)
@@ -250,21 +675,353 @@ impl PythonDownload {
                 err,
             })?;
 
-        Ok(DownloadResult::Fetched(path))
+        Ok(())
     }
 
-    pub fn python_version(&self) -> PythonVersion {
-        PythonVersion::from_str(&format!("{}.{}.{}", self.major, self.minor, self.patch))
-            .expect("Python downloads should always have valid versions")
+    /// Download the archive to the `.part` file, resuming a previous partial download if any.
+    ///
+    /// If the download is interrupted, it's retried from where it left off.
+    async fn download_archive(
+        &self,
+        client: &uv_client::BaseClient,
+        url: &Url,
+        part: &Path,
+        reporter: Option<&dyn Reporter>,
+    ) -> Result<(), Error> {
+        // The progress is reported across attempts, such that resumed downloads continue where
+        // they left off.
+        let mut progress = None;
+        let mut attempt = 0;
+        loop {
+            match self
+                .download_remaining(client, url, part, reporter, &mut progress)
+                .await
+            {
+                Err(Error::DownloadInterrupted(err)) if attempt < DOWNLOAD_RETRIES => {
+                    attempt += 1;
+                    debug!(
+                        "Download of {url} was interrupted, resuming (attempt {attempt} of {DOWNLOAD_RETRIES}): {err}"
+                    );
+                }
+                result => {
+                    if let (Some(reporter), Some(id), Ok(())) = (reporter, progress, &result) {
+                        reporter.on_download_complete(self, id);
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Download the part of the archive that's missing from the `.part` file.
+    async fn download_remaining(
+        &self,
+        client: &uv_client::BaseClient,
+        url: &Url,
+        part: &Path,
+        reporter: Option<&dyn Reporter>,
+        progress: &mut Option<usize>,
+    ) -> Result<(), Error> {
+        let offset = match fs_err::tokio::metadata(part).await {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => 0,
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut request = client.get(url.clone());
+        if offset > 0 {
+            debug!("Resuming download of {url} at byte {offset}");
+            request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+        }
+        let response = request.send().await?;
+
+        // The partial download is already complete; its digest is verified later.
+        if offset > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(());
+        }
+
+        // Ensure the request was successful.
+        response.error_for_status_ref()?;
+
+        // Servers that don't support range requests send the entire archive.
+        let resume = offset > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        if offset > 0 && !resume {
+            debug!("Server does not support resuming downloads, restarting download of {url}");
+        }
+        let progress = reporter.map(|reporter| {
+            let id = *progress.get_or_insert_with(|| {
+                let size =
+                    response
+                        .content_length()
+                        .map(|length| if resume { length + offset } else { length });
+                let id = reporter.on_download_start(self, size);
+                // Account for the bytes downloaded by a previous process.
+                if resume {
+                    reporter.on_download_progress(id, offset);
+                }
+                id
+            });
+            (reporter, id)
+        });
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resume)
+            .truncate(!resume)
+            .open(part)
+            .await?;
+
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream
+            .try_next()
+            .await
+            .map_err(Error::DownloadInterrupted)?
+        {
+            file.write_all(&chunk).await?;
+            if let Some((reporter, id)) = progress {
+                reporter.on_download_progress(id, chunk.len() as u64);
+            }
+        }
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    /// Verify the SHA256 digest of the downloaded archive, if it's known.
+    fn verify_hash(&self, hashers: Vec<Hasher>) -> Result<(), Error> {
+        let Some(expected) = self.sha256 else {
+            return Ok(());
+        };
+        let actual = hashers
+            .into_iter()
+            .map(HashDigest::from)
+            .next()
+            .map(|digest| digest.digest.to_string())
+            .unwrap_or_default();
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(Error::HashMismatch {
+                installation: self.key.to_string(),
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Options for downloading a managed toolchain.
+#[derive(Clone, Default)]
+pub struct FetchOptions {
+    /// The base URL of a mirror of the python-build-standalone releases.
+    mirror: Option<String>,
+    /// The policy for verifying the downloaded archive.
+    signature_policy: SignaturePolicy,
+    /// The reporter for the progress of the download, verification and extraction.
+    reporter: Option<Arc<dyn Reporter>>,
+}
+
+impl FetchOptions {
+    /// Read the options from the environment, i.e., the mirror from `UV_PYTHON_INSTALL_MIRROR`.
+    pub fn from_env() -> Self {
+        Self {
+            mirror: std::env::var("UV_PYTHON_INSTALL_MIRROR")
+                .ok()
+                .filter(|mirror| !mirror.is_empty()),
+            ..Self::default()
+        }
+    }
+
+    /// Download archives from the given mirror of the python-build-standalone releases, e.g.,
+    /// `https://example.com/python-build-standalone/releases/download`.
+    #[must_use]
+    pub fn with_mirror(mut self, mirror: impl Into<String>) -> Self {
+        self.mirror = Some(mirror.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_signature_policy(mut self, signature_policy: SignaturePolicy) -> Self {
+        self.signature_policy = signature_policy;
+        self
+    }
+
+    /// Report the progress of the download, verification and extraction to the given reporter.
+    #[must_use]
+    pub fn with_reporter(mut self, reporter: impl Reporter + 'static) -> Self {
+        self.reporter = Some(Arc::new(reporter));
+        self
+    }
+
+    pub fn mirror(&self) -> Option<&str> {
+        self.mirror.as_deref()
+    }
+
+    pub fn signature_policy(&self) -> &SignaturePolicy {
+        &self.signature_policy
+    }
+}
+
+/// The policy for verifying managed toolchain downloads beyond their SHA256 checksum.
+///
+/// Defaults to [`SignaturePolicy::ChecksumOnly`].
+#[derive(Clone, Default)]
+pub enum SignaturePolicy {
+    /// Only verify the SHA256 checksum of the archive.
+    #[default]
+    ChecksumOnly,
+    /// Require the archive to match its detached signature before it's extracted.
+    ///
+    /// The signature is downloaded from next to the archive, with the suffix of the verifier,
+    /// e.g., `.sig`.
+    Require(Arc<dyn VerifySignature>),
+}
+
+/// A verifier for the detached signature of a downloaded toolchain archive, e.g.,
+/// [`Ed25519Verifier`].
+pub trait VerifySignature: Send + Sync {
+    /// The suffix of the signature file published next to the archive, e.g., `.sig`.
+    fn suffix(&self) -> &str;
+
+    /// Verify the `archive` downloaded for `download` against its `signature`, returning the
+    /// reason on failure.
+    fn verify(
+        &self,
+        download: &PythonDownload,
+        archive: &Path,
+        signature: &[u8],
+    ) -> Result<(), String>;
+}
+
+#[cfg(feature = "downloads")]
+impl SignaturePolicy {
+    /// Require archives to be signed with the Ed25519 key with the given base64-encoded public
+    /// key.
+    pub fn ed25519(public_key: &str) -> Result<Self, Error> {
+        Ok(Self::Require(Arc::new(Ed25519Verifier::from_base64(
+            public_key,
+        )?)))
     }
 }
 
+/// Verifies detached Ed25519 signatures of the archive contents, published as `<archive>.sig`,
+/// e.g., by an internal mirror that signs the releases it serves.
+///
+/// The signature file contains the 64-byte signature, either raw or base64-encoded.
+#[cfg(feature = "downloads")]
+#[derive(Debug, Clone)]
+pub struct Ed25519Verifier {
+    key: ed25519_dalek::VerifyingKey,
+}
+
+#[cfg(feature = "downloads")]
+impl Ed25519Verifier {
+    /// Create a verifier from a base64-encoded, 32-byte public key.
+    pub fn from_base64(public_key: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidPublicKey(public_key.to_string());
+        let bytes = BASE64_STANDARD
+            .decode(public_key.trim())
+            .map_err(|_| invalid())?;
+        let bytes = <[u8; ed25519_dalek::PUBLIC_KEY_LENGTH]>::try_from(bytes.as_slice())
+            .map_err(|_| invalid())?;
+        let key = ed25519_dalek::VerifyingKey::from_bytes(&bytes).map_err(|_| invalid())?;
+        Ok(Self { key })
+    }
+}
+
+#[cfg(feature = "downloads")]
+impl VerifySignature for Ed25519Verifier {
+    fn suffix(&self) -> &str {
+        ".sig"
+    }
+
+    fn verify(
+        &self,
+        _download: &PythonDownload,
+        archive: &Path,
+        signature: &[u8],
+    ) -> Result<(), String> {
+        let signature = if signature.len() == ed25519_dalek::SIGNATURE_LENGTH {
+            Cow::Borrowed(signature)
+        } else {
+            let signature = std::str::from_utf8(signature)
+                .map_err(|_| "signature is neither raw nor base64-encoded".to_string())?;
+            Cow::Owned(
+                BASE64_STANDARD
+                    .decode(signature.trim())
+                    .map_err(|err| format!("signature is not valid base64: {err}"))?,
+            )
+        };
+        let signature = ed25519_dalek::Signature::from_slice(&signature)
+            .map_err(|_| "signature is not a valid Ed25519 signature".to_string())?;
+        let contents = fs_err::read(archive).map_err(|err| err.to_string())?;
+        self.key
+            .verify_strict(&contents, &signature)
+            .map_err(|_| "archive does not match its signature".to_string())
+    }
+}
+
+impl fmt::Debug for FetchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FetchOptions")
+            .field("mirror", &self.mirror)
+            .field("signature_policy", &self.signature_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An asynchronous reader that reports the progress of an extraction as bytes are read.
+#[cfg(feature = "downloads")]
+struct ProgressReader<'a, R> {
+    reader: R,
+    progress: Option<(&'a dyn Reporter, usize)>,
+}
+
+#[cfg(feature = "downloads")]
+impl<'a, R> ProgressReader<'a, R> {
+    /// Create a new [`ProgressReader`] that wraps another reader.
+    fn new(reader: R, progress: Option<(&'a dyn Reporter, usize)>) -> Self {
+        Self { reader, progress }
+    }
+}
+
+#[cfg(feature = "downloads")]
+impl<R> AsyncRead for ProgressReader<'_, R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        Pin::new(&mut self.as_mut().reader)
+            .poll_read(cx, buf)
+            .map_ok(|()| {
+                if let Some((reporter, id)) = self.progress {
+                    reporter.on_extract_progress(id, (buf.filled().len() - filled) as u64);
+                }
+            })
+    }
+}
+
+impl fmt::Debug for SignaturePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChecksumOnly => f.write_str("ChecksumOnly"),
+            Self::Require(_) => f.write_str("Require(..)"),
+        }
+    }
+}
+
+#[cfg(feature = "downloads")]
 impl From<reqwest::Error> for Error {
     fn from(error: reqwest::Error) -> Self {
         Self::NetworkError(BetterReqwestError::from(error))
     }
 }
 
+#[cfg(feature = "downloads")]
 impl From<reqwest_middleware::Error> for Error {
     fn from(error: reqwest_middleware::Error) -> Self {
         match error {
@@ -281,3 +1038,501 @@ impl Display for PythonDownload {
         f.write_str(self.key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::platform::{Arch, Libc, Os};
+
+    use super::{PythonDownload, PythonDownloadRequest};
+
+    /// The contents of the archive served by the mock server.
+    #[cfg(feature = "downloads")]
+    const ARCHIVE: &[u8] = b"toolchain archive";
+
+    /// A download without a known digest, e.g., as for toolchains without published checksums.
+    #[cfg(feature = "downloads")]
+    fn download_without_digest() -> PythonDownload {
+        let download = PythonDownload::from_key("cpython-3.12.3-linux-x86_64-gnu").unwrap();
+        PythonDownload {
+            os: download.os.clone(),
+            sha256: None,
+            ..*download
+        }
+    }
+
+    /// Write a minimal toolchain archive, with a single top-level directory like the
+    /// python-build-standalone archives.
+    #[cfg(feature = "downloads")]
+    fn write_archive(path: &std::path::Path) {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(fs_err::File::create(path).unwrap());
+        writer
+            .start_file("python/install/README", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"toolchain").unwrap();
+        writer.finish().unwrap();
+    }
+
+    /// A [`Reporter`](crate::managed::Reporter) that records the callbacks it receives.
+    #[cfg(feature = "downloads")]
+    #[derive(Default)]
+    struct RecordingReporter {
+        /// The callbacks, except for progress updates, which are summed instead.
+        events: std::sync::Mutex<Vec<String>>,
+        downloaded: std::sync::atomic::AtomicU64,
+        extracted: std::sync::atomic::AtomicU64,
+    }
+
+    #[cfg(feature = "downloads")]
+    impl RecordingReporter {
+        fn record(&self, event: String) -> usize {
+            let mut events = self.events.lock().unwrap();
+            events.push(event);
+            events.len()
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    #[cfg(feature = "downloads")]
+    impl crate::managed::Reporter for RecordingReporter {
+        fn on_download_start(&self, _download: &PythonDownload, size: Option<u64>) -> usize {
+            self.record(format!("download start {size:?}"))
+        }
+
+        fn on_download_progress(&self, _id: usize, inc: u64) {
+            self.downloaded
+                .fetch_add(inc, std::sync::atomic::Ordering::SeqCst);
+        }
+
+        fn on_download_complete(&self, _download: &PythonDownload, _id: usize) {
+            self.record("download complete".to_string());
+        }
+
+        fn on_verify_start(&self, _download: &PythonDownload) -> usize {
+            self.record("verify start".to_string())
+        }
+
+        fn on_verify_complete(&self, _download: &PythonDownload, _id: usize) {
+            self.record("verify complete".to_string());
+        }
+
+        fn on_extract_start(&self, _download: &PythonDownload, size: u64) -> usize {
+            self.record(format!("extract start {size}"))
+        }
+
+        fn on_extract_progress(&self, _id: usize, inc: u64) {
+            self.extracted
+                .fetch_add(inc, std::sync::atomic::Ordering::SeqCst);
+        }
+
+        fn on_extract_complete(&self, _download: &PythonDownload, _id: usize) {
+            self.record("extract complete".to_string());
+        }
+    }
+
+    #[test]
+    fn archive_filename() {
+        let download = PythonDownload::from_key("cpython-3.12.3-linux-x86_64-gnu").unwrap();
+        let filename = download.archive_filename();
+        assert!(filename.starts_with("cpython-3.12.3+20240415-x86_64"));
+        assert_eq!(
+            PythonDownload::from_archive_filename(&filename),
+            Some(download)
+        );
+        assert_eq!(PythonDownload::from_archive_filename("python.tar.gz"), None);
+    }
+
+    #[test]
+    fn free_threaded_request() {
+        let request = PythonDownloadRequest::from_str("3.12t")
+            .unwrap()
+            .with_os(Os::Linux)
+            .with_arch(Arch::X86_64)
+            .with_libc(Libc::Gnu);
+        assert_eq!(request.to_string(), "3.12t-linux-x86_64-gnu");
+        // There are no free-threaded builds of Python 3.12, and regular builds must not be used.
+        assert_eq!(PythonDownload::from_request(&request), None);
+
+        let request = request.with_free_threaded(false);
+        let download = PythonDownload::from_request(&request).unwrap();
+        assert!(!download.free_threaded());
+    }
+
+    #[test]
+    fn download_url_mirror() {
+        let download = PythonDownload::from_key("cpython-3.12.3-linux-x86_64-gnu").unwrap();
+        assert_eq!(
+            download.download_url(None).unwrap().as_str(),
+            download.url()
+        );
+
+        let mirrored = download
+            .download_url(Some("https://mirror.example.com/pbs/"))
+            .unwrap();
+        assert!(mirrored
+            .as_str()
+            .starts_with("https://mirror.example.com/pbs/20240415/cpython-3.12.3"));
+        assert_eq!(
+            mirrored.path_segments().unwrap().last(),
+            download
+                .download_url(None)
+                .unwrap()
+                .path_segments()
+                .unwrap()
+                .last()
+        );
+    }
+
+    #[cfg(feature = "downloads")]
+    #[test]
+    fn ed25519_signature() {
+        use base64::{prelude::BASE64_STANDARD, Engine};
+        use ed25519_dalek::{Signer, SigningKey};
+
+        use super::{Ed25519Verifier, VerifySignature};
+
+        let download = PythonDownload::from_key("cpython-3.12.3-linux-x86_64-gnu").unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("archive.tar.gz");
+        fs_err::write(&archive, b"toolchain").unwrap();
+
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let verifier =
+            Ed25519Verifier::from_base64(&BASE64_STANDARD.encode(key.verifying_key().as_bytes()))
+                .unwrap();
+        let signature = key.sign(b"toolchain").to_bytes();
+
+        // Both raw and base64-encoded signatures are accepted.
+        verifier.verify(download, &archive, &signature).unwrap();
+        let encoded = format!("{}\n", BASE64_STANDARD.encode(signature));
+        verifier
+            .verify(download, &archive, encoded.as_bytes())
+            .unwrap();
+
+        // Signatures by another key are rejected.
+        let other = SigningKey::from_bytes(&[8; 32])
+            .sign(b"toolchain")
+            .to_bytes();
+        assert_eq!(
+            verifier.verify(download, &archive, &other).unwrap_err(),
+            "archive does not match its signature"
+        );
+
+        // Malformed signatures are rejected.
+        assert!(verifier
+            .verify(download, &archive, b"not a signature")
+            .is_err());
+
+        // The signature no longer matches once the archive is modified.
+        fs_err::write(&archive, b"tampered").unwrap();
+        assert_eq!(
+            verifier.verify(download, &archive, &signature).unwrap_err(),
+            "archive does not match its signature"
+        );
+    }
+
+    #[cfg(feature = "downloads")]
+    #[test]
+    fn ed25519_public_key() {
+        use super::{Ed25519Verifier, Error};
+
+        assert!(matches!(
+            Ed25519Verifier::from_base64("not base64!"),
+            Err(Error::InvalidPublicKey(_))
+        ));
+        // The key must be 32 bytes.
+        assert!(matches!(
+            Ed25519Verifier::from_base64("AAAA"),
+            Err(Error::InvalidPublicKey(_))
+        ));
+    }
+
+    #[cfg(feature = "downloads")]
+    #[tokio::test]
+    async fn resume_download() {
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("range", "bytes=4-"))
+            .respond_with(ResponseTemplate::new(206).set_body_bytes(&ARCHIVE[4..]))
+            .mount(&server)
+            .await;
+
+        let download = download_without_digest();
+        let client = uv_client::BaseClientBuilder::new().build();
+        let url = url::Url::parse(&format!("{}/archive.tar.gz", server.uri())).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let part = temp_dir.path().join("archive.tar.gz.part");
+
+        // Only the missing bytes are requested, while the progress covers the entire archive.
+        fs_err::write(&part, &ARCHIVE[..4]).unwrap();
+        let reporter = RecordingReporter::default();
+        download
+            .download_archive(&client, &url, &part, Some(&reporter))
+            .await
+            .unwrap();
+        assert_eq!(fs_err::read(&part).unwrap(), ARCHIVE);
+        assert_eq!(
+            reporter.events(),
+            [
+                format!("download start Some({})", ARCHIVE.len()),
+                "download complete".to_string()
+            ]
+        );
+        assert_eq!(
+            reporter
+                .downloaded
+                .load(std::sync::atomic::Ordering::SeqCst),
+            ARCHIVE.len() as u64
+        );
+    }
+
+    #[cfg(feature = "downloads")]
+    #[tokio::test]
+    async fn restart_download() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // The server doesn't support range requests, and sends the entire archive.
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(ARCHIVE))
+            .mount(&server)
+            .await;
+
+        let download = download_without_digest();
+        let client = uv_client::BaseClientBuilder::new().build();
+        let url = url::Url::parse(&format!("{}/archive.tar.gz", server.uri())).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let part = temp_dir.path().join("archive.tar.gz.part");
+
+        // The partial download is replaced rather than appended to.
+        fs_err::write(&part, b"stale").unwrap();
+        download
+            .download_archive(&client, &url, &part, None)
+            .await
+            .unwrap();
+        assert_eq!(fs_err::read(&part).unwrap(), ARCHIVE);
+    }
+
+    #[cfg(feature = "downloads")]
+    #[tokio::test]
+    async fn corrupt_partial_download() {
+        use wiremock::matchers::{header_exists, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use super::FetchOptions;
+
+        // The partial download is already complete, so the server rejects the range.
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header_exists("range"))
+            .respond_with(ResponseTemplate::new(416))
+            .mount(&server)
+            .await;
+
+        let download = download_without_digest();
+        let client = uv_client::BaseClientBuilder::new().build();
+        let options = FetchOptions::default().with_mirror(server.uri());
+        let url = download.download_url(options.mirror()).unwrap();
+        let filename = url.path_segments().unwrap().last().unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let part = temp_dir.path().join(format!("{filename}.part"));
+
+        // Without a known digest, the corrupt archive is only detected when it's extracted, after
+        // which it must be removed rather than resumed from again.
+        fs_err::write(&part, ARCHIVE).unwrap();
+        assert!(download
+            .fetch_with_options(&client, temp_dir.path(), &options)
+            .await
+            .is_err());
+        assert!(!part.exists());
+        assert!(!temp_dir.path().join(download.key()).exists());
+    }
+
+    #[cfg(feature = "downloads")]
+    #[tokio::test]
+    async fn mirror_metadata() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use super::FetchOptions;
+        use crate::managed::Toolchain;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("archive.zip");
+        write_archive(&archive);
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_bytes(fs_err::read(&archive).unwrap()),
+            )
+            .mount(&server)
+            .await;
+
+        let download = PythonDownload {
+            url: "https://github.com/indygreg/python-build-standalone/releases/download/20240415/cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-install_only.zip",
+            ..download_without_digest()
+        };
+        let client = uv_client::BaseClientBuilder::new().build();
+        let options = FetchOptions::default().with_mirror(server.uri());
+        let toolchains = temp_dir.path().join("toolchains");
+        download
+            .fetch_with_options(&client, &toolchains, &options)
+            .await
+            .unwrap();
+
+        // The metadata records the mirror the archive was downloaded from.
+        let metadata = Toolchain::new(toolchains.join(download.key()))
+            .unwrap()
+            .metadata()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            metadata.url,
+            format!(
+                "{}/20240415/cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-install_only.zip",
+                server.uri()
+            )
+        );
+    }
+
+    #[cfg(feature = "downloads")]
+    #[tokio::test]
+    async fn checksum_mismatch() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use super::{Error, FetchOptions};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("archive.zip");
+        write_archive(&archive);
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_bytes(fs_err::read(&archive).unwrap()),
+            )
+            .mount(&server)
+            .await;
+
+        let download = PythonDownload {
+            url: "https://github.com/indygreg/python-build-standalone/releases/download/20240415/cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-install_only.zip",
+            sha256: Some("0000000000000000000000000000000000000000000000000000000000000000"),
+            ..download_without_digest()
+        };
+        let client = uv_client::BaseClientBuilder::new().build();
+        let options = FetchOptions::default().with_mirror(server.uri());
+        let toolchains = temp_dir.path().join("toolchains");
+        let result = download
+            .fetch_with_options(&client, &toolchains, &options)
+            .await;
+        assert!(matches!(result, Err(Error::HashMismatch { .. })));
+
+        // Neither the download nor the extracted toolchain are left behind.
+        let entries = fs_err::read_dir(&toolchains)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().map_or(true, |ext| ext != "lock"))
+            .collect::<Vec<_>>();
+        assert!(entries.is_empty(), "{entries:?}");
+    }
+
+    #[cfg(feature = "downloads")]
+    #[tokio::test]
+    async fn concurrent_install() {
+        use std::time::Duration;
+
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use super::{DownloadResult, FetchOptions};
+        use crate::managed::{verify, InstalledToolchains};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("archive.zip");
+        write_archive(&archive);
+        let server = MockServer::start().await;
+        // Delay the response, such that the second install starts while the first is downloading.
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_bytes(fs_err::read(&archive).unwrap())
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let download = PythonDownload {
+            url: "https://github.com/indygreg/python-build-standalone/releases/download/20240415/cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-install_only.zip",
+            ..download_without_digest()
+        };
+        let client = uv_client::BaseClientBuilder::new().build();
+        let options = FetchOptions::default().with_mirror(server.uri());
+        let toolchains = temp_dir.path().join("toolchains");
+        let (first, second) = tokio::join!(
+            download.fetch_with_options(&client, &toolchains, &options),
+            download.fetch_with_options(&client, &toolchains, &options),
+        );
+
+        // The archive is downloaded once, and the other install reuses the toolchain.
+        let path = toolchains.join(download.key());
+        let mut results = [first.unwrap(), second.unwrap()];
+        results.sort_by_key(|result| matches!(result, DownloadResult::AlreadyAvailable(_)));
+        assert!(matches!(&results[0], DownloadResult::Fetched(fetched) if *fetched == path));
+        assert!(
+            matches!(&results[1], DownloadResult::AlreadyAvailable(available) if *available == path)
+        );
+        let toolchains = InstalledToolchains::from_path(&toolchains).unwrap();
+        assert!(verify(&toolchains, download.key()).unwrap().is_intact());
+        server.verify().await;
+    }
+
+    #[cfg(feature = "downloads")]
+    #[tokio::test]
+    async fn install_archive_progress() {
+        use super::SignaturePolicy;
+
+        let download = download_without_digest();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("archive.zip");
+        write_archive(&archive);
+        let size = fs_err::metadata(&archive).unwrap().len();
+        let toolchains = temp_dir.path().join("toolchains");
+
+        let reporter = RecordingReporter::default();
+        download
+            .install_archive(
+                &archive,
+                &toolchains,
+                &SignaturePolicy::ChecksumOnly,
+                Some(&reporter),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            reporter.events(),
+            [
+                format!("extract start {size}"),
+                "extract complete".to_string(),
+                "verify start".to_string(),
+                "verify complete".to_string(),
+            ]
+        );
+        assert!(reporter.extracted.load(std::sync::atomic::Ordering::SeqCst) > 0);
+        assert!(toolchains
+            .join(download.key())
+            .join("install")
+            .join("README")
+            .is_file());
+    }
+}
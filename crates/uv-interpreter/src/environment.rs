@@ -1,13 +1,24 @@
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::env;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::Context;
+use fs_err as fs;
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use pep440_rs::Version;
+use pep508_rs::PackageName;
 use uv_cache::Cache;
 use uv_configuration::PreviewMode;
 use uv_fs::{LockedFile, Simplified};
 
 use crate::discovery::{InterpreterRequest, SourceSelector, SystemPython};
+use crate::health::{self, EnvironmentProblem};
 use crate::virtualenv::{virtualenv_python_executable, PyVenvConfiguration};
 use crate::{
     find_default_interpreter, find_interpreter, Error, Interpreter, InterpreterSource, Prefix,
@@ -69,13 +80,14 @@ impl PythonEnvironment {
         let request = InterpreterRequest::Any;
         let found = find_interpreter(&request, SystemPython::Disallowed, &sources, cache)??;
 
-        debug_assert!(
-            found.interpreter().is_virtualenv()
-                || matches!(found.source(), InterpreterSource::CondaPrefix),
-            "Not a virtualenv (source: {}, prefix: {})",
-            found.source(),
-            found.interpreter().sys_base_prefix().display()
-        );
+        if !(found.interpreter().is_virtualenv()
+            || matches!(found.source(), InterpreterSource::CondaPrefix))
+        {
+            return Err(Error::NotAVirtualEnv {
+                interpreter_source: *found.source(),
+                prefix: found.interpreter().sys_prefix().to_path_buf(),
+            });
+        }
 
         Ok(Self(Arc::new(PythonEnvironmentShared {
             root: found.interpreter().sys_prefix().to_path_buf(),
@@ -199,7 +211,11 @@ impl PythonEnvironment {
     /// Some distributions also create symbolic links from `purelib` to `platlib`; in such cases, we
     /// still deduplicate the entries, returning a single path.
     pub fn site_packages(&self) -> impl Iterator<Item = Cow<Path>> {
-        let target = self.0.interpreter.target().map(Target::site_packages);
+        let target = self
+            .0
+            .interpreter
+            .target()
+            .map(|target| target.site_packages(self.0.interpreter.virtualenv()));
 
         let prefix = self
             .0
@@ -216,29 +232,118 @@ impl PythonEnvironment {
         target
             .into_iter()
             .flatten()
-            .map(Cow::Borrowed)
+            .map(Cow::Owned)
             .chain(prefix.into_iter().flatten().map(Cow::Owned))
             .chain(interpreter.into_iter().flatten().map(Cow::Borrowed))
     }
 
+    /// Returns the version of the package installed in the environment, if any, e.g., to determine
+    /// whether `pip` or `setuptools` is available without running `python -m pip --version`.
+    ///
+    /// The package is found by its metadata in `site-packages`, so the result reflects the
+    /// current state of the environment rather than the cached interpreter info.
+    pub fn installed_version(&self, name: &PackageName) -> anyhow::Result<Option<Version>> {
+        Ok(self
+            .installed_distributions()?
+            .into_iter()
+            .find(|dist| dist.name() == name)
+            .map(|dist| dist.version().clone()))
+    }
+
+    /// List the distributions installed in the environment, e.g., for introspection without
+    /// running `pip list`.
+    ///
+    /// Distributions are found by their `.dist-info` or `.egg-info` metadata, or by their
+    /// `.egg-link` files for legacy editable installs, in each `site-packages` directory; see
+    /// [`InstalledDist::try_from_path`]. The installer of a distribution is available via
+    /// [`InstalledDist::installer`].
+    pub fn installed_distributions(&self) -> anyhow::Result<Vec<InstalledDist>> {
+        let mut distributions = Vec::new();
+        for site_packages in self.site_packages() {
+            distributions.extend(installed_distributions(&site_packages)?);
+        }
+        Ok(distributions)
+    }
+
+    /// Check the environment for common breakage, e.g., `.pth` files that refer to missing
+    /// paths, distributions that overwrote each other's modules, or scripts that run with a
+    /// different interpreter.
+    ///
+    /// Returns an empty list if no problems were found.
+    pub fn check(&self) -> anyhow::Result<Vec<EnvironmentProblem>> {
+        let mut problems = Vec::new();
+        for site_packages in self.site_packages() {
+            problems.extend(health::orphaned_pth_files(&site_packages)?);
+        }
+        problems.extend(health::conflicting_modules(
+            &self.installed_distributions()?,
+        )?);
+        let scripts = self.0.interpreter.layout().scheme.scripts;
+        problems.extend(health::foreign_shebangs(
+            &scripts,
+            self.python_executable(),
+        )?);
+        Ok(problems)
+    }
+
     /// Returns the path to the `bin` directory inside this environment.
     pub fn scripts(&self) -> &Path {
         self.0.interpreter.scripts()
     }
 
+    /// Returns `true` if packages can be installed into the environment, i.e., if its
+    /// `site-packages` and scripts directories are writable.
+    ///
+    /// Directories that don't exist yet are writable if they can be created, i.e., if their
+    /// nearest existing ancestor is writable.
+    pub fn is_writable(&self) -> bool {
+        let scripts = self.0.interpreter.layout().scheme.scripts;
+        self.site_packages()
+            .chain(std::iter::once(Cow::Owned(scripts)))
+            .all(|dir| is_writable(&dir))
+    }
+
     /// Grab a file lock for the environment to prevent concurrent writes across processes.
     pub fn lock(&self) -> Result<LockedFile, std::io::Error> {
+        let (path, resource) = self.lock_file();
+        LockedFile::acquire(path, resource)
+    }
+
+    /// Grab a file lock for the environment, waiting for at most the given timeout for another
+    /// process to release it.
+    ///
+    /// See [`LockedFile::acquire_with_timeout`].
+    pub fn lock_with_timeout(&self, timeout: Duration) -> Result<LockedFile, std::io::Error> {
+        let (path, resource) = self.lock_file();
+        LockedFile::acquire_with_timeout(path, resource, timeout)
+    }
+
+    /// Return the path to the lock file for the environment, along with a description of the
+    /// locked resource.
+    fn lock_file(&self) -> (PathBuf, String) {
         if let Some(target) = self.0.interpreter.target() {
             // If we're installing into a `--target`, use a target-specific lock file.
-            LockedFile::acquire(target.root().join(".lock"), target.root().user_display())
+            (
+                target.root().join(".lock"),
+                target.root().user_display().to_string(),
+            )
+        } else if let Some(prefix) = self.0.interpreter.prefix() {
+            // Likewise, if we're installing into a `--prefix`, use a prefix-specific lock file.
+            (
+                prefix.root().join(".lock"),
+                prefix.root().user_display().to_string(),
+            )
         } else if self.0.interpreter.is_virtualenv() {
             // If the environment a virtualenv, use a virtualenv-specific lock file.
-            LockedFile::acquire(self.0.root.join(".lock"), self.0.root.user_display())
+            (
+                self.0.root.join(".lock"),
+                self.0.root.user_display().to_string(),
+            )
         } else {
             // Otherwise, use a global lock file.
-            LockedFile::acquire(
+            (
                 env::temp_dir().join(format!("uv-{}.lock", cache_key::digest(&self.0.root))),
-                self.0.root.user_display(),
+                self.0.root.user_display().to_string(),
             )
         }
     }
@@ -250,3 +355,160 @@ impl PythonEnvironment {
         Arc::unwrap_or_clone(self.0).interpreter
     }
 }
+
+/// Returns `true` if files can be created in the directory, or in its nearest existing ancestor
+/// if it doesn't exist.
+///
+/// Writability is checked by creating a temporary file, rather than by inspecting permissions, to
+/// account for access control lists on Windows, read-only mounts, and the like.
+fn is_writable(dir: &Path) -> bool {
+    let Some(existing) = dir.ancestors().find(|ancestor| ancestor.is_dir()) else {
+        return false;
+    };
+    match tempfile::tempfile_in(existing) {
+        Ok(_) => true,
+        Err(err) => {
+            debug!(
+                "Directory `{}` is not writable: {err}",
+                existing.user_display()
+            );
+            false
+        }
+    }
+}
+
+/// List the distributions installed in the given `site-packages` directory, sorted by path.
+fn installed_distributions(site_packages: &Path) -> anyhow::Result<Vec<InstalledDist>> {
+    // Collect sorted directory paths; `read_dir` is not stable across platforms
+    let dist_likes: BTreeSet<_> = match fs::read_dir(site_packages) {
+        Ok(entries) => entries
+            .filter_map(|read_dir| match read_dir {
+                Ok(entry) => match entry.file_type() {
+                    Ok(file_type) => (file_type.is_dir()
+                        || entry
+                            .path()
+                            .extension()
+                            .map_or(false, |ext| ext == "egg-link" || ext == "egg-info"))
+                    .then_some(Ok(entry.path())),
+                    Err(err) => Some(Err(err)),
+                },
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<Result<_, io::Error>>()?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).context("Failed to read site-packages directory"),
+    };
+
+    let mut distributions = Vec::new();
+    for path in dist_likes {
+        if let Some(dist) = InstalledDist::try_from_path(&path)
+            .with_context(|| format!("Failed to read metadata: from {}", path.display()))?
+        {
+            distributions.push(dist);
+        }
+    }
+    Ok(distributions)
+}
+
+#[cfg(test)]
+mod tests {
+    use distribution_types::{InstalledDist, Name};
+    use fs_err as fs;
+
+    use super::{installed_distributions, is_writable};
+
+    #[test]
+    fn find_installed_distributions() {
+        let site_packages = tempfile::tempdir().unwrap();
+        for dir in [
+            "pip-24.0.dist-info",
+            "setuptools-65.5.0-py3.11.egg-info",
+            "typing_extensions-4.11.0.dist-info",
+            "pip",
+        ] {
+            fs::create_dir(site_packages.path().join(dir)).unwrap();
+        }
+        fs::write(
+            site_packages
+                .path()
+                .join("pip-24.0.dist-info")
+                .join("INSTALLER"),
+            "uv\n",
+        )
+        .unwrap();
+        // A legacy `.egg-info` file, as written by `distutils`.
+        fs::write(site_packages.path().join("vtk-9.2.6.egg-info"), "").unwrap();
+        // A legacy editable install.
+        let source = tempfile::tempdir().unwrap();
+        fs::create_dir(source.path().join("zstandard.egg-info")).unwrap();
+        fs::write(
+            source.path().join("zstandard.egg-info").join("PKG-INFO"),
+            "Metadata-Version: 1.0\nName: zstandard\nVersion: 0.22.0\n",
+        )
+        .unwrap();
+        fs::write(
+            site_packages.path().join("zstandard.egg-link"),
+            format!("{}\n.\n", source.path().display()),
+        )
+        .unwrap();
+
+        let distributions = installed_distributions(site_packages.path()).unwrap();
+        assert_eq!(
+            distributions
+                .iter()
+                .map(|dist| (
+                    dist.name().to_string(),
+                    dist.version().to_string(),
+                    // An `.egg-info` file has no `INSTALLER` next to it.
+                    match dist {
+                        InstalledDist::EggInfoFile(_) => None,
+                        dist => dist.installer().unwrap(),
+                    },
+                    dist.is_editable()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "pip".to_string(),
+                    "24.0".to_string(),
+                    Some("uv\n".to_string()),
+                    false
+                ),
+                ("setuptools".to_string(), "65.5.0".to_string(), None, false),
+                (
+                    "typing-extensions".to_string(),
+                    "4.11.0".to_string(),
+                    None,
+                    false
+                ),
+                ("vtk".to_string(), "9.2.6".to_string(), None, false),
+                ("zstandard".to_string(), "0.22.0".to_string(), None, true),
+            ]
+        );
+        assert!(matches!(distributions[4], InstalledDist::LegacyEditable(_)));
+        assert!(
+            installed_distributions(&site_packages.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn writable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(is_writable(temp_dir.path()));
+        assert!(is_writable(&temp_dir.path().join("bin")));
+
+        let read_only = temp_dir.path().join("read-only");
+        fs::create_dir(&read_only).unwrap();
+        fs::set_permissions(&read_only, std::fs::Permissions::from_mode(0o555)).unwrap();
+        // Permissions don't apply to root.
+        if tempfile::tempfile_in(&read_only).is_err() {
+            assert!(!is_writable(&read_only));
+            assert!(!is_writable(&read_only.join("lib").join("site-packages")));
+        }
+    }
+}
@@ -0,0 +1,425 @@
+//! Detect common breakage in a Python environment, e.g., as left behind by moving an environment
+//! or by mixing installers.
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use same_file::is_same_file;
+
+use distribution_types::{InstalledDist, Name};
+use uv_fs::Simplified;
+
+/// A problem with a Python environment, as detected by
+/// [`PythonEnvironment::check`](crate::PythonEnvironment::check).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvironmentProblem {
+    /// A `.pth` file adds a path that doesn't exist to `sys.path`, e.g., for an editable install
+    /// whose source tree was moved or deleted.
+    OrphanedPth { pth: PathBuf, path: PathBuf },
+    /// Multiple distributions install the same files for a top-level module, such that one
+    /// overwrote the other.
+    ConflictingModule {
+        module: String,
+        distributions: Vec<String>,
+    },
+    /// A script's shebang points to a different Python interpreter than the environment's, e.g.,
+    /// because the environment was moved or copied.
+    ForeignShebang {
+        script: PathBuf,
+        interpreter: PathBuf,
+    },
+}
+
+impl fmt::Display for EnvironmentProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OrphanedPth { pth, path } => write!(
+                f,
+                "`{}` refers to `{}`, which does not exist",
+                pth.user_display(),
+                path.user_display()
+            ),
+            Self::ConflictingModule {
+                module,
+                distributions,
+            } => write!(
+                f,
+                "`{module}` is provided by multiple distributions: {}",
+                distributions
+                    .iter()
+                    .map(|distribution| format!("`{distribution}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::ForeignShebang {
+                script,
+                interpreter,
+            } => write!(
+                f,
+                "`{}` runs with `{}`, which is not the environment's interpreter",
+                script.user_display(),
+                interpreter.user_display()
+            ),
+        }
+    }
+}
+
+/// Find `.pth` files in the `site-packages` directory that add missing paths to `sys.path`.
+///
+/// Lines that are executed rather than added to `sys.path`, i.e., those starting with `import`,
+/// are ignored.
+pub(crate) fn orphaned_pth_files(
+    site_packages: &Path,
+) -> Result<Vec<EnvironmentProblem>, io::Error> {
+    let entries = match fs::read_dir(site_packages) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut pth_files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "pth") && path.is_file() {
+            pth_files.push(path);
+        }
+    }
+    pth_files.sort();
+
+    let mut problems = Vec::new();
+    for pth in pth_files {
+        // `site.addpackage` skips undecodable files, so we do too.
+        let Ok(contents) = fs::read_to_string(&pth) else {
+            continue;
+        };
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("import ")
+                || line.starts_with("import\t")
+            {
+                continue;
+            }
+            let path = site_packages.join(line);
+            if !path.exists() {
+                problems.push(EnvironmentProblem::OrphanedPth {
+                    pth: pth.clone(),
+                    path,
+                });
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// Find top-level modules for which multiple distributions record the same files in their
+/// `RECORD`.
+///
+/// Namespace packages that are shared between distributions, e.g., `google`, are not reported
+/// unless the distributions install the same files into them.
+pub(crate) fn conflicting_modules(
+    distributions: &[InstalledDist],
+) -> Result<Vec<EnvironmentProblem>, io::Error> {
+    let mut owners: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+    for distribution in distributions {
+        // Only `.dist-info` directories have a `RECORD`.
+        if !matches!(
+            distribution,
+            InstalledDist::Registry(_) | InstalledDist::Url(_)
+        ) {
+            continue;
+        }
+        let contents = match fs::read_to_string(distribution.path().join("RECORD")) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for path in contents.lines().filter_map(record_path) {
+            owners
+                .entry(path.replace('\\', "/"))
+                .or_default()
+                .insert(distribution.name().as_ref());
+        }
+    }
+
+    let mut conflicts: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+    for (path, distributions) in owners {
+        if distributions.len() < 2 {
+            continue;
+        }
+        let Some(module) = top_level_module(&path) else {
+            continue;
+        };
+        conflicts
+            .entry(module.to_string())
+            .or_default()
+            .extend(distributions);
+    }
+
+    Ok(conflicts
+        .into_iter()
+        .map(
+            |(module, distributions)| EnvironmentProblem::ConflictingModule {
+                module,
+                distributions: distributions.into_iter().map(ToString::to_string).collect(),
+            },
+        )
+        .collect())
+}
+
+/// Parse the path from a line of a `RECORD` file, i.e., the first field of a CSV row.
+fn record_path(line: &str) -> Option<&str> {
+    let path = if let Some(quoted) = line.strip_prefix('"') {
+        quoted.split_once('"')?.0
+    } else {
+        line.split_once(',').map_or(line, |(path, _)| path)
+    };
+    Some(path).filter(|path| !path.is_empty())
+}
+
+/// Determine the top-level module that a path in `site-packages` belongs to, e.g., `foo` for
+/// `foo/__init__.py`, `foo.py`, or `foo.cpython-312-x86_64-linux-gnu.so`.
+///
+/// Returns `None` for paths that don't belong to a module, e.g., metadata and scripts.
+fn top_level_module(path: &str) -> Option<&str> {
+    let (first, is_file) = match path.split_once('/') {
+        Some((first, _)) => (first, false),
+        None => (path, true),
+    };
+    if first.is_empty()
+        || first == ".."
+        || first == "__pycache__"
+        || first.ends_with(".dist-info")
+        || first.ends_with(".egg-info")
+        || Path::new(first)
+            .extension()
+            .is_some_and(|extension| extension == "data")
+    {
+        return None;
+    }
+    if !is_file {
+        return Some(first);
+    }
+    // A single-file module, e.g., `six.py` or a native extension, but not a `.pth` file.
+    match first.split_once('.')? {
+        (_, "pth") => None,
+        (module, _) => Some(module),
+    }
+}
+
+/// Find scripts in the given directory whose shebang points to a Python interpreter other than
+/// the given one.
+///
+/// Shebangs that don't name a Python interpreter, e.g., `#!/bin/bash` or `#!/usr/bin/env python`,
+/// are ignored.
+pub(crate) fn foreign_shebangs(
+    scripts: &Path,
+    executable: &Path,
+) -> Result<Vec<EnvironmentProblem>, io::Error> {
+    let entries = match fs::read_dir(scripts) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut problems = Vec::new();
+    for script in paths {
+        let Some(interpreter) = shebang_interpreter(&script)? else {
+            continue;
+        };
+        let is_python = interpreter
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("python") || name.starts_with("pypy"));
+        if !is_python {
+            continue;
+        }
+        if interpreter != executable && !is_same_file(&interpreter, executable).unwrap_or(false) {
+            problems.push(EnvironmentProblem::ForeignShebang {
+                script,
+                interpreter,
+            });
+        }
+    }
+    Ok(problems)
+}
+
+/// Read the interpreter from the shebang of a script, if any.
+///
+/// Supports the `/bin/sh` trampoline used for interpreter paths that are too long or contain
+/// spaces, i.e., `#!/bin/sh` followed by `'''exec' '/path/to/python' "$0" "$@"`.
+fn shebang_interpreter(script: &Path) -> Result<Option<PathBuf>, io::Error> {
+    let mut buffer = Vec::with_capacity(1024);
+    fs::File::open(script)?
+        .take(1024)
+        .read_to_end(&mut buffer)?;
+    if !buffer.starts_with(b"#!") {
+        return Ok(None);
+    }
+    let head = String::from_utf8_lossy(&buffer);
+    let mut lines = head.lines();
+    let Some(shebang) = lines.next().and_then(|line| line.strip_prefix("#!")) else {
+        return Ok(None);
+    };
+    let shebang = shebang.trim();
+    if shebang == "/bin/sh" {
+        let interpreter = lines
+            .next()
+            .and_then(|line| line.strip_prefix("'''exec' "))
+            .and_then(shell_word)
+            .map(PathBuf::from);
+        return Ok(interpreter);
+    }
+    let interpreter = shebang
+        .split_whitespace()
+        .next()
+        .map(|interpreter| interpreter.trim_matches('"'))
+        .filter(|interpreter| Path::new(interpreter).is_absolute())
+        .map(PathBuf::from);
+    Ok(interpreter)
+}
+
+/// Parse the first word of a shell command, resolving single and double quotes, e.g.,
+/// `'/home/it'"'"'s me/python'` to `/home/it's me/python`.
+fn shell_word(command: &str) -> Option<String> {
+    let mut word = String::new();
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => loop {
+                match chars.next()? {
+                    quote if quote == c => break,
+                    other => word.push(other),
+                }
+            },
+            c if c.is_whitespace() => break,
+            c => word.push(c),
+        }
+    }
+    Some(word).filter(|word| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use distribution_types::InstalledDist;
+    use fs_err as fs;
+
+    use super::{conflicting_modules, foreign_shebangs, orphaned_pth_files, EnvironmentProblem};
+
+    #[test]
+    fn orphaned_pth() {
+        let site_packages = tempfile::tempdir().unwrap();
+        let source = site_packages.path().join("src");
+        fs::create_dir(&source).unwrap();
+        let pth = site_packages.path().join("_editable.pth");
+        fs::write(
+            &pth,
+            format!(
+                "# A comment\n{}\nimport _virtualenv\n{}\n",
+                source.display(),
+                site_packages.path().join("moved").display()
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            orphaned_pth_files(site_packages.path()).unwrap(),
+            vec![EnvironmentProblem::OrphanedPth {
+                pth,
+                path: site_packages.path().join("moved"),
+            }]
+        );
+    }
+
+    #[test]
+    fn conflicting() {
+        let site_packages = tempfile::tempdir().unwrap();
+        let distribution = |name: &str, record: &str| {
+            let path = site_packages.path().join(format!("{name}-1.0.0.dist-info"));
+            fs::create_dir(&path).unwrap();
+            fs::write(path.join("RECORD"), record).unwrap();
+            InstalledDist::try_from_path(&path).unwrap().unwrap()
+        };
+        let distributions = [
+            distribution(
+                "foo",
+                "foo/__init__.py,sha256=abc,10\n\
+                 google/cloud/foo.py,sha256=abc,10\n\
+                 foo-1.0.0.dist-info/RECORD,,\n\
+                 ../../bin/foo,sha256=abc,10\n",
+            ),
+            distribution(
+                "foo_fork",
+                "foo/__init__.py,sha256=def,10\n\
+                 google/cloud/bar.py,sha256=abc,10\n\
+                 foo_fork-1.0.0.dist-info/RECORD,,\n\
+                 ../../bin/foo,sha256=abc,10\n",
+            ),
+            distribution("six", "six.py,sha256=abc,10\n\"six,old.py\",,\n"),
+        ];
+
+        assert_eq!(
+            conflicting_modules(&distributions).unwrap(),
+            vec![EnvironmentProblem::ConflictingModule {
+                module: "foo".to_string(),
+                distributions: vec!["foo".to_string(), "foo-fork".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn shebangs() {
+        let root = tempfile::tempdir().unwrap();
+        let scripts = root.path().join("bin");
+        fs::create_dir(&scripts).unwrap();
+        let python = scripts.join("python");
+        fs::write(&python, "").unwrap();
+        std::os::unix::fs::symlink(&python, scripts.join("python3")).unwrap();
+
+        fs::write(
+            scripts.join("ok"),
+            format!("#!{}\nimport sys\n", scripts.join("python3").display()),
+        )
+        .unwrap();
+        fs::write(scripts.join("activate"), "# This file must be sourced\n").unwrap();
+        fs::write(scripts.join("shell"), "#!/bin/bash\necho hello\n").unwrap();
+        fs::write(scripts.join("env"), "#!/usr/bin/env python\nimport sys\n").unwrap();
+        fs::write(
+            scripts.join("moved"),
+            "#!/old/venv/bin/python\nimport sys\n",
+        )
+        .unwrap();
+        fs::write(
+            scripts.join("trampoline"),
+            "#!/bin/sh\n'''exec' '/old venv/it'\"'\"'s/python' \"$0\" \"$@\"\n' '''\nimport sys\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            foreign_shebangs(&scripts, &python).unwrap(),
+            vec![
+                EnvironmentProblem::ForeignShebang {
+                    script: scripts.join("moved"),
+                    interpreter: PathBuf::from("/old/venv/bin/python"),
+                },
+                EnvironmentProblem::ForeignShebang {
+                    script: scripts.join("trampoline"),
+                    interpreter: PathBuf::from("/old venv/it's/python"),
+                },
+            ]
+        );
+    }
+}
@@ -1,14 +1,18 @@
-use std::io;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 use configparser::ini::Ini;
 use fs_err as fs;
 use once_cell::sync::OnceCell;
 use same_file::is_same_file;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
 
 use cache_key::digest;
 use install_wheel_rs::Layout;
@@ -20,7 +24,9 @@ use pypi_types::Scheme;
 use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
 use uv_fs::{write_atomic_sync, PythonExt, Simplified};
 
+use crate::implementation::LenientImplementationName;
 use crate::pointer_size::PointerSize;
+use crate::probe::StaticProbe;
 use crate::{Prefix, PythonVersion, Target, VirtualEnvironment};
 
 /// A Python executable and its associated platform markers.
@@ -42,20 +48,222 @@ pub struct Interpreter {
     prefix: Option<Prefix>,
     pointer_size: PointerSize,
     gil_disabled: bool,
+    build_revision: Option<String>,
+    build_info: BuildInfo,
+    sysconfig_vars: SysconfigVars,
+    user_site: Option<UserSite>,
+    missing_stdlib_modules: Vec<String>,
+    canonicalization: CanonicalizationPolicy,
+}
+
+/// The `sysconfig` configuration variables of an interpreter that are needed to build and link
+/// native extensions, i.e., a subset of `sysconfig.get_config_vars()`.
+///
+/// Variables that aren't defined on the platform, e.g., `LDLIBRARY` on Windows, are `None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SysconfigVars {
+    /// The filename suffix of extension modules, i.e., `EXT_SUFFIX`, e.g.,
+    /// `.cpython-312-x86_64-linux-gnu.so`.
+    pub ext_suffix: Option<String>,
+    /// The ABI tag of extension modules, i.e., `SOABI`, e.g., `cpython-312-x86_64-linux-gnu`.
+    pub soabi: Option<String>,
+    /// The ABI flags of the build, i.e., `sys.abiflags`, e.g., `t` for free-threaded builds or
+    /// `d` for debug builds.
+    pub abiflags: Option<String>,
+    /// The multiarch triplet, i.e., `MULTIARCH`, e.g., `x86_64-linux-gnu`.
+    pub multiarch: Option<String>,
+    /// The directory containing the Python library, i.e., `LIBDIR`.
+    pub libdir: Option<PathBuf>,
+    /// The filename of the Python library to link against, i.e., `LDLIBRARY`, e.g.,
+    /// `libpython3.12.so`.
+    pub ldlibrary: Option<String>,
+    /// The directory containing `Python.h`, i.e., `INCLUDEPY`.
+    pub includepy: Option<PathBuf>,
+    /// Whether the interpreter was built with a shared library, i.e., `Py_ENABLE_SHARED`.
+    pub py_enable_shared: bool,
+}
+
+/// How an interpreter was built, as reported by `platform.python_build()` and
+/// `platform.python_compiler()`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BuildInfo {
+    /// The build number, e.g., `main` or `tags/v3.12.3:f6650f9`.
+    pub build_number: Option<String>,
+    /// The build date, e.g., `Apr  9 2024 14:05:25`.
+    pub build_date: Option<String>,
+    /// The compiler used to build the interpreter, e.g., `GCC 13.2.0` or `Clang 17.0.6 `.
+    pub compiler: Option<String>,
+}
+
+/// The per-user installation directories of an interpreter, as defined by PEP 370.
+///
+/// See: <https://peps.python.org/pep-0370/>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UserSite {
+    /// The user `site-packages` directory, i.e., `site.getusersitepackages()`, e.g.,
+    /// `~/.local/lib/python3.12/site-packages`.
+    pub site_packages: PathBuf,
+    /// The directory for scripts installed into the user site, e.g., `~/.local/bin`.
+    pub scripts: PathBuf,
+}
+
+/// How symlinks are resolved when determining the base executable of an interpreter, e.g., to
+/// create a virtual environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CanonicalizationPolicy {
+    /// Resolve symlinks, but stop before entering the Nix store.
+    ///
+    /// Nix profiles (e.g., `~/.nix-profile/bin/python3`) are symlinks into `/nix/store`. The
+    /// store path of an interpreter changes on upgrades and is removed by garbage collection, so
+    /// environments should refer to the profile path instead.
+    #[default]
+    Auto,
+    /// Resolve all symlinks to the real executable.
+    Always,
+    /// Do not resolve symlinks.
+    Never,
 }
 
 impl Interpreter {
     /// Detect the interpreter info for the given Python executable.
+    ///
+    /// Queries that fail with a transient error are retried with the default
+    /// [`QueryRetryPolicy`].
     pub fn query(executable: impl AsRef<Path>, cache: &Cache) -> Result<Self, Error> {
-        let info = InterpreterInfo::query_cached(executable.as_ref(), cache)?;
+        Self::query_with_retries(executable, cache, &QueryRetryPolicy::default())
+    }
+
+    /// Detect the interpreter info for each of the given Python executables, e.g., to list all
+    /// available interpreters.
+    ///
+    /// The executables are queried concurrently, with at most one query per available CPU at a
+    /// time. The results are returned in the order of the given executables.
+    pub fn query_many(
+        executables: &[impl AsRef<Path> + Sync],
+        cache: &Cache,
+    ) -> Vec<Result<Self, Error>> {
+        let workers = std::thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(executables.len());
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<Option<Result<Self, Error>>> = std::iter::repeat_with(|| None)
+            .take(executables.len())
+            .collect();
+
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(executable) = executables.get(index) else {
+                                break;
+                            };
+                            results.push((index, Self::query(executable, cache)));
+                        }
+                        results
+                    })
+                })
+                .collect();
+            for worker in workers {
+                for (index, result) in worker.join().expect("interpreter query panicked") {
+                    results[index] = Some(result);
+                }
+            }
+        });
 
+        results
+            .into_iter()
+            .map(|result| result.expect("every executable is queried"))
+            .collect()
+    }
+
+    /// Detect the interpreter info for the given Python executable, retrying transient failures
+    /// according to the given [`QueryRetryPolicy`].
+    pub fn query_with_retries(
+        executable: impl AsRef<Path>,
+        cache: &Cache,
+        retries: &QueryRetryPolicy,
+    ) -> Result<Self, Error> {
+        let executable = executable.as_ref();
+        let info = retries.run(executable, || {
+            InterpreterInfo::query_cached(executable, cache)
+        })?;
+        Ok(Self::from_info(info))
+    }
+
+    /// Query the given Python executable and record its interpreter info at `path`, such that it
+    /// can be read with [`Interpreter::from_record`] without running the executable again.
+    ///
+    /// Used to record the interpreter info of managed toolchains when they are installed.
+    pub(crate) fn record(executable: &Path, path: &Path) -> Result<(), Error> {
+        // Stage the query script next to the record, rather than in the system temporary directory.
+        let parent = path.parent().expect("Record path must have a parent");
+        let info = InterpreterInfo::query(executable, parent)?;
+        let record = InterpreterRecord {
+            version: CacheBucket::Interpreter.to_string(),
+            info,
+        };
+        write_atomic_sync(
+            path,
+            serde_json::to_vec_pretty(&record).map_err(io::Error::from)?,
+        )?;
+        Ok(())
+    }
+
+    /// Read the interpreter info recorded at `path` with [`Interpreter::record`].
+    ///
+    /// Returns `None` if there is no record, if it was written by a version of uv with a different
+    /// interpreter info format, or if it doesn't belong to the given executable, e.g., because the
+    /// toolchain was moved since it was recorded.
+    pub(crate) fn from_record(executable: &Path, path: &Path) -> Result<Option<Self>, Error> {
+        let contents = match fs::read(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        // Check the version before parsing the interpreter info, since its format may have changed.
+        let version = serde_json::from_slice::<InterpreterRecordVersion>(&contents)
+            .ok()
+            .and_then(|record| record.version);
+        if version.as_deref() != Some(CacheBucket::Interpreter.to_string().as_str()) {
+            trace!(
+                "Ignoring outdated interpreter record at {}",
+                path.user_display()
+            );
+            return Ok(None);
+        }
+
+        let info = match serde_json::from_slice::<InterpreterRecord>(&contents) {
+            Ok(record) => record.info,
+            Err(err) => {
+                warn!(
+                    "Ignoring broken interpreter record at {}: {err}",
+                    path.user_display()
+                );
+                return Ok(None);
+            }
+        };
+        if !is_same_file(executable, &info.sys_executable).unwrap_or(false) {
+            trace!(
+                "Ignoring stale interpreter record at {}",
+                path.user_display()
+            );
+            return Ok(None);
+        }
+        Ok(Some(Self::from_info(info)))
+    }
+
+    fn from_info(info: InterpreterInfo) -> Self {
         debug_assert!(
             info.sys_executable.is_absolute(),
             "`sys.executable` is not an absolute Python; Python installation is broken: {}",
             info.sys_executable.display()
         );
 
-        Ok(Self {
+        Self {
             platform: info.platform,
             markers: Box::new(info.markers),
             scheme: info.scheme,
@@ -64,6 +272,11 @@ impl Interpreter {
             sys_base_exec_prefix: info.sys_base_exec_prefix,
             pointer_size: info.pointer_size,
             gil_disabled: info.gil_disabled,
+            build_revision: info.build_revision,
+            build_info: info.build_info,
+            sysconfig_vars: info.sysconfig_vars,
+            user_site: info.user_site,
+            missing_stdlib_modules: info.missing_stdlib_modules,
             sys_base_prefix: info.sys_base_prefix,
             sys_base_executable: info.sys_base_executable,
             sys_executable: info.sys_executable,
@@ -72,7 +285,8 @@ impl Interpreter {
             tags: OnceCell::new(),
             target: None,
             prefix: None,
-        })
+            canonicalization: CanonicalizationPolicy::default(),
+        }
     }
 
     // TODO(konstin): Find a better way mocking the fields
@@ -106,6 +320,12 @@ impl Interpreter {
             prefix: None,
             pointer_size: PointerSize::_64,
             gil_disabled: false,
+            build_revision: None,
+            build_info: BuildInfo::default(),
+            sysconfig_vars: SysconfigVars::default(),
+            user_site: None,
+            missing_stdlib_modules: Vec::new(),
+            canonicalization: CanonicalizationPolicy::default(),
         }
     }
 
@@ -140,6 +360,15 @@ impl Interpreter {
         }
     }
 
+    /// Return a new [`Interpreter`] with the given [`CanonicalizationPolicy`].
+    #[must_use]
+    pub fn with_canonicalization(self, canonicalization: CanonicalizationPolicy) -> Self {
+        Self {
+            canonicalization,
+            ..self
+        }
+    }
+
     /// Returns the path to the Python virtual environment.
     #[inline]
     pub fn platform(&self) -> &Platform {
@@ -165,12 +394,52 @@ impl Interpreter {
         })
     }
 
+    /// Returns the PEP 370 user site directories of this Python executable, if supported.
+    ///
+    /// The directories are returned whether or not the user site is enabled, e.g., it's disabled
+    /// in virtual environments that don't include the system site packages, but packages in the
+    /// user site may still shadow those of the base interpreter.
+    pub fn user_site(&self) -> Option<&UserSite> {
+        self.user_site.as_ref()
+    }
+
+    /// Returns the standard library modules used to create and seed virtual environments that
+    /// are missing from this Python executable, e.g., `ensurepip` and `distutils` on Debian and
+    /// Ubuntu, which ship them separately as `python3-venv` and `python3-distutils`.
+    pub fn missing_stdlib_modules(&self) -> &[String] {
+        &self.missing_stdlib_modules
+    }
+
+    /// Returns `true` if `ensurepip` is available, i.e., if `python -m venv` and
+    /// `python -m ensurepip` can install `pip`.
+    pub fn has_ensurepip(&self) -> bool {
+        !self
+            .missing_stdlib_modules
+            .iter()
+            .any(|module| module == "ensurepip")
+    }
+
+    /// Returns the wheel tags supported by this Python executable as
+    /// `(python_tag, abi_tag, platform_tag)` triples, ordered from most to least preferred.
+    ///
+    /// Follows the PEP 425 priority list, i.e., the interpreter-specific tags (e.g., `cp312`),
+    /// then `abi3`, then the generic `py3` tags, with each platform tag expanded to all compatible
+    /// platforms (e.g., older `manylinux` and `musllinux` versions).
+    pub fn supported_tags(&self) -> Result<Vec<(&str, &str, &str)>, TagsError> {
+        Ok(self.tags()?.by_priority())
+    }
+
     /// Returns `true` if the environment is a PEP 405-compliant virtual environment.
     ///
     /// See: <https://github.com/pypa/pip/blob/0ad4c94be74cc24874c6feb5bb3c2152c398a18e/src/pip/_internal/utils/virtualenv.py#L14>
     pub fn is_virtualenv(&self) -> bool {
         // Maybe this should return `false` if it's a target?
-        self.sys_prefix != self.sys_base_prefix
+        if self.sys_prefix == self.sys_base_prefix {
+            return false;
+        }
+        // The prefixes may refer to the same directory through different paths, e.g., if the
+        // prefix contains a symlink or, on Windows, differs in case.
+        !is_same_file(&self.sys_prefix, &self.sys_base_prefix).unwrap_or(false)
     }
 
     /// Returns `true` if the environment is a `--target` environment.
@@ -202,28 +471,10 @@ impl Interpreter {
             return None;
         };
 
-        let mut ini = Ini::new_cs();
-        ini.set_multiline(true);
-
-        let Ok(mut sections) = ini.read(contents) else {
-            // If a file exists but is not a valid INI file, we assume the environment is
-            // externally managed.
-            return Some(ExternallyManaged::default());
-        };
-
-        let Some(section) = sections.get_mut("externally-managed") else {
-            // If the file exists but does not contain an "externally-managed" section, we assume
-            // the environment is externally managed.
-            return Some(ExternallyManaged::default());
-        };
-
-        let Some(error) = section.remove("Error") else {
-            // If the file exists but does not contain an "Error" key, we assume the environment is
-            // externally managed.
-            return Some(ExternallyManaged::default());
-        };
-
-        Some(ExternallyManaged { error })
+        Some(ExternallyManaged::parse(
+            contents,
+            message_locale().as_deref(),
+        ))
     }
 
     /// Returns the `python_full_version` marker corresponding to this Python version.
@@ -284,6 +535,31 @@ impl Interpreter {
         (self.implementation_major(), self.implementation_minor())
     }
 
+    /// Returns the full version of the implementation, e.g., `7.3.15` for `PyPy` 7.3.15.
+    ///
+    /// Unlike [`Interpreter::python_version`], which is the version of the Python language the
+    /// interpreter is compatible with, e.g., `3.10.13`, this is the version of the implementation
+    /// itself. For `CPython`, the two are the same.
+    pub fn implementation_version(&self) -> &Version {
+        &self.markers.implementation_version().version
+    }
+
+    /// Returns a description of the implementation and version of the interpreter for display,
+    /// e.g., `CPython 3.12.3`, or `PyPy 7.3.15 (Python 3.10.13)` for implementations whose version
+    /// differs from the Python version.
+    pub fn implementation_display(&self) -> String {
+        let implementation = LenientImplementationName::from(self.implementation_name());
+        let python_version = self.python_full_version();
+        if self.implementation_version() == &python_version.version {
+            format!("{implementation} {python_version}")
+        } else {
+            format!(
+                "{implementation} {} (Python {python_version})",
+                self.markers.implementation_version()
+            )
+        }
+    }
+
     /// Returns the implementation name (e.g., `CPython` or `PyPy`).
     pub fn implementation_name(&self) -> &str {
         self.markers.implementation_name()
@@ -315,6 +591,17 @@ impl Interpreter {
         &self.sys_executable
     }
 
+    /// Return the `sys.executable` path with symlinks resolved according to the
+    /// [`CanonicalizationPolicy`].
+    pub fn resolved_executable(&self) -> io::Result<PathBuf> {
+        resolve_executable(&self.sys_executable, self.canonicalization)
+    }
+
+    /// Return the [`CanonicalizationPolicy`] for this Python interpreter.
+    pub fn canonicalization(&self) -> CanonicalizationPolicy {
+        self.canonicalization
+    }
+
     /// Return the `sys.path` for this Python interpreter.
     pub fn sys_path(&self) -> &Vec<PathBuf> {
         &self.sys_path
@@ -369,6 +656,48 @@ impl Interpreter {
         self.gil_disabled
     }
 
+    /// Return the ABI flags of the interpreter, i.e., `sys.abiflags`, e.g., `t` for a
+    /// free-threaded build of `CPython` 3.13, whose ABI tag is `cp313t` rather than `cp313`.
+    ///
+    /// Returns `None` if the interpreter doesn't report its ABI flags, e.g., on Windows prior to
+    /// Python 3.13.
+    pub fn abiflags(&self) -> Option<&str> {
+        self.sysconfig_vars.abiflags.as_deref()
+    }
+
+    /// Return the ABI tag of extension modules built for the interpreter, i.e., `SOABI`, e.g.,
+    /// `cpython-313t-x86_64-linux-gnu`.
+    pub fn soabi(&self) -> Option<&str> {
+        self.sysconfig_vars.soabi.as_deref()
+    }
+
+    /// Return the `sysconfig` variables needed to build and link native extensions, as reported
+    /// by the interpreter.
+    pub fn sysconfig_vars(&self) -> &SysconfigVars {
+        &self.sysconfig_vars
+    }
+
+    /// Return the source revision the interpreter was built from, e.g., the `CPython` git commit
+    /// hash, as reported by `platform.python_revision()`, if known.
+    pub fn build_revision(&self) -> Option<&str> {
+        self.build_revision.as_deref()
+    }
+
+    /// Return the build number, build date, and compiler of the interpreter, e.g., to tell a
+    /// distribution's build apart from a python-build-standalone build of the same version.
+    pub fn build_info(&self) -> &BuildInfo {
+        &self.build_info
+    }
+
+    /// Returns `true` if the Python runtime is statically linked into the executable rather than
+    /// loaded from `libpython`, i.e., if `Py_ENABLE_SHARED` is unset.
+    ///
+    /// Always `false` on Windows, where the runtime is loaded from `pythonXY.dll`.
+    pub fn is_statically_linked(&self) -> bool {
+        !self.sysconfig_vars.py_enable_shared
+            && !matches!(self.platform.os(), platform_tags::Os::Windows)
+    }
+
     /// Return the `--target` directory for this interpreter, if any.
     pub fn target(&self) -> Option<&Target> {
         self.target.as_ref()
@@ -386,7 +715,7 @@ impl Interpreter {
             sys_executable: self.sys_executable().to_path_buf(),
             os_name: self.markers.os_name().to_string(),
             scheme: if let Some(target) = self.target.as_ref() {
-                target.scheme()
+                target.scheme(&self.virtualenv)
             } else if let Some(prefix) = self.prefix.as_ref() {
                 prefix.scheme(&self.virtualenv)
             } else {
@@ -435,6 +764,52 @@ impl Interpreter {
             (version.major(), version.minor()) == self.python_tuple()
         }
     }
+
+    /// Returns `true` if the interpreter is interchangeable with the given interpreter for the
+    /// purpose of installing packages, i.e., if both have the same implementation, major and
+    /// minor version, ABI, and platform.
+    ///
+    /// For example, a virtual environment created with one interpreter can be reused for a
+    /// request that resolved to the other.
+    pub fn is_compatible_with(&self, other: &Interpreter) -> bool {
+        self.implementation_name() == other.implementation_name()
+            && self.python_tuple() == other.python_tuple()
+            && self.implementation_tuple() == other.implementation_tuple()
+            && self.gil_disabled == other.gil_disabled
+            && self.abiflags() == other.abiflags()
+            && self.soabi() == other.soabi()
+            && self.pointer_size == other.pointer_size
+            && self.platform == other.platform
+    }
+
+    /// Returns `true` if the interpreter is compatible with the given interpreter and both belong
+    /// to the same base installation, i.e., share `sys.base_prefix`.
+    ///
+    /// For example, this is the case for a virtual environment's interpreter and the interpreter
+    /// it was created from.
+    pub fn same_base(&self, other: &Interpreter) -> bool {
+        self.is_compatible_with(other)
+            && (self.sys_base_prefix == other.sys_base_prefix
+                || is_same_file(&self.sys_base_prefix, &other.sys_base_prefix).unwrap_or(false))
+    }
+}
+
+/// Returns the locale for messages from the environment, e.g., `de_DE` for `LANG=de_DE.UTF-8`,
+/// following the precedence of `LC_ALL`, `LC_MESSAGES`, and `LANG`.
+fn message_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            // Strip the encoding and modifier, e.g., `.UTF-8` or `@euro`.
+            value
+                .split(['.', '@'])
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .filter(|locale| !matches!(locale.as_str(), "" | "C" | "POSIX"))
 }
 
 /// The `EXTERNALLY-MANAGED` file in a Python installation.
@@ -446,6 +821,44 @@ pub struct ExternallyManaged {
 }
 
 impl ExternallyManaged {
+    /// Parse the contents of an `EXTERNALLY-MANAGED` file.
+    ///
+    /// The error message is localized for the given locale, e.g., `de_DE`, if the file contains a
+    /// translation, i.e., an `Error-de_DE` or `Error-de` key, falling back to the `Error` key.
+    fn parse(contents: String, locale: Option<&str>) -> Self {
+        let mut ini = Ini::new_cs();
+        ini.set_multiline(true);
+
+        let Ok(mut sections) = ini.read(contents) else {
+            // If a file exists but is not a valid INI file, we assume the environment is
+            // externally managed.
+            return Self::default();
+        };
+
+        let Some(section) = sections.get_mut("externally-managed") else {
+            // If the file exists but does not contain an "externally-managed" section, we assume
+            // the environment is externally managed.
+            return Self::default();
+        };
+
+        // Per the spec, prefer the most specific translation of the error message.
+        let mut keys = Vec::new();
+        if let Some(locale) = locale {
+            keys.push(format!("Error-{locale}"));
+            if let Some((language, _)) = locale.split_once('_') {
+                keys.push(format!("Error-{language}"));
+            }
+        }
+        keys.push("Error".to_string());
+
+        // If the file exists but does not contain an "Error" key, we assume the environment is
+        // externally managed.
+        let error = keys
+            .iter()
+            .find_map(|key| section.remove(key.as_str()).flatten());
+        Self { error }
+    }
+
     /// Return the `EXTERNALLY-MANAGED` error message, if any.
     pub fn into_error(self) -> Option<String> {
         self.error
@@ -456,13 +869,13 @@ impl ExternallyManaged {
 pub enum Error {
     #[error(transparent)]
     Io(#[from] io::Error),
-    #[error("Failed to query Python interpreter at `{path}`")]
+    #[error("Failed to query Python interpreter at `{}`", path.user_display())]
     SpawnFailed {
         path: PathBuf,
         #[source]
         err: io::Error,
     },
-    #[error("Querying Python at `{}` did not return the expected data\n{err}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---", path.display())]
+    #[error("Querying Python at `{}` did not return the expected data\n{err}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---", path.user_display())]
     UnexpectedResponse {
         err: serde_json::Error,
         stdout: String,
@@ -470,23 +883,129 @@ pub enum Error {
         path: PathBuf,
     },
 
-    #[error("Querying Python at `{}` failed with exit status {code}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---", path.display())]
+    #[error("Querying Python at `{}` failed with exit status {code}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---", path.user_display())]
     StatusCode {
         code: ExitStatus,
         stdout: String,
         stderr: String,
         path: PathBuf,
     },
-    #[error("Can't use Python at `{path}`")]
+    #[error("Can't use Python at `{}`", path.user_display())]
     QueryScript {
         #[source]
         err: InterpreterInfoError,
         path: PathBuf,
     },
+    #[error("Found {probe} at `{}`, but it is unusable, e.g., its standard library may be missing or broken", path.user_display())]
+    Unusable {
+        probe: StaticProbe,
+        path: PathBuf,
+        #[source]
+        err: Box<Error>,
+    },
     #[error("Failed to write to cache")]
     Encode(#[from] rmp_serde::encode::Error),
 }
 
+impl Error {
+    /// Returns `true` if the error may be caused by a transient condition, such that querying the
+    /// interpreter again may succeed.
+    ///
+    /// For example, an antivirus scanner may briefly lock the executable on Windows, or a network
+    /// file system may briefly be unavailable.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Io(err) | Self::SpawnFailed { err, .. } => is_transient_io_error(err),
+            Self::UnexpectedResponse { .. }
+            | Self::StatusCode { .. }
+            | Self::QueryScript { .. }
+            | Self::Unusable { .. }
+            | Self::Encode(_) => false,
+        }
+    }
+}
+
+/// Returns `true` if the I/O error may be caused by a transient condition.
+fn is_transient_io_error(err: &io::Error) -> bool {
+    if matches!(
+        err.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    ) {
+        return true;
+    }
+    let Some(code) = err.raw_os_error() else {
+        return false;
+    };
+    if cfg!(windows) {
+        // `ERROR_SHARING_VIOLATION` and `ERROR_LOCK_VIOLATION`, e.g., due to antivirus scanners.
+        matches!(code, 32 | 33)
+    } else if cfg!(target_os = "linux") {
+        // `ETXTBSY` while the executable is being written, and `ESTALE` on NFS.
+        matches!(code, 26 | 116)
+    } else if cfg!(target_os = "macos") {
+        // `ETXTBSY` and `ESTALE`.
+        matches!(code, 26 | 70)
+    } else {
+        false
+    }
+}
+
+/// The policy for retrying interpreter queries that fail with a transient error.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryRetryPolicy {
+    /// The number of retries after the initial attempt.
+    pub retries: u32,
+    /// The delay before the first retry, which is doubled for each subsequent retry.
+    pub backoff: Duration,
+    /// Whether a query error is transient and should be retried.
+    pub is_transient: fn(&Error) -> bool,
+}
+
+impl Default for QueryRetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            backoff: Duration::from_millis(25),
+            is_transient: Error::is_transient,
+        }
+    }
+}
+
+impl QueryRetryPolicy {
+    /// A policy that never retries.
+    pub fn never() -> Self {
+        Self {
+            retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Run the query, retrying transient failures.
+    fn run<T>(
+        &self,
+        executable: &Path,
+        mut query: impl FnMut() -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let mut backoff = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match query() {
+                Err(err) if attempt < self.retries && (self.is_transient)(&err) => {
+                    attempt += 1;
+                    debug!(
+                        "Retrying query of `{}` after transient error (attempt {attempt} of {}): {err}",
+                        executable.user_display(),
+                        self.retries
+                    );
+                    std::thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(tag = "result", rename_all = "lowercase")]
 enum InterpreterInfoResult {
@@ -522,12 +1041,51 @@ struct InterpreterInfo {
     stdlib: PathBuf,
     pointer_size: PointerSize,
     gil_disabled: bool,
+    #[serde(default)]
+    build_revision: Option<String>,
+    #[serde(default)]
+    build_info: BuildInfo,
+    #[serde(default)]
+    sysconfig_vars: SysconfigVars,
+    #[serde(default)]
+    user_site: Option<UserSite>,
+    #[serde(default)]
+    missing_stdlib_modules: Vec<String>,
 }
 
 impl InterpreterInfo {
     /// Return the resolved [`InterpreterInfo`] for the given Python executable.
-    pub(crate) fn query(interpreter: &Path, cache: &Cache) -> Result<Self, Error> {
-        let tempdir = tempfile::tempdir_in(cache.root())?;
+    ///
+    /// If the query script fails, e.g., because the interpreter's standard library is broken, the
+    /// binary is probed statically to identify the interpreter in the error.
+    ///
+    /// The query script is written to a temporary directory in `scratch`.
+    pub(crate) fn query(interpreter: &Path, scratch: &Path) -> Result<Self, Error> {
+        match Self::query_script(interpreter, scratch) {
+            Err(err @ (Error::StatusCode { .. } | Error::UnexpectedResponse { .. })) => {
+                match StaticProbe::from_executable(interpreter) {
+                    Ok(Some(probe)) => Err(Error::Unusable {
+                        probe,
+                        path: interpreter.to_path_buf(),
+                        err: Box::new(err),
+                    }),
+                    Ok(None) => Err(err),
+                    Err(probe_err) => {
+                        debug!(
+                            "Failed to probe Python executable at `{}`: {probe_err}",
+                            interpreter.user_display()
+                        );
+                        Err(err)
+                    }
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Run the query script with the given Python executable.
+    fn query_script(interpreter: &Path, scratch: &Path) -> Result<Self, Error> {
+        let tempdir = tempfile::tempdir_in(scratch)?;
         Self::setup_python_query_files(tempdir.path())?;
 
         // Sanitize the path by (1) running under isolated mode (`-I`) to ignore any site packages
@@ -537,10 +1095,14 @@ impl InterpreterInfo {
             r#"import sys; sys.path = ["{}"] + sys.path; from python.get_interpreter_info import main; main()"#,
             tempdir.path().escape_for_python()
         );
+        // Isolated mode already ignores `PYTHONPATH` and `PYTHONSTARTUP`, but clear them anyway in
+        // case the interpreter is a wrapper that launches another Python without `-I`.
         let output = Command::new(interpreter)
             .arg("-I")
             .arg("-c")
             .arg(script)
+            .env_remove("PYTHONPATH")
+            .env_remove("PYTHONSTARTUP")
             .output()
             .map_err(|err| Error::SpawnFailed {
                 path: interpreter.to_path_buf(),
@@ -566,25 +1128,25 @@ impl InterpreterInfo {
             });
         }
 
-        let result: InterpreterInfoResult =
-            serde_json::from_slice(&output.stdout).map_err(|err| {
-                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let result: InterpreterInfoResult = serde_json::from_slice(query_payload(&output.stdout))
+            .map_err(|err| {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
 
-                // If the Python version is too old, we may not even be able to invoke the query script
-                if stderr.contains("Unknown option: -I") {
-                    Error::QueryScript {
-                        err: InterpreterInfoError::UnsupportedPython,
-                        path: interpreter.to_path_buf(),
-                    }
-                } else {
-                    Error::UnexpectedResponse {
-                        err,
-                        stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
-                        stderr,
-                        path: interpreter.to_path_buf(),
-                    }
+            // If the Python version is too old, we may not even be able to invoke the query script
+            if stderr.contains("Unknown option: -I") {
+                Error::QueryScript {
+                    err: InterpreterInfoError::UnsupportedPython,
+                    path: interpreter.to_path_buf(),
                 }
-            })?;
+            } else {
+                Error::UnexpectedResponse {
+                    err,
+                    stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+                    stderr,
+                    path: interpreter.to_path_buf(),
+                }
+            }
+        })?;
 
         match result {
             InterpreterInfoResult::Error(err) => Err(Error::QueryScript {
@@ -633,7 +1195,9 @@ impl InterpreterInfo {
     ///
     /// Running a Python script is (relatively) expensive, and the markers won't change
     /// unless the Python executable changes, so we use the executable's last modified
-    /// time as a cache key.
+    /// time and its [`ExecutableSignature`] as a cache key. The signature catches executables
+    /// that were replaced while preserving the modification time, e.g., when copied into place
+    /// with `cp -p`, or when rebuilt by pyenv at the same path.
     pub(crate) fn query_cached(executable: &Path, cache: &Cache) -> Result<Self, Error> {
         let cache_entry = cache.entry(
             CacheBucket::Interpreter,
@@ -643,9 +1207,11 @@ impl InterpreterInfo {
             format!("{}.msgpack", digest(&uv_fs::absolutize_path(executable)?)),
         );
 
-        // We check the timestamp of the canonicalized executable to check if an underlying
-        // interpreter has been modified
-        let modified = Timestamp::from_path(uv_fs::canonicalize_executable(executable)?)?;
+        // We check the timestamp and signature of the canonicalized executable to check if an
+        // underlying interpreter has been modified
+        let canonical = uv_fs::canonicalize_executable(executable)?;
+        let modified = Timestamp::from_path(&canonical)?;
+        let signature = ExecutableSignature::from_path(&canonical)?;
 
         // Read from the cache.
         if cache
@@ -653,15 +1219,15 @@ impl InterpreterInfo {
             .is_ok_and(Freshness::is_fresh)
         {
             if let Ok(data) = fs::read(cache_entry.path()) {
-                match rmp_serde::from_slice::<CachedByTimestamp<Self>>(&data) {
+                match rmp_serde::from_slice::<CachedByTimestamp<CachedInterpreterInfo>>(&data) {
                     Ok(cached) => {
-                        if cached.timestamp == modified {
+                        if cached.timestamp == modified && cached.data.signature == signature {
                             trace!(
                                 "Cached interpreter info for Python {}, skipping probing: {}",
-                                cached.data.markers.python_full_version(),
+                                cached.data.info.markers.python_full_version(),
                                 executable.user_display()
                             );
-                            return Ok(cached.data);
+                            return Ok(cached.data.info);
                         }
 
                         trace!(
@@ -685,7 +1251,7 @@ impl InterpreterInfo {
             "Querying interpreter executable at {}",
             executable.display()
         );
-        let info = Self::query(executable, cache)?;
+        let info = Self::query(executable, cache.root())?;
 
         // If `executable` is a pyenv shim, a bash script that redirects to the activated
         // python executable at another path, we're not allowed to cache the interpreter info.
@@ -695,7 +1261,10 @@ impl InterpreterInfo {
                 cache_entry.path(),
                 rmp_serde::to_vec(&CachedByTimestamp {
                     timestamp: modified,
-                    data: info.clone(),
+                    data: CachedInterpreterInfo {
+                        signature,
+                        info: info.clone(),
+                    },
                 })?,
             )?;
         }
@@ -704,9 +1273,134 @@ impl InterpreterInfo {
     }
 }
 
+/// The [`InterpreterInfo`] of an executable recorded with [`Interpreter::record`].
+#[derive(Debug, Deserialize, Serialize)]
+struct InterpreterRecord {
+    /// The [`CacheBucket::Interpreter`] version the record was written for, such that records are
+    /// invalidated whenever the format of the interpreter info changes.
+    version: String,
+    info: InterpreterInfo,
+}
+
+/// The version of an [`InterpreterRecord`], which can be read regardless of the format of the
+/// interpreter info; records written by older versions of uv have no version.
+#[derive(Debug, Deserialize)]
+struct InterpreterRecordVersion {
+    #[serde(default)]
+    version: Option<String>,
+}
+
+/// The [`InterpreterInfo`] of an executable in the cache, along with the signature of the
+/// executable it was queried from.
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedInterpreterInfo {
+    signature: ExecutableSignature,
+    info: InterpreterInfo,
+}
+
+/// The number of bytes at the start and end of an executable included in its
+/// [`ExecutableSignature`].
+const SIGNATURE_SAMPLE_SIZE: u64 = 64 * 1024;
+
+/// A fast fingerprint of the contents of an executable: its size and a SHA256 digest of its first
+/// and last [`SIGNATURE_SAMPLE_SIZE`] bytes.
+///
+/// Hashing the entire executable would dominate the cost of a cache hit for large, statically
+/// linked interpreters. The headers at the start of the binary and the sections at its end change
+/// with any rebuild, so sampling them suffices to detect replaced executables.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct ExecutableSignature {
+    size: u64,
+    digest: String,
+}
+
+impl ExecutableSignature {
+    fn from_path(path: &Path) -> io::Result<Self> {
+        let mut file = fs::File::open(path)?;
+        let size = file.metadata()?.len();
+        let mut hasher = Sha256::new();
+        io::copy(&mut file.by_ref().take(SIGNATURE_SAMPLE_SIZE), &mut hasher)?;
+        if size > SIGNATURE_SAMPLE_SIZE {
+            file.seek(SeekFrom::Start(
+                size.saturating_sub(SIGNATURE_SAMPLE_SIZE)
+                    .max(SIGNATURE_SAMPLE_SIZE),
+            ))?;
+            io::copy(&mut file.take(SIGNATURE_SAMPLE_SIZE), &mut hasher)?;
+        }
+        Ok(Self {
+            size,
+            digest: format!("{:x}", hasher.finalize()),
+        })
+    }
+}
+
+/// The maximum number of symlinks to follow, matching the Linux limit.
+const MAX_SYMLINKS: usize = 40;
+
+/// Resolve the symlinks of an executable according to the [`CanonicalizationPolicy`].
+fn resolve_executable(
+    executable: &Path,
+    canonicalization: CanonicalizationPolicy,
+) -> io::Result<PathBuf> {
+    match canonicalization {
+        CanonicalizationPolicy::Always => uv_fs::canonicalize_executable(executable),
+        CanonicalizationPolicy::Never => Ok(uv_fs::absolutize_path(executable)?.into_owned()),
+        CanonicalizationPolicy::Auto => {
+            if !cfg!(unix) {
+                return uv_fs::canonicalize_executable(executable);
+            }
+
+            // Follow the symlinks one at a time, and stop before entering the Nix store.
+            let mut current = uv_fs::absolutize_path(executable)?.into_owned();
+            for _ in 0..MAX_SYMLINKS {
+                if !current.symlink_metadata()?.file_type().is_symlink() {
+                    break;
+                }
+                let target = fs::read_link(&current)?;
+                let target = match current.parent() {
+                    Some(parent) => uv_fs::normalize_path(&parent.join(target))?,
+                    None => target,
+                };
+                if is_nix_store_path(&target) && !is_nix_store_path(&current) {
+                    trace!(
+                        "Not resolving `{}` into the Nix store",
+                        current.simplified_display()
+                    );
+                    return Ok(current);
+                }
+                current = target;
+            }
+            uv_fs::canonicalize_executable(current)
+        }
+    }
+}
+
+/// Returns `true` if the path is in the Nix store.
+fn is_nix_store_path(path: &Path) -> bool {
+    path.starts_with("/nix/store")
+}
+
+/// The marker that precedes the JSON payload in the output of the query script.
+const QUERY_RESULT_MARKER: &[u8] = b"__UV_INTERPRETER_INFO__";
+
+/// Extract the JSON payload from the output of the query script.
+///
+/// Startup hooks, e.g., a `sitecustomize` module, may print to stdout before the query script
+/// runs, so only the output following the last marker is considered. If there's no marker, the
+/// output is returned as-is.
+fn query_payload(stdout: &[u8]) -> &[u8] {
+    stdout
+        .windows(QUERY_RESULT_MARKER.len())
+        .rposition(|window| window == QUERY_RESULT_MARKER)
+        .map_or(stdout, |position| {
+            &stdout[position + QUERY_RESULT_MARKER.len()..]
+        })
+}
+
 #[cfg(unix)]
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use std::str::FromStr;
 
     use fs_err as fs;
@@ -714,99 +1408,527 @@ mod tests {
     use tempfile::tempdir;
 
     use pep440_rs::Version;
-    use uv_cache::Cache;
+    use uv_cache::{Cache, CacheBucket};
 
     use crate::Interpreter;
 
+    use super::{
+        resolve_executable, CanonicalizationPolicy, Error, ExecutableSignature, ExternallyManaged,
+        InterpreterInfo, QueryRetryPolicy,
+    };
+
+    /// Without `distutils`, e.g., on Debian and Ubuntu without `python3-distutils`, the schemes
+    /// fall back to `sysconfig`, and match the schemes of interpreters that use it anyway.
     #[test]
-    fn test_cache_invalidation() {
-        let mock_dir = tempdir().unwrap();
-        let mocked_interpreter = mock_dir.path().join("python");
-        let json = indoc! {r##"
-            {
-                "result": "success",
-                "platform": {
-                    "os": {
-                        "name": "manylinux",
-                        "major": 2,
-                        "minor": 38
-                    },
-                    "arch": "x86_64"
-                },
-                "markers": {
-                    "implementation_name": "cpython",
-                    "implementation_version": "3.12.0",
-                    "os_name": "posix",
-                    "platform_machine": "x86_64",
-                    "platform_python_implementation": "CPython",
-                    "platform_release": "6.5.0-13-generic",
-                    "platform_system": "Linux",
-                    "platform_version": "#13-Ubuntu SMP PREEMPT_DYNAMIC Fri Nov  3 12:16:05 UTC 2023",
-                    "python_full_version": "3.12.0",
-                    "python_version": "3.12",
-                    "sys_platform": "linux"
-                },
-                "sys_base_exec_prefix": "/home/ferris/.pyenv/versions/3.12.0",
-                "sys_base_prefix": "/home/ferris/.pyenv/versions/3.12.0",
-                "sys_prefix": "/home/ferris/projects/uv/.venv",
-                "sys_executable": "/home/ferris/projects/uv/.venv/bin/python",
-                "sys_path": [
-                    "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/lib/python3.12",
-                    "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages"
-                ],
-                "stdlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12",
-                "scheme": {
-                    "data": "/home/ferris/.pyenv/versions/3.12.0",
-                    "include": "/home/ferris/.pyenv/versions/3.12.0/include",
-                    "platlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages",
-                    "purelib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages",
-                    "scripts": "/home/ferris/.pyenv/versions/3.12.0/bin"
-                },
-                "virtualenv": {
-                    "data": "",
-                    "include": "include",
-                    "platlib": "lib/python3.12/site-packages",
-                    "purelib": "lib/python3.12/site-packages",
-                    "scripts": "bin"
-                },
-                "pointer_size": "64",
-                "gil_disabled": true
+    fn scheme_without_distutils() {
+        let python = which::which("python3").expect("`python3` is required to run this test");
+        let temp_dir = tempdir().unwrap();
+        InterpreterInfo::setup_python_query_files(temp_dir.path()).unwrap();
+
+        let schemes = |patch: &str| {
+            let script = formatdoc! {r#"
+                import json, sys, sysconfig
+                sys.path = ["{}"] + sys.path
+                {patch}
+                from python.get_interpreter_info import get_scheme, get_virtualenv
+                print(json.dumps([get_scheme(), get_virtualenv()]))
+                "#,
+                temp_dir.path().display(),
+            };
+            let output = std::process::Command::new(&python)
+                .arg("-I")
+                .arg("-c")
+                .arg(script)
+                .output()
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            String::from_utf8(output.stdout).unwrap()
+        };
+
+        // Use `sysconfig` for the install scheme, as on Python 3.10 and later, and the `venv`
+        // scheme for virtualenvs, as on Python 3.11 and later.
+        let expected = schemes("sysconfig._PIP_USE_SYSCONFIG = True");
+
+        // Without `distutils`, older interpreters, which would use it for both schemes, fall back
+        // to `sysconfig` and its unaltered default scheme.
+        let actual = schemes(indoc! {r#"
+            sys.modules["distutils.command.install"] = None
+            sysconfig._PIP_USE_SYSCONFIG = False
+            names = sysconfig.get_scheme_names()
+            sysconfig.get_scheme_names = lambda: tuple(name for name in names if name != "venv")
+        "#});
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn query_retries() {
+        let policy = QueryRetryPolicy {
+            retries: 2,
+            backoff: std::time::Duration::ZERO,
+            ..QueryRetryPolicy::default()
+        };
+        let locked = || Error::Io(std::io::Error::from(std::io::ErrorKind::Interrupted));
+        let missing = || Error::Io(std::io::Error::from(std::io::ErrorKind::NotFound));
+
+        // Transient errors are retried until the query succeeds.
+        let mut attempts = 0;
+        let result = policy.run(Path::new("python"), || {
+            attempts += 1;
+            if attempts < 3 {
+                Err(locked())
+            } else {
+                Ok(attempts)
             }
-        "##};
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        // Retries are limited.
+        let mut attempts = 0;
+        let result: Result<(), _> = policy.run(Path::new("python"), || {
+            attempts += 1;
+            Err(locked())
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+
+        // Permanent errors are not retried.
+        let mut attempts = 0;
+        let result: Result<(), _> = policy.run(Path::new("python"), || {
+            attempts += 1;
+            Err(missing())
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
 
-        let cache = Cache::temp().unwrap().init().unwrap();
+    #[test]
+    fn resolve_executable_policy() {
+        let temp_dir = tempdir().unwrap();
+        let real = temp_dir.path().join("python3.12");
+        fs::write(&real, "").unwrap();
+        let link = temp_dir.path().join("python3");
+        fs::os::unix::fs::symlink(&real, &link).unwrap();
 
+        assert_eq!(
+            resolve_executable(&link, CanonicalizationPolicy::Never).unwrap(),
+            link
+        );
+        assert_eq!(
+            resolve_executable(&link, CanonicalizationPolicy::Auto).unwrap(),
+            real.canonicalize().unwrap()
+        );
+        assert_eq!(
+            resolve_executable(&link, CanonicalizationPolicy::Always).unwrap(),
+            real.canonicalize().unwrap()
+        );
+
+        // A profile symlink into the Nix store is kept, even if the target does not exist.
+        let profile = temp_dir.path().join("python");
+        fs::os::unix::fs::symlink(
+            "/nix/store/00000000000000000000000000000000-python3-3.12.3/bin/python3",
+            &profile,
+        )
+        .unwrap();
+        assert_eq!(
+            resolve_executable(&profile, CanonicalizationPolicy::Auto).unwrap(),
+            profile
+        );
+    }
+
+    /// The interpreter info reported by a mocked Python 3.12 interpreter.
+    const MOCKED_INFO: &str = indoc! {r##"
+        {
+            "result": "success",
+            "platform": {
+                "os": {
+                    "name": "manylinux",
+                    "major": 2,
+                    "minor": 38
+                },
+                "arch": "x86_64"
+            },
+            "markers": {
+                "implementation_name": "cpython",
+                "implementation_version": "3.12.0",
+                "os_name": "posix",
+                "platform_machine": "x86_64",
+                "platform_python_implementation": "CPython",
+                "platform_release": "6.5.0-13-generic",
+                "platform_system": "Linux",
+                "platform_version": "#13-Ubuntu SMP PREEMPT_DYNAMIC Fri Nov  3 12:16:05 UTC 2023",
+                "python_full_version": "3.12.0",
+                "python_version": "3.12",
+                "sys_platform": "linux"
+            },
+            "sys_base_exec_prefix": "/home/ferris/.pyenv/versions/3.12.0",
+            "sys_base_prefix": "/home/ferris/.pyenv/versions/3.12.0",
+            "sys_prefix": "/home/ferris/projects/uv/.venv",
+            "sys_executable": "/home/ferris/projects/uv/.venv/bin/python",
+            "sys_path": [
+                "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/lib/python3.12",
+                "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages"
+            ],
+            "stdlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12",
+            "scheme": {
+                "data": "/home/ferris/.pyenv/versions/3.12.0",
+                "include": "/home/ferris/.pyenv/versions/3.12.0/include",
+                "platlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages",
+                "purelib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages",
+                "scripts": "/home/ferris/.pyenv/versions/3.12.0/bin"
+            },
+            "virtualenv": {
+                "data": "",
+                "include": "include",
+                "platlib": "lib/python3.12/site-packages",
+                "purelib": "lib/python3.12/site-packages",
+                "scripts": "bin"
+            },
+            "pointer_size": "64",
+            "gil_disabled": true,
+            "build_info": {
+                "build_number": "main",
+                "build_date": "Oct  2 2023 17:51:28",
+                "compiler": "GCC 13.2.0"
+            },
+            "sysconfig_vars": {
+                "ext_suffix": ".cpython-312-x86_64-linux-gnu.so",
+                "soabi": "cpython-312-x86_64-linux-gnu",
+                "abiflags": "t",
+                "multiarch": "x86_64-linux-gnu",
+                "libdir": "/home/ferris/.pyenv/versions/3.12.0/lib",
+                "ldlibrary": "libpython3.12.so",
+                "includepy": "/home/ferris/.pyenv/versions/3.12.0/include/python3.12",
+                "py_enable_shared": true
+            },
+            "user_site": {
+                "site_packages": "/home/ferris/.local/lib/python3.12/site-packages",
+                "scripts": "/home/ferris/.local/bin"
+            }
+        }
+    "##};
+
+    /// Write a mock Python executable at `path` that prints the given interpreter info.
+    fn mock_interpreter(path: &Path, json: &str) {
         fs::write(
-            &mocked_interpreter,
+            path,
             formatdoc! {r##"
             #!/bin/bash
             echo '{json}'
             "##},
         )
         .unwrap();
+        fs::set_permissions(path, std::os::unix::fs::PermissionsExt::from_mode(0o770)).unwrap();
+    }
 
-        fs::set_permissions(
-            &mocked_interpreter,
-            std::os::unix::fs::PermissionsExt::from_mode(0o770),
-        )
-        .unwrap();
+    #[test]
+    fn test_cache_invalidation() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("python");
+        let cache = Cache::temp().unwrap().init().unwrap();
+
+        mock_interpreter(&mocked_interpreter, MOCKED_INFO);
         let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
         assert_eq!(
             interpreter.markers.python_version().version,
             Version::from_str("3.12").unwrap()
         );
+        assert_eq!(
+            interpreter.sysconfig_vars().ext_suffix.as_deref(),
+            Some(".cpython-312-x86_64-linux-gnu.so")
+        );
+        assert!(interpreter.sysconfig_vars().py_enable_shared);
+        assert!(!interpreter.is_statically_linked());
+        assert_eq!(
+            interpreter.build_info().compiler.as_deref(),
+            Some("GCC 13.2.0")
+        );
+        assert!(interpreter.gil_disabled());
+        assert_eq!(interpreter.abiflags(), Some("t"));
+        assert_eq!(interpreter.soabi(), Some("cpython-312-x86_64-linux-gnu"));
+        assert_eq!(
+            interpreter
+                .user_site()
+                .map(|user_site| user_site.scripts.as_path()),
+            Some(Path::new("/home/ferris/.local/bin"))
+        );
+        mock_interpreter(&mocked_interpreter, &MOCKED_INFO.replace("3.12", "3.13"));
+        let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
+        assert_eq!(
+            interpreter.markers.python_version().version,
+            Version::from_str("3.13").unwrap()
+        );
+    }
+
+    #[test]
+    fn record() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("python");
+        let record = mock_dir.path().join("interpreter.json");
+        let json = MOCKED_INFO.replace(
+            "/home/ferris/projects/uv/.venv/bin/python",
+            mocked_interpreter.to_str().unwrap(),
+        );
+        mock_interpreter(&mocked_interpreter, &json);
+
+        assert!(Interpreter::from_record(&mocked_interpreter, &record)
+            .unwrap()
+            .is_none());
+        Interpreter::record(&mocked_interpreter, &record).unwrap();
+
+        // The record is used without running the interpreter.
+        fs::write(&mocked_interpreter, "#!/bin/bash\nexit 1\n").unwrap();
+        let interpreter = Interpreter::from_record(&mocked_interpreter, &record)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            interpreter.python_version(),
+            &Version::from_str("3.12.0").unwrap()
+        );
+        assert_eq!(interpreter.sys_executable(), mocked_interpreter);
+
+        // Records written for another version of the interpreter info are ignored.
+        let contents = fs::read_to_string(&record).unwrap();
         fs::write(
-            &mocked_interpreter,
-            formatdoc! {r##"
-            #!/bin/bash
-            echo '{}'
-            "##, json.replace("3.12", "3.13")},
+            &record,
+            contents.replace(&CacheBucket::Interpreter.to_string(), "interpreter-v0"),
         )
         .unwrap();
+        assert!(Interpreter::from_record(&mocked_interpreter, &record)
+            .unwrap()
+            .is_none());
+        fs::write(&record, contents).unwrap();
+
+        // Records of other executables are ignored, e.g., if the toolchain was moved.
+        let other = mock_dir.path().join("python3");
+        fs::write(&other, "").unwrap();
+        assert!(Interpreter::from_record(&other, &record).unwrap().is_none());
+    }
+
+    #[test]
+    fn executable_signature() {
+        let temp_dir = tempdir().unwrap();
+        let executable = temp_dir.path().join("python");
+
+        // Binaries of the same size that differ at the start, in the middle, or at the end.
+        let mut contents = vec![0u8; 256 * 1024];
+        fs::write(&executable, &contents).unwrap();
+        let signature = ExecutableSignature::from_path(&executable).unwrap();
+        assert_eq!(
+            ExecutableSignature::from_path(&executable).unwrap(),
+            signature
+        );
+
+        contents[0] = 1;
+        fs::write(&executable, &contents).unwrap();
+        let start = ExecutableSignature::from_path(&executable).unwrap();
+        assert_ne!(start, signature);
+
+        contents[256 * 1024 - 1] = 1;
+        fs::write(&executable, &contents).unwrap();
+        let end = ExecutableSignature::from_path(&executable).unwrap();
+        assert_ne!(end, start);
+
+        // Only the start and end of the binary are sampled.
+        contents[128 * 1024] = 1;
+        fs::write(&executable, &contents).unwrap();
+        assert_eq!(ExecutableSignature::from_path(&executable).unwrap(), end);
+
+        contents.push(0);
+        fs::write(&executable, &contents).unwrap();
+        assert_ne!(ExecutableSignature::from_path(&executable).unwrap(), end);
+    }
+
+    #[test]
+    fn externally_managed() {
+        let contents = indoc! {r"
+            [externally-managed]
+            Error=To install Python packages system-wide, try apt install python3-xyz.
+            Error-de=Um Python-Pakete systemweit zu installieren, versuchen Sie apt install python3-xyz.
+        "};
+        let error = |locale| ExternallyManaged::parse(contents.to_string(), locale).into_error();
+        assert_eq!(
+            error(None).as_deref(),
+            Some("To install Python packages system-wide, try apt install python3-xyz.")
+        );
+        assert_eq!(
+            error(Some("de_DE")).as_deref(),
+            Some("Um Python-Pakete systemweit zu installieren, versuchen Sie apt install python3-xyz.")
+        );
+        assert_eq!(error(Some("fr_FR")), error(None));
+
+        // A file without an error message still marks the environment as externally managed.
+        assert_eq!(
+            ExternallyManaged::parse("[externally-managed]\n".to_string(), None).into_error(),
+            None
+        );
+        assert_eq!(
+            ExternallyManaged::parse(String::new(), None).into_error(),
+            None
+        );
+    }
+
+    #[test]
+    fn query_many() {
+        let mock_dir = tempdir().unwrap();
+        let cache = Cache::temp().unwrap().init().unwrap();
+
+        let mut executables = Vec::new();
+        for version in ["3.10", "3.11", "3.12"] {
+            let executable = mock_dir.path().join(format!("python{version}"));
+            mock_interpreter(&executable, &MOCKED_INFO.replace("3.12", version));
+            executables.push(executable);
+        }
+        executables.insert(1, mock_dir.path().join("missing"));
+
+        let results = Interpreter::query_many(&executables, &cache);
+        assert_eq!(results.len(), 4);
+        let versions = results
+            .iter()
+            .map(|result| result.as_ref().ok().map(Interpreter::python_minor))
+            .collect::<Vec<_>>();
+        assert_eq!(versions, vec![Some(10), None, Some(11), Some(12)]);
+    }
+
+    #[test]
+    fn implementation_version() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("pypy3");
+        let cache = Cache::temp().unwrap().init().unwrap();
+        let json = MOCKED_INFO
+            .replace(
+                r#""implementation_name": "cpython""#,
+                r#""implementation_name": "pypy""#,
+            )
+            .replace(
+                r#""implementation_version": "3.12.0""#,
+                r#""implementation_version": "7.3.15""#,
+            );
+        mock_interpreter(&mocked_interpreter, &json);
+
         let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
         assert_eq!(
-            interpreter.markers.python_version().version,
-            Version::from_str("3.13").unwrap()
+            interpreter.implementation_version(),
+            &Version::from_str("7.3.15").unwrap()
+        );
+        assert_eq!(
+            interpreter.python_version(),
+            &Version::from_str("3.12.0").unwrap()
+        );
+        assert_eq!(interpreter.implementation_tuple(), (7, 3));
+        assert_eq!(
+            interpreter.implementation_display(),
+            "PyPy 7.3.15 (Python 3.12.0)"
+        );
+    }
+
+    #[test]
+    fn noisy_startup() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("python");
+        let cache = Cache::temp().unwrap().init().unwrap();
+        mock_interpreter(
+            &mocked_interpreter,
+            &formatdoc! {r#"
+                Hello from sitecustomize! {{"result": "error"}}
+                __UV_INTERPRETER_INFO__{MOCKED_INFO}"#
+            },
+        );
+
+        let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
+        assert_eq!(
+            interpreter.python_version(),
+            &Version::from_str("3.12.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn symlinked_prefix() {
+        let temp_dir = tempdir().unwrap();
+        let base_prefix = temp_dir.path().join("python");
+        let prefix = temp_dir.path().join("link");
+        fs::create_dir(&base_prefix).unwrap();
+        fs::os::unix::fs::symlink(&base_prefix, &prefix).unwrap();
+
+        let mocked_interpreter = temp_dir.path().join("python3");
+        let cache = Cache::temp().unwrap().init().unwrap();
+        let json = MOCKED_INFO
+            .replace(
+                r#""sys_base_prefix": "/home/ferris/.pyenv/versions/3.12.0""#,
+                &format!(r#""sys_base_prefix": "{}""#, base_prefix.display()),
+            )
+            .replace(
+                r#""sys_prefix": "/home/ferris/projects/uv/.venv""#,
+                &format!(r#""sys_prefix": "{}""#, prefix.display()),
+            );
+        mock_interpreter(&mocked_interpreter, &json);
+
+        let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
+        assert_ne!(interpreter.sys_prefix(), interpreter.sys_base_prefix());
+        assert!(!interpreter.is_virtualenv());
+    }
+
+    #[test]
+    fn compatibility() {
+        let mock_dir = tempdir().unwrap();
+        let cache = Cache::temp().unwrap().init().unwrap();
+        let query = |name: &str, json: &str| {
+            let mocked_interpreter = mock_dir.path().join(name);
+            mock_interpreter(&mocked_interpreter, json);
+            Interpreter::query(&mocked_interpreter, &cache).unwrap()
+        };
+
+        let venv = query("venv", MOCKED_INFO);
+        let base = query(
+            "base",
+            &MOCKED_INFO.replace(
+                r#""sys_prefix": "/home/ferris/projects/uv/.venv""#,
+                r#""sys_prefix": "/home/ferris/.pyenv/versions/3.12.0""#,
+            ),
+        );
+        let other_base = query(
+            "other-base",
+            &MOCKED_INFO.replace("/home/ferris/.pyenv", "/opt/pyenv"),
+        );
+        let other_version = query(
+            "other-version",
+            &MOCKED_INFO.replace("3.12", "3.13").replace("312", "313"),
+        );
+        let other_abi = query(
+            "other-abi",
+            &MOCKED_INFO
+                .replace(r#""gil_disabled": true"#, r#""gil_disabled": false"#)
+                .replace(r#""abiflags": "t""#, r#""abiflags": """#),
+        );
+
+        assert!(venv.is_compatible_with(&base));
+        assert!(venv.same_base(&base));
+        assert!(venv.is_compatible_with(&other_base));
+        assert!(!venv.same_base(&other_base));
+        assert!(!venv.is_compatible_with(&other_version));
+        assert!(!venv.same_base(&other_version));
+        assert!(!venv.is_compatible_with(&other_abi));
+    }
+
+    #[test]
+    fn missing_stdlib_modules() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("python3");
+        let cache = Cache::temp().unwrap().init().unwrap();
+        let json = MOCKED_INFO.replace(
+            r#""pointer_size": "64","#,
+            r#""pointer_size": "64", "missing_stdlib_modules": ["ensurepip", "distutils"],"#,
+        );
+        mock_interpreter(&mocked_interpreter, &json);
+
+        let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
+        assert_eq!(
+            interpreter.missing_stdlib_modules(),
+            ["ensurepip".to_string(), "distutils".to_string()]
         );
+        assert!(!interpreter.has_ensurepip());
     }
 }
@@ -1,32 +1,54 @@
 //! Find requested Python interpreters and query interpreters for information.
+use std::path::PathBuf;
+
 use thiserror::Error;
 
+use uv_fs::Simplified;
+
 pub use crate::discovery::{
-    find_best_interpreter, find_default_interpreter, find_interpreter, Error as DiscoveryError,
-    InterpreterNotFound, InterpreterRequest, InterpreterSource, SourceSelector, SystemPython,
-    VersionRequest,
+    find_best_interpreter, find_default_interpreter, find_interpreter,
+    find_interpreter_with_toolchains, ConfirmToolchainFetch, Error as DiscoveryError,
+    InterpreterNotFound, InterpreterRequest, InterpreterSource, MismatchReason, SourceSelector,
+    SystemPython, ToolchainFetch, VersionRequest, VersionRequestError,
+};
+#[cfg(feature = "downloads")]
+pub use crate::discovery::{
+    find_or_fetch_best_interpreter, find_or_fetch_best_interpreter_with_toolchains,
 };
 pub use crate::environment::PythonEnvironment;
-pub use crate::interpreter::Interpreter;
+pub use crate::health::EnvironmentProblem;
+pub use crate::implementation::ImplementationName;
+pub use crate::interpreter::{
+    BuildInfo, CanonicalizationPolicy, Interpreter, QueryRetryPolicy, SysconfigVars, UserSite,
+};
+pub use crate::named::{Error as NamedEnvironmentError, NamedEnvironments};
 pub use crate::pointer_size::PointerSize;
 pub use crate::prefix::Prefix;
+pub use crate::probe::{relative_libraries, BinaryFormat, StaticProbe};
 pub use crate::python_version::PythonVersion;
-pub use crate::target::Target;
-pub use crate::virtualenv::{Error as VirtualEnvError, PyVenvConfiguration, VirtualEnvironment};
+pub use crate::target::{Target, TargetLayout};
+pub use crate::virtualenv::{
+    Error as VirtualEnvError, PyVenvConfiguration, VirtualEnvironment, VirtualEnvironmentProblem,
+};
+pub use crate::warnings::{set_warning_sink, Warning, WarningSink};
 
 mod discovery;
 pub mod downloads;
 mod environment;
+mod health;
 mod implementation;
 mod interpreter;
 pub mod managed;
+mod named;
 pub mod platform;
 mod pointer_size;
 mod prefix;
+mod probe;
 mod py_launcher;
 mod python_version;
 mod target;
 mod virtualenv;
+mod warnings;
 
 #[cfg(not(test))]
 pub(crate) fn current_dir() -> Result<std::path::PathBuf, std::io::Error> {
@@ -57,6 +79,12 @@ pub enum Error {
 
     #[error(transparent)]
     NotFound(#[from] InterpreterNotFound),
+
+    #[error("The interpreter found in the {interpreter_source} at `{}` is not in a virtual environment: its prefix matches its base prefix", prefix.user_display())]
+    NotAVirtualEnv {
+        interpreter_source: InterpreterSource,
+        prefix: PathBuf,
+    },
 }
 
 // The mock interpreters are not valid on Windows so we don't have unit test coverage there
@@ -81,10 +109,12 @@ mod tests {
 
     use crate::{
         discovery::DiscoveredInterpreter, find_best_interpreter, find_default_interpreter,
-        find_interpreter, implementation::ImplementationName, managed::InstalledToolchains,
-        virtualenv::virtualenv_python_executable, Error, InterpreterNotFound, InterpreterRequest,
-        InterpreterSource, PythonEnvironment, PythonVersion, SourceSelector, SystemPython,
-        VersionRequest,
+        find_interpreter, find_interpreter_with_toolchains, implementation::ImplementationName,
+        managed::InstalledToolchains, managed::Toolchain, platform::Platform,
+        virtualenv::virtualenv_python_executable, Error, Interpreter, InterpreterNotFound,
+        InterpreterRequest, InterpreterSource, MismatchReason, PythonEnvironment, PythonVersion,
+        SourceSelector, SystemPython, VersionRequest, VirtualEnvironment,
+        VirtualEnvironmentProblem,
     };
 
     struct TestContext {
@@ -777,6 +807,109 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn find_interpreter_alternatives_prefers_first() -> Result<()> {
+        let mut context = TestContext::new()?;
+        context.add_python_versions(&["3.10.1", "3.11.2", "3.12.3"])?;
+
+        let found = context.run(|| {
+            find_interpreter(
+                &InterpreterRequest::parse("3.12|3.11"),
+                SystemPython::Allowed,
+                &SourceSelector::All(PreviewMode::Disabled),
+                &context.cache,
+            )
+        })??;
+
+        assert_eq!(
+            &found.interpreter().python_full_version().to_string(),
+            "3.12.3",
+            "We should prefer the first alternative even if a later one is found first"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_interpreter_alternatives_fallback() -> Result<()> {
+        let mut context = TestContext::new()?;
+        context.add_python_versions(&["3.10.1", "3.11.2"])?;
+
+        let found = context.run(|| {
+            find_interpreter(
+                &InterpreterRequest::parse("3.12|3.11"),
+                SystemPython::Allowed,
+                &SourceSelector::All(PreviewMode::Disabled),
+                &context.cache,
+            )
+        })??;
+
+        assert_eq!(
+            &found.interpreter().python_full_version().to_string(),
+            "3.11.2",
+            "We should fall back to the next alternative"
+        );
+
+        let result = context.run(|| {
+            find_interpreter(
+                &InterpreterRequest::parse("3.12|3.9"),
+                SystemPython::Allowed,
+                &SourceSelector::All(PreviewMode::Disabled),
+                &context.cache,
+            )
+        })?;
+        assert!(
+            matches!(result, Err(InterpreterNotFound::NoMatchingAlternatives(..))),
+            "We should not find an interpreter; got {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_interpreter_wildcard_prefers_newest() -> Result<()> {
+        let mut context = TestContext::new()?;
+        context.add_python_versions(&["3.10.1", "3.12.3", "3.11.2"])?;
+
+        for request in ["latest", "3.*", "python3.*"] {
+            let found = context.run(|| {
+                find_interpreter(
+                    &InterpreterRequest::parse(request),
+                    SystemPython::Allowed,
+                    &SourceSelector::All(PreviewMode::Disabled),
+                    &context.cache,
+                )
+            })??;
+
+            assert_eq!(
+                &found.interpreter().python_full_version().to_string(),
+                "3.12.3",
+                "We should find the newest interpreter for `{request}`, not the first"
+            );
+        }
+
+        let result = context.run(|| {
+            find_interpreter(
+                &InterpreterRequest::parse("4.*"),
+                SystemPython::Allowed,
+                &SourceSelector::All(PreviewMode::Disabled),
+                &context.cache,
+            )
+        })?;
+        assert!(
+            matches!(
+                result,
+                Err(InterpreterNotFound::NoMatchingVersion(
+                    _,
+                    VersionRequest::MajorWildcard(4)
+                ))
+            ),
+            "We should not find an interpreter; got {result:?}"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn find_best_interpreter_version_patch_exact() -> Result<()> {
         let mut context = TestContext::new()?;
@@ -1049,6 +1182,83 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn find_environment_from_workspace_root() -> Result<()> {
+        let context = TestContext::new()?;
+
+        // The working directory is a workspace member with its own environment
+        context
+            .workdir
+            .child("pyproject.toml")
+            .write_str("[project]\nname = \"member\"\n")?;
+        TestContext::mock_venv(context.workdir.child(".venv"), "3.12.1")?;
+        TestContext::mock_venv(context.tempdir.child(".venv"), "3.12.0")?;
+
+        let environment = context.run(|| {
+            PythonEnvironment::find(
+                None,
+                SystemPython::Allowed,
+                PreviewMode::Disabled,
+                &context.cache,
+            )
+        })?;
+        assert_eq!(
+            environment.interpreter().python_full_version().to_string(),
+            "3.12.1",
+            "We should prefer the closest environment outside of a workspace"
+        );
+
+        // Declare the parent directory as the workspace root
+        context
+            .tempdir
+            .child("pyproject.toml")
+            .write_str(indoc! {r#"
+            [tool.uv.workspace]
+            members = ["workdir"]
+        "#})?;
+        let environment = context.run(|| {
+            PythonEnvironment::find(
+                None,
+                SystemPython::Allowed,
+                PreviewMode::Disabled,
+                &context.cache,
+            )
+        })?;
+        assert_eq!(
+            environment.interpreter().python_full_version().to_string(),
+            "3.12.0",
+            "We should prefer the environment at the workspace root"
+        );
+
+        // Projects that aren't members of the workspace keep their own environment
+        for workspace in [
+            "[tool.uv.workspace]\nmembers = [\"packages/*\"]\n",
+            "[tool.uv.workspace]\nmembers = [\"*\"]\nexclude = [\"workdir\"]\n",
+            "[tool.uv.workspace]\nmembers = [\"*\", \"!workdir\"]\n",
+            "[tool.uv.workspace]\nmembers = [\"*\"]\nexclude = [\"member\"]\n",
+        ] {
+            context
+                .tempdir
+                .child("pyproject.toml")
+                .write_str(workspace)?;
+            let environment = context.run(|| {
+                PythonEnvironment::find(
+                    None,
+                    SystemPython::Allowed,
+                    PreviewMode::Disabled,
+                    &context.cache,
+                )
+            })?;
+            assert_eq!(
+                environment.interpreter().python_full_version().to_string(),
+                "3.12.1",
+                "We should not use the workspace environment for a non-member: {workspace}"
+            );
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn find_environment_skips_broken_active_environment() -> Result<()> {
         let context = TestContext::new()?;
@@ -1641,6 +1851,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn explain_mismatch() -> Result<()> {
+        let context = TestContext::new()?;
+        let python = context.tempdir.child("python");
+        TestContext::create_mock_interpreter(
+            &python,
+            &PythonVersion::from_str("3.8.10").unwrap(),
+            ImplementationName::CPython,
+            true,
+        )?;
+        let interpreter = Interpreter::query(&python, &context.cache)?;
+
+        // The mock interpreter may be built for a different architecture than the test machine.
+        let explain = |request: &str| {
+            InterpreterRequest::parse(request)
+                .explain_mismatch(&interpreter)
+                .into_iter()
+                .filter(|reason| !matches!(reason, MismatchReason::Architecture { .. }))
+                .map(|reason| reason.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(explain("3.8").is_empty());
+        assert!(explain("3.11|3.8").is_empty());
+        assert_eq!(
+            explain("3.11"),
+            vec!["Found Python 3.8.10 but Python 3.11 was requested"]
+        );
+        assert_eq!(
+            explain("pypy3.11"),
+            vec![
+                "Found CPython but PyPy was requested",
+                "Found Python 3.8.10 but Python 3.11 was requested"
+            ]
+        );
+        assert_eq!(
+            explain("3.12|3.11"),
+            vec![
+                "Found Python 3.8.10 but Python 3.12 was requested",
+                "Found Python 3.8.10 but Python 3.11 was requested"
+            ]
+        );
+
+        // The executable is no longer executable.
+        fs_err::set_permissions(&python, std::os::unix::fs::PermissionsExt::from_mode(0o660))?;
+        assert!(InterpreterRequest::parse("3.8")
+            .explain_mismatch(&interpreter)
+            .contains(&MismatchReason::NotExecutable(python.to_path_buf())));
+
+        Ok(())
+    }
+
     #[test]
     fn find_environment_pypy_request_ignores_cpython() -> Result<()> {
         let mut context = TestContext::new()?;
@@ -1852,4 +2114,378 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn find_interpreter_with_toolchain_dir() -> Result<()> {
+        let context = TestContext::new()?;
+
+        // A toolchain in a project-specific directory, not in `UV_TOOLCHAIN_DIR`
+        let toolchains =
+            InstalledToolchains::from_path(context.tempdir.child("toolchains").path())?;
+        let toolchain = Toolchain::new(
+            toolchains
+                .root()
+                .join(format!("cpython-3.12.1-{}", Platform::from_env()?)),
+        )?;
+        TestContext::create_mock_interpreter(
+            &toolchain.executable(),
+            &PythonVersion::from_str("3.12.1").unwrap(),
+            ImplementationName::CPython,
+            true,
+        )?;
+
+        let request = InterpreterRequest::parse("3.12");
+        let sources = SourceSelector::All(PreviewMode::Enabled);
+        let result = context
+            .run(|| find_interpreter(&request, SystemPython::Allowed, &sources, &context.cache))?;
+        assert!(
+            result.is_err(),
+            "The toolchain should not be found in the default directory; got {result:?}"
+        );
+
+        let found = context.run(|| {
+            find_interpreter_with_toolchains(
+                &request,
+                SystemPython::Allowed,
+                &sources,
+                &toolchains,
+                &context.cache,
+            )
+        })??;
+        assert_eq!(found.source(), &InterpreterSource::ManagedToolchain);
+        assert_eq!(found.interpreter().sys_executable(), toolchain.executable());
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_interpreter_with_toolchain_pin() -> Result<()> {
+        let mut context = TestContext::new()?;
+        context.add_python_versions(&["3.12.3"])?;
+        TestContext::mock_venv(context.tempdir.child(".venv"), "3.12.0")?;
+        for version in ["3.12.1", "3.12.2"] {
+            let toolchain = Toolchain::new(
+                context
+                    .toolchains
+                    .root()
+                    .join(format!("cpython-{version}-{}", Platform::from_env()?)),
+            )?;
+            TestContext::create_mock_interpreter(
+                &toolchain.executable(),
+                &PythonVersion::from_str(version).unwrap(),
+                ImplementationName::CPython,
+                true,
+            )?;
+        }
+        context
+            .workdir
+            .child(".python-version")
+            .write_str("cpython-3.12.1\n")?;
+
+        let sources = SourceSelector::All(PreviewMode::Enabled);
+        let found = context.run(|| {
+            find_interpreter(
+                &InterpreterRequest::Any,
+                SystemPython::Allowed,
+                &sources,
+                &context.cache,
+            )
+        })??;
+        assert_eq!(
+            found.source(),
+            &InterpreterSource::ManagedToolchain,
+            "The pinned toolchain should take precedence over the discovered environment"
+        );
+        assert_eq!(
+            found.interpreter().python_full_version().to_string(),
+            "3.12.1"
+        );
+
+        // An activated environment takes precedence over the pin
+        let venv = context.tempdir.child("active");
+        TestContext::mock_venv(&venv, "3.11.1")?;
+        let found = context.run_with_vars(&[("VIRTUAL_ENV", Some(venv.as_os_str()))], || {
+            find_interpreter(
+                &InterpreterRequest::Any,
+                SystemPython::Allowed,
+                &sources,
+                &context.cache,
+            )
+        })??;
+        assert_eq!(found.source(), &InterpreterSource::ActiveEnvironment);
+        assert_eq!(
+            found.interpreter().python_full_version().to_string(),
+            "3.11.1"
+        );
+
+        // As does the interpreter that spawned uv
+        let parent = virtualenv_python_executable(&venv);
+        let found = context.run_with_vars(
+            &[("UV_INTERNAL__PARENT_INTERPRETER", Some(parent.as_os_str()))],
+            || {
+                find_interpreter(
+                    &InterpreterRequest::Any,
+                    SystemPython::Allowed,
+                    &sources,
+                    &context.cache,
+                )
+            },
+        )??;
+        assert_eq!(found.source(), &InterpreterSource::ParentInterpreter);
+
+        // A pin that can't be read is ignored rather than failing discovery
+        fs_err::remove_file(context.workdir.child(".python-version"))?;
+        context.workdir.child(".python-version").create_dir_all()?;
+        let found = context.run(|| {
+            find_interpreter(
+                &InterpreterRequest::Any,
+                SystemPython::Allowed,
+                &sources,
+                &context.cache,
+            )
+        })??;
+        assert_eq!(found.source(), &InterpreterSource::DiscoveredEnvironment);
+        assert_eq!(
+            found.interpreter().python_full_version().to_string(),
+            "3.12.0"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_environment_by_name() -> Result<()> {
+        let mut context = TestContext::new()?;
+        context.add_python_versions(&["3.12.0"])?;
+
+        let environments = context.tempdir.child("envs");
+        TestContext::mock_venv(environments.child("myproj"), "3.11.1")?;
+
+        let environment = context.run_with_vars(
+            &[("UV_ENVIRONMENT_DIR", Some(environments.as_os_str()))],
+            || {
+                PythonEnvironment::find(
+                    Some("env:myproj"),
+                    SystemPython::Allowed,
+                    PreviewMode::Disabled,
+                    &context.cache,
+                )
+            },
+        )?;
+        assert_eq!(
+            environment.interpreter().python_full_version().to_string(),
+            "3.11.1",
+            "We should find the named environment"
+        );
+
+        let result = context.run_with_vars(
+            &[("UV_ENVIRONMENT_DIR", Some(environments.as_os_str()))],
+            || {
+                PythonEnvironment::find(
+                    Some("env:other"),
+                    SystemPython::Allowed,
+                    PreviewMode::Disabled,
+                    &context.cache,
+                )
+            },
+        );
+        assert!(
+            matches!(result, Err(Error::NotFound(..))),
+            "We should not find a missing named environment; got {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_dangling_virtualenv() -> Result<()> {
+        let mut context = TestContext::new()?;
+        context.add_python_versions(&["3.12.1"])?;
+        let python = context.run(|| {
+            find_interpreter(
+                &InterpreterRequest::parse("3.12"),
+                SystemPython::Allowed,
+                &SourceSelector::System(PreviewMode::Disabled),
+                &context.cache,
+            )
+        })??;
+        let python = python.interpreter().sys_executable().to_path_buf();
+
+        // A venv whose base interpreter was deleted, e.g., by upgrading it.
+        let venv = context.tempdir.child(".venv");
+        venv.child("lib/python3.12/site-packages")
+            .create_dir_all()?;
+        venv.child("pyvenv.cfg")
+            .write_str("home = /deleted/bin\nimplementation = CPython\nversion_info = 3.12.0\n")?;
+        venv.child("bin").create_dir_all()?;
+        let target = context.tempdir.child("deleted").child("python3.12");
+        std::os::unix::fs::symlink(target, venv.child("bin").child("python"))?;
+        std::os::unix::fs::symlink("python", venv.child("bin").child("python3"))?;
+
+        let virtualenv = VirtualEnvironment::from_root(venv.path())?;
+        assert!(matches!(
+            virtualenv.validate(&context.cache)?.as_slice(),
+            [VirtualEnvironmentProblem::DanglingExecutable { .. }]
+        ));
+
+        let interpreter = context
+            .run(|| virtualenv.repair(PreviewMode::Disabled, &context.cache))?
+            .expect("The system interpreter should be compatible");
+        assert_eq!(interpreter.sys_executable(), python);
+        assert_eq!(
+            fs_err::read_link(venv.child("bin").child("python"))?,
+            python
+        );
+        assert!(virtualenv.validate(&context.cache)?.is_empty());
+        let cfg = crate::PyVenvConfiguration::parse(venv.child("pyvenv.cfg"))?;
+        assert_eq!(cfg.home(), python.parent());
+        assert_eq!(cfg.version(), Some("3.12.1"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_copied_virtualenv() -> Result<()> {
+        let mut context = TestContext::new()?;
+        let search_path = context.new_search_path_directory("bin")?;
+        let python = search_path.child("python3");
+        TestContext::create_mock_interpreter(
+            &python,
+            &PythonVersion::from_str("3.12.1").unwrap(),
+            ImplementationName::default(),
+            true,
+        )?;
+
+        // The interpreter reports a base executable that loads `libpython` relative to itself,
+        // like python-build-standalone builds.
+        let prefix = context.tempdir.child("python");
+        let base_python = prefix.child("bin").child("python3.12");
+        let executable = crate::probe::tests::elf_with_dynamic(
+            &["libpython3.12.so.1.0", "libc.so.6"],
+            Some("$ORIGIN/../lib"),
+        );
+        base_python.write_binary(&executable)?;
+        prefix
+            .child("lib")
+            .child("libpython3.12.so.1.0")
+            .write_str("libpython")?;
+        let script = fs_err::read_to_string(&python)?.replace(
+            &format!("\"sys_executable\": \"{}\"", python.display()),
+            &format!("\"sys_executable\": \"{}\"", base_python.display()),
+        );
+        python.write_str(&script)?;
+
+        // A venv with copies of a deleted base interpreter that no longer runs.
+        let venv = context.tempdir.child(".venv");
+        venv.child("pyvenv.cfg")
+            .write_str("home = /deleted/bin\nimplementation = CPython\nversion_info = 3.12.0\n")?;
+        for name in ["python", "python3"] {
+            venv.child("bin")
+                .child(name)
+                .write_str("#!/bin/sh\nexit 1\n")?;
+        }
+
+        let virtualenv = VirtualEnvironment::from_root(venv.path())?;
+        let interpreter = context
+            .run(|| virtualenv.repair(PreviewMode::Disabled, &context.cache))?
+            .expect("The system interpreter should be compatible");
+        assert_eq!(interpreter.sys_executable(), base_python.path());
+
+        // The executables are replaced with copies, along with the libraries they load.
+        for name in ["python", "python3"] {
+            let path = venv.child("bin").child(name);
+            assert!(!fs_err::symlink_metadata(&path)?.file_type().is_symlink());
+            assert_eq!(fs_err::read(&path)?, executable);
+        }
+        assert_eq!(
+            fs_err::read_to_string(venv.child("lib").child("libpython3.12.so.1.0"))?,
+            "libpython"
+        );
+        assert!(!venv.child("lib").child("libc.so.6").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "downloads",
+        target_os = "linux",
+        target_arch = "x86_64",
+        target_env = "gnu"
+    ))]
+    fn find_or_fetch_interpreter_with_toolchains() -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::downloads::FetchOptions;
+        use crate::{find_or_fetch_best_interpreter_with_toolchains, ToolchainFetch};
+
+        let mut context = TestContext::new()?;
+        context.add_python_versions(&["3.12.1"])?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        // Serve an archive with a mock interpreter, for a toolchain without a published digest.
+        let key = "cpython-3.10.0-linux-x86_64-gnu";
+        let toolchains =
+            InstalledToolchains::from_path(context.tempdir.child("toolchains").path())?;
+        let executable = toolchains.root().join(key).join("install/bin/python3");
+        TestContext::create_mock_interpreter(
+            &executable,
+            &PythonVersion::from_str("3.10.0").unwrap(),
+            ImplementationName::default(),
+            true,
+        )?;
+        let script = fs_err::read(&executable)?;
+        fs_err::remove_dir_all(toolchains.root())?;
+        let archive = runtime.block_on(async {
+            let mut header = tokio_tar::Header::new_gnu();
+            header.set_size(script.len().try_into()?);
+            header.set_mode(0o755);
+            header.set_cksum();
+            let mut builder = tokio_tar::Builder::new(Vec::new());
+            builder
+                .append_data(&mut header, "python/install/bin/python3", script.as_slice())
+                .await?;
+            let mut encoder = async_compression::tokio::write::ZstdEncoder::new(Vec::new());
+            encoder.write_all(&builder.into_inner().await?).await?;
+            encoder.shutdown().await?;
+            anyhow::Ok(encoder.into_inner())
+        })?;
+        let server = runtime.block_on(MockServer::start());
+        runtime.block_on(
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(archive))
+                .expect(1)
+                .mount(&server),
+        );
+
+        // Without a matching interpreter, the toolchain is installed into the given directory.
+        let client = uv_client::BaseClientBuilder::new().build();
+        let options = FetchOptions::default().with_mirror(server.uri());
+        let found = context.run(|| {
+            runtime.block_on(find_or_fetch_best_interpreter_with_toolchains(
+                &InterpreterRequest::parse("3.10.0"),
+                SystemPython::Allowed,
+                PreviewMode::Enabled,
+                &ToolchainFetch::Always,
+                &client,
+                &options,
+                &toolchains,
+                &context.cache,
+            ))
+        })??;
+        assert_eq!(found.source(), &InterpreterSource::FetchedToolchain);
+        assert_eq!(
+            found.interpreter().python_full_version().to_string(),
+            "3.10.0"
+        );
+        assert!(executable.is_file());
+        assert!(!context.toolchains.root().join(key).exists());
+        runtime.block_on(server.verify());
+
+        Ok(())
+    }
 }
@@ -1,17 +1,82 @@
 use core::fmt;
 use fs_err as fs;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsStr;
+#[cfg(feature = "downloads")]
+use std::io::Read;
 use std::io::{self, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use uv_cache::Cache;
+#[cfg(feature = "downloads")]
+use uv_client::BaseClient;
+use uv_fs::{LockedFile, Simplified};
 use uv_state::{StateBucket, StateStore};
+use walkdir::WalkDir;
 
 // TODO(zanieb): Separate download and managed error types
+use crate::discovery::VersionRequest;
 pub use crate::downloads::Error;
-use crate::platform::{Arch, Libc, Os};
+use crate::downloads::PythonDownload;
+#[cfg(feature = "downloads")]
+use crate::downloads::{DownloadResult, FetchOptions, PythonDownloadRequest, SignaturePolicy};
+use crate::implementation::ImplementationName;
+use crate::interpreter::Interpreter;
+use crate::platform::{Arch, Libc, Os, Platform};
 use crate::python_version::PythonVersion;
+use crate::virtualenv::PyVenvConfiguration;
+
+/// The name of the file that pins a managed toolchain, see [`ToolchainPin`].
+pub const PIN_FILENAME: &str = ".python-version";
+
+/// The script embedded in the trampoline of a shim on Windows, which runs the toolchain's Python
+/// executable with the arguments of the shim.
+const SHIM_SCRIPT: &str =
+    "import subprocess\nimport sys\n\nsys.exit(subprocess.call([sys.executable, *sys.argv[1:]]))\n";
+
+/// The file in a toolchain directory containing its [`ToolchainMetadata`].
+const METADATA_FILENAME: &str = "metadata.json";
+
+/// The file in a toolchain directory containing the interpreter info of its Python executable, see
+/// [`Toolchain::interpreter`].
+const INTERPRETER_FILENAME: &str = "interpreter.json";
+
+/// The file in a toolchain directory listing the SHA256 digest of each of its files.
+const MANIFEST_FILENAME: &str = "manifest.json";
+
+/// The code appended to the `sysconfig` data of a toolchain to replace the build prefix, i.e.,
+/// `/install`, with the location of the toolchain at runtime.
+#[cfg(feature = "downloads")]
+const SYSCONFIG_RELOCATION: &str = r#"
+# Added by uv: replace the build prefix with the location of the installation at runtime.
+def _uv_relocate():
+    import sys
+
+    def relocate(arg):
+        # e.g., `/install/lib`, or a flag like `-I/install/include`
+        for flag in ("", "-I", "-L"):
+            path = arg[len(flag):]
+            if arg.startswith(flag) and (path == "/install" or path.startswith("/install/")):
+                return flag + sys.base_prefix + path[len("/install"):]
+        return arg
+
+    for key, value in build_time_vars.items():
+        if isinstance(value, str):
+            build_time_vars[key] = " ".join(relocate(arg) for arg in value.split(" "))
+
+
+_uv_relocate()
+del _uv_relocate
+"#;
+
+/// The file in a toolchain directory recording when the toolchain was last used.
+const LAST_USED: &str = ".last-used";
 
 /// A collection of installed Python toolchains.
 #[derive(Debug, Clone)]
@@ -101,7 +166,13 @@ impl InstalledToolchains {
         };
         Ok(dirs
             .into_iter()
-            .map(|path| Toolchain::new(path).unwrap())
+            .filter_map(|path| match Toolchain::new(path) {
+                Ok(toolchain) => Some(toolchain),
+                Err(err) => {
+                    debug!("Skipping invalid toolchain directory: {err}");
+                    None
+                }
+            })
             .rev())
     }
 
@@ -111,21 +182,21 @@ impl InstalledToolchains {
     ) -> Result<impl DoubleEndedIterator<Item = Toolchain>, Error> {
         let platform_key = platform_key_from_env()?;
 
-        let iter = InstalledToolchains::from_settings()?
-            .find_all()?
-            .filter(move |toolchain| {
-                toolchain
-                    .path
-                    .file_name()
-                    .map(OsStr::to_string_lossy)
-                    .is_some_and(|filename| filename.ends_with(&platform_key))
-            });
+        let iter = self.find_all()?.filter(move |toolchain| {
+            toolchain
+                .path
+                .file_name()
+                .map(OsStr::to_string_lossy)
+                .is_some_and(|filename| filename.ends_with(&platform_key))
+        });
 
         Ok(iter)
     }
 
     /// Iterate over toolchains that satisfy the given Python version on this platform.
     ///
+    /// Free-threaded toolchains are not included.
+    ///
     /// ## Errors
     ///
     /// - The platform metadata cannot be read
@@ -137,48 +208,230 @@ impl InstalledToolchains {
         Ok(self
             .find_matching_current_platform()?
             .filter(move |toolchain| {
-                toolchain
-                    .path
-                    .file_name()
-                    .map(OsStr::to_string_lossy)
-                    .is_some_and(|filename| filename.starts_with(&format!("cpython-{version}")))
+                !toolchain.free_threaded
+                    && toolchain
+                        .path
+                        .file_name()
+                        .map(OsStr::to_string_lossy)
+                        .is_some_and(|filename| filename.starts_with(&format!("cpython-{version}")))
             }))
     }
 
+    /// Resolve an alias to the newest installed toolchain on this platform that matches it, e.g.,
+    /// `3.12` to the newest `3.12.x` toolchain, or `latest` to the newest toolchain overall.
+    ///
+    /// Full keys, e.g., `cpython-3.12.3-linux-x86_64-gnu`, resolve to the toolchain itself.
+    /// Free-threaded toolchains only match free-threaded aliases, e.g., `3.13t`. Toolchains with
+    /// the same version are ordered by their key.
+    ///
+    /// Returns `None` if no installed toolchain matches the alias.
+    pub fn resolve(&self, alias: &str) -> Result<Option<Toolchain>, Error> {
+        // Only a single path component can name a toolchain, such that an alias like `../x-y`
+        // can't resolve to a directory outside the toolchains directory.
+        let mut components = Path::new(alias).components();
+        if let (Some(Component::Normal(key)), None) = (components.next(), components.next()) {
+            let path = self.root.join(key);
+            if alias.contains('-') && path.is_dir() {
+                return Toolchain::new(path).map(Some);
+            }
+        }
+
+        let request =
+            VersionRequest::from_str(alias).map_err(|_| Error::InvalidAlias(alias.to_string()))?;
+        Ok(self
+            .find_matching_current_platform()?
+            .filter(|toolchain| request.matches_toolchain(toolchain))
+            .max_by(|a, b| {
+                a.python_version
+                    .version()
+                    .cmp(b.python_version.version())
+                    .then_with(|| a.key.cmp(&b.key))
+            }))
+    }
+
+    /// The installed toolchain the interpreter belongs to, if any.
+    pub fn find_for_interpreter(&self, interpreter: &Interpreter) -> Option<Toolchain> {
+        self.find_for_prefix(interpreter.sys_base_prefix())
+    }
+
+    /// The installed toolchain with the given installation prefix, i.e., `<key>/install`, if any.
+    fn find_for_prefix(&self, prefix: &Path) -> Option<Toolchain> {
+        if prefix.file_name() != Some(OsStr::new("install")) {
+            return None;
+        }
+        let path = prefix.parent()?;
+        let root = path.parent()?;
+        if root.canonicalize().ok()? != self.root.canonicalize().ok()? {
+            return None;
+        }
+        Toolchain::new(path.to_path_buf()).ok()
+    }
+
     pub fn root(&self) -> &Path {
         &self.root
     }
 }
 
+/// Metadata about how an installed toolchain was built and where it came from, stored as
+/// `metadata.json` in the toolchain directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolchainMetadata {
+    /// The key of the toolchain, e.g., `cpython-3.12.3-linux-x86_64-gnu`.
+    pub key: String,
+    /// The URL the toolchain archive was downloaded from, e.g., from a mirror. For toolchains
+    /// installed from a local archive, the URL of the release.
+    pub url: String,
+    /// The expected SHA256 digest of the toolchain archive, if known.
+    pub sha256: Option<String>,
+    /// The date of the python-build-standalone release, e.g., `20240415`.
+    pub build_date: Option<String>,
+    /// The libc the toolchain was built against, e.g., `gnu`.
+    pub libc: String,
+    /// Whether the toolchain includes the `tkinter` module.
+    pub tkinter: bool,
+    /// The options the toolchain was built with, e.g., `pgo+lto`.
+    pub build_options: Option<String>,
+}
+
+impl ToolchainMetadata {
+    /// Collect the metadata for a download from the given URL, extracted into the given directory.
+    #[cfg(feature = "downloads")]
+    pub(crate) fn from_download(download: &PythonDownload, url: &str, dir: &Path) -> Self {
+        let filename = download.archive_filename();
+
+        // e.g., `cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-pgo+lto-full.tar.zst`
+        let build_date = filename
+            .split_once('+')
+            .and_then(|(_, rest)| rest.split('-').next())
+            .filter(|date| !date.is_empty() && date.bytes().all(|byte| byte.is_ascii_digit()))
+            .map(ToString::to_string);
+
+        // Prefer the build options recorded by python-build-standalone, falling back to the flavor
+        // in the archive filename.
+        let build_options = fs::read(dir.join("PYTHON.json"))
+            .ok()
+            .and_then(|contents| serde_json::from_slice::<serde_json::Value>(&contents).ok())
+            .and_then(|json| json.get("build_options")?.as_str().map(ToString::to_string))
+            .or_else(|| {
+                filename
+                    .split(".tar.")
+                    .next()
+                    .map(|stem| stem.trim_end_matches("-full"))
+                    .and_then(|stem| stem.rsplit('-').next())
+                    .map(ToString::to_string)
+            });
+
+        let version = download.python_version();
+        let stdlib = if download.platform().os == Os::Windows {
+            dir.join("install").join("Lib")
+        } else {
+            dir.join("install").join("lib").join(format!(
+                "python{}.{}{}",
+                version.major(),
+                version.minor(),
+                if download.free_threaded() { "t" } else { "" }
+            ))
+        };
+
+        Self {
+            key: download.key().to_string(),
+            url: url.to_string(),
+            sha256: download.sha256().map(ToString::to_string),
+            build_date,
+            libc: download.platform().libc.to_string(),
+            tkinter: stdlib.join("tkinter").is_dir(),
+            build_options,
+        }
+    }
+
+    /// Write the metadata into the given toolchain directory.
+    #[cfg(feature = "downloads")]
+    pub(crate) fn write(&self, dir: &Path) -> Result<(), io::Error> {
+        fs::write(
+            dir.join(METADATA_FILENAME),
+            serde_json::to_vec_pretty(self)?,
+        )
+    }
+}
+
+/// The handling of existing files when creating a shim with [`Toolchain::create_shim`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShimCollision {
+    /// Fail if the file is not a shim for a managed toolchain.
+    #[default]
+    Fail,
+    /// Replace the file.
+    Replace,
+}
+
 /// An installed Python toolchain.
+///
+/// The metadata of the toolchain is derived from the name of its directory, i.e., its key, e.g.,
+/// `cpython-3.12.3-linux-x86_64-gnu`. Free-threaded toolchains have a `t` suffix on the version,
+/// e.g., `cpython-3.13.0t-linux-x86_64-gnu`.
 #[derive(Debug, Clone)]
 pub struct Toolchain {
     /// The path to the top-level directory of the installed toolchain.
     path: PathBuf,
+    /// The full build key, e.g., `cpython-3.12.3-linux-x86_64-gnu`.
+    key: String,
+    implementation: ImplementationName,
     python_version: PythonVersion,
+    /// Whether the toolchain is a free-threaded build, i.e., with the GIL disabled.
+    free_threaded: bool,
+    platform: Platform,
 }
 
 impl Toolchain {
+    /// Read an installed toolchain from its directory.
+    ///
+    /// The directory name must be a key of the form `{implementation}-{version}-{os}-{arch}-{libc}`.
     pub fn new(path: PathBuf) -> Result<Self, Error> {
-        let python_version = PythonVersion::from_str(
-            path.file_name()
-                .ok_or(Error::NameError("No directory name".to_string()))?
-                .to_str()
-                .ok_or(Error::NameError("Name not a valid string".to_string()))?
-                .split('-')
-                .nth(1)
-                .ok_or(Error::NameError(
-                    "Not enough `-`-separated values".to_string(),
-                ))?,
-        )
-        .map_err(|err| Error::NameError(format!("Name has invalid Python version: {err}")))?;
+        let key = path
+            .file_name()
+            .ok_or(Error::NameError("No directory name".to_string()))?
+            .to_str()
+            .ok_or(Error::NameError("Name not a valid string".to_string()))?
+            .to_string();
+
+        let [implementation, version, os, arch, libc] = key.split('-').collect::<Vec<_>>()[..]
+        else {
+            return Err(Error::NameError(format!(
+                "Expected five `-`-separated values in `{key}`"
+            )));
+        };
+
+        let (version, free_threaded) = match version.strip_suffix('t') {
+            Some(version) => (version, true),
+            None => (version, false),
+        };
+        let implementation = ImplementationName::from_str(implementation)
+            .map_err(|err| Error::NameError(format!("Name has invalid implementation: {err}")))?;
+        let python_version = PythonVersion::from_str(version)
+            .map_err(|err| Error::NameError(format!("Name has invalid Python version: {err}")))?;
+        let platform = Platform::new(
+            Os::from_str(os).map_err(|err| Error::NameError(format!("Name has invalid {err}")))?,
+            Arch::from_str(arch)
+                .map_err(|err| Error::NameError(format!("Name has invalid {err}")))?,
+            Libc::from_str(libc)
+                .map_err(|err| Error::NameError(format!("Name has invalid {err}")))?,
+        );
 
         Ok(Self {
             path,
+            key,
+            implementation,
             python_version,
+            free_threaded,
+            platform,
         })
     }
 
+    /// The path to the top-level directory of the toolchain.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn executable(&self) -> PathBuf {
         if cfg!(windows) {
             self.path.join("install").join("python.exe")
@@ -189,17 +442,987 @@ impl Toolchain {
         }
     }
 
+    /// The full build key of the toolchain, e.g., `cpython-3.12.3-linux-x86_64-gnu`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn implementation(&self) -> ImplementationName {
+        self.implementation
+    }
+
     pub fn python_version(&self) -> &PythonVersion {
         &self.python_version
     }
+
+    /// Whether the toolchain is a free-threaded build, i.e., with the GIL disabled.
+    pub fn free_threaded(&self) -> bool {
+        self.free_threaded
+    }
+
+    /// The platform the toolchain was built for, e.g., `linux-x86_64-gnu`.
+    pub fn platform(&self) -> &Platform {
+        &self.platform
+    }
+
+    pub fn libc(&self) -> Libc {
+        self.platform.libc
+    }
+
+    /// The URL the toolchain was downloaded from, if it is a known download.
+    pub fn source_url(&self) -> Option<&str> {
+        PythonDownload::from_key(&self.key).map(PythonDownload::url)
+    }
+
+    /// The time at which the toolchain was installed.
+    ///
+    /// Falls back to the modification time of the toolchain directory on platforms that do not
+    /// record the creation time.
+    pub fn installed_at(&self) -> Result<SystemTime, io::Error> {
+        let metadata = fs::metadata(&self.path)?;
+        metadata.created().or_else(|_| metadata.modified())
+    }
+
+    /// Record that the toolchain was used, e.g., when a virtual environment was created from it.
+    pub fn record_use(&self) -> Result<(), io::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        fs::write(self.path.join(LAST_USED), now.to_string())
+    }
+
+    /// The time at which the toolchain was last used.
+    ///
+    /// Falls back to the installation time if no use of the toolchain was recorded.
+    pub fn last_used(&self) -> Result<SystemTime, io::Error> {
+        match fs::read_to_string(self.path.join(LAST_USED)) {
+            Ok(contents) => match contents.trim().parse::<u64>() {
+                Ok(seconds) => return Ok(UNIX_EPOCH + Duration::from_secs(seconds)),
+                Err(err) => debug!("Ignoring invalid last use of toolchain `{self}`: {err}"),
+            },
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        self.installed_at()
+    }
+
+    /// Whether a newer patch release of the same minor version is among the given toolchains.
+    fn is_superseded_by<'a>(&self, toolchains: impl IntoIterator<Item = &'a Toolchain>) -> bool {
+        toolchains.into_iter().any(|other| {
+            other.implementation == self.implementation
+                && other.free_threaded == self.free_threaded
+                && other.platform == self.platform
+                && other.python_version.major() == self.python_version.major()
+                && other.python_version.minor() == self.python_version.minor()
+                && other.python_version.version() > self.python_version.version()
+        })
+    }
+
+    /// The name of the shim for the toolchain, e.g., `python3.12`, or `python3.13t` for
+    /// free-threaded toolchains.
+    pub fn shim_name(&self) -> String {
+        format!(
+            "python{}.{}{}{}",
+            self.python_version.major(),
+            self.python_version.minor(),
+            if self.free_threaded { "t" } else { "" },
+            std::env::consts::EXE_SUFFIX
+        )
+    }
+
+    /// Create a shim for the toolchain in the given directory, e.g., `~/.local/bin/python3.12`, so
+    /// that the toolchain can be used outside of uv.
+    ///
+    /// On Unix, the shim is a symbolic link to the toolchain's executable. On Windows, it is a
+    /// trampoline executable that launches the toolchain's executable.
+    ///
+    /// Existing shims for managed toolchains in the same toolchain directory, e.g., for an older
+    /// patch release, are replaced. Other existing files are only replaced with
+    /// [`ShimCollision::Replace`].
+    pub fn create_shim(&self, bin_dir: &Path, collision: ShimCollision) -> Result<PathBuf, Error> {
+        let shim = bin_dir.join(self.shim_name());
+        if collision == ShimCollision::Fail {
+            match fs::symlink_metadata(&shim) {
+                Ok(_) => {
+                    let root = self.path.parent().unwrap_or(&self.path);
+                    if !shim_target(&shim)
+                        .is_some_and(|target| target.starts_with(root.simplified()))
+                    {
+                        return Err(Error::ShimCollision(shim));
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(Error::ShimError { path: shim, err }),
+            }
+        }
+
+        let result = fs::create_dir_all(bin_dir).and_then(|()| {
+            if cfg!(windows) {
+                let launcher = install_wheel_rs::windows_script_launcher(
+                    SHIM_SCRIPT,
+                    false,
+                    self.executable(),
+                )
+                .map_err(io::Error::other)?;
+                uv_fs::write_atomic_sync(&shim, launcher)
+            } else {
+                uv_fs::replace_symlink(self.executable(), &shim)
+            }
+        });
+        result.map_err(|err| Error::ShimError {
+            path: shim.clone(),
+            err,
+        })?;
+
+        debug!(
+            "Created shim for toolchain `{self}` at `{}`",
+            shim.user_display()
+        );
+        Ok(shim)
+    }
+
+    /// Read the metadata recorded when the toolchain was installed.
+    ///
+    /// Returns `None` for toolchains installed without metadata, e.g., by older versions of uv.
+    pub fn metadata(&self) -> Result<Option<ToolchainMetadata>, Error> {
+        let path = self.path.join(METADATA_FILENAME);
+        let contents = match fs::read(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let metadata =
+            serde_json::from_slice(&contents).map_err(|err| Error::MetadataError { path, err })?;
+        Ok(Some(metadata))
+    }
+
+    /// Query the Python executable of the toolchain and record its interpreter info, such that
+    /// [`Toolchain::interpreter`] doesn't need to run it.
+    pub fn record_interpreter(&self) -> Result<(), crate::interpreter::Error> {
+        Interpreter::record(&self.executable(), &self.path.join(INTERPRETER_FILENAME))
+    }
+
+    /// The interpreter of the toolchain.
+    ///
+    /// Uses the interpreter info recorded when the toolchain was installed, if any, falling back
+    /// to querying the Python executable, e.g., for toolchains installed by older versions of uv.
+    pub fn interpreter(&self, cache: &Cache) -> Result<Interpreter, crate::interpreter::Error> {
+        query_interpreter(&self.executable(), cache)
+    }
+
+    /// The total size of the files in the toolchain, in bytes.
+    ///
+    /// Symbolic links are not followed.
+    pub fn size(&self) -> Result<u64, io::Error> {
+        let mut size = 0;
+        for entry in WalkDir::new(&self.path) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                size += entry.metadata()?.len();
+            }
+        }
+        Ok(size)
+    }
+}
+
+/// A managed toolchain pinned by a `.python-version` file, e.g., in the project root.
+///
+/// The file contains the key of the toolchain, e.g., `cpython-3.12.3-linux-x86_64-gnu`. The
+/// platform may be omitted, e.g., `cpython-3.12.3`, to pin the same toolchain on every platform.
+/// Files that only contain a version, e.g., `3.12` as used by pyenv, do not pin a toolchain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainPin {
+    /// The full key of the pinned toolchain.
+    key: String,
+    /// The path to the pin file.
+    path: PathBuf,
+}
+
+impl ToolchainPin {
+    /// Read the pin file in the given directory or, if there is none, its closest ancestor.
+    ///
+    /// Returns `None` if there is no pin file, or if the closest pin file does not pin a toolchain.
+    pub fn find(dir: &Path) -> Result<Option<Self>, Error> {
+        for dir in dir.ancestors() {
+            let path = dir.join(PIN_FILENAME);
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            return Ok(Self::parse(&contents, path, &Platform::from_env()?));
+        }
+        Ok(None)
+    }
+
+    /// Parse the contents of a pin file, completing the key with the given platform if omitted.
+    fn parse(contents: &str, path: PathBuf, platform: &Platform) -> Option<Self> {
+        // Use the first entry, skipping comments.
+        let entry = contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))?;
+        let key = match entry.split('-').count() {
+            5 => entry.to_string(),
+            2 => format!("{entry}-{platform}"),
+            _ => {
+                debug!(
+                    "Ignoring `{}`, which does not pin a managed toolchain",
+                    path.user_display()
+                );
+                return None;
+            }
+        };
+        if let Err(err) = Toolchain::new(PathBuf::from(&key)) {
+            debug!("Ignoring invalid pin in `{}`: {err}", path.user_display());
+            return None;
+        }
+        Some(Self { key, path })
+    }
+
+    /// The key of the pinned toolchain, e.g., `cpython-3.12.3-linux-x86_64-gnu`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The path to the pin file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// List the managed toolchains that can be installed on the given platform.
+///
+/// The catalogue of downloads is embedded at build time, so this does not require network access.
+/// Downloads are ordered from the newest to the oldest Python version.
+pub fn available_downloads(
+    platform: &Platform,
+) -> impl Iterator<Item = &'static PythonDownload> + '_ {
+    PythonDownload::iter_all().filter(move |download| download.platform() == *platform)
+}
+
+/// Find a newer patch release of an installed toolchain, e.g., 3.12.5 for 3.12.3.
+///
+/// Only downloads of the same implementation, minor version, build variant, i.e., free-threaded or
+/// not, and platform are considered. Returns
+/// the newest such download, if it is newer than the installed toolchain.
+pub fn find_upgrade(toolchain: &Toolchain) -> Option<&'static PythonDownload> {
+    let installed = toolchain.python_version();
+    available_downloads(toolchain.platform())
+        .filter(|download| {
+            download.implementation() == toolchain.implementation()
+                && download.free_threaded() == toolchain.free_threaded()
+        })
+        .map(|download| (download, download.python_version()))
+        .filter(|(_, version)| {
+            version.major() == installed.major() && version.minor() == installed.minor()
+        })
+        .max_by(|(_, a), (_, b)| a.version().cmp(b.version()))
+        .filter(|(_, version)| version.version() > installed.version())
+        .map(|(download, _)| download)
+}
+
+/// A managed toolchain that was upgraded to a newer patch release.
+#[derive(Debug, Clone)]
+pub struct ToolchainUpgrade {
+    /// The previously installed toolchain.
+    pub previous: Toolchain,
+    /// The newly installed toolchain.
+    pub upgraded: Toolchain,
+}
+
+impl ToolchainUpgrade {
+    /// Return the virtual environments, of those given, that are based on the previous toolchain.
+    ///
+    /// The previous toolchain is left in place, so these environments remain functional, but they
+    /// must be recreated to use the upgraded toolchain.
+    pub fn stale_environments<'a>(
+        &self,
+        environments: impl IntoIterator<Item = &'a Path>,
+    ) -> Vec<&'a Path> {
+        environments
+            .into_iter()
+            .filter(
+                |root| match PyVenvConfiguration::parse(root.join("pyvenv.cfg")) {
+                    Ok(cfg) => cfg
+                        .home()
+                        .is_some_and(|home| home.starts_with(self.previous.path())),
+                    Err(err) => {
+                        debug!(
+                            "Skipping virtual environment `{}`: {err}",
+                            root.user_display()
+                        );
+                        false
+                    }
+                },
+            )
+            .collect()
+    }
+}
+
+/// Read the executable that a shim launches, if it was created by [`Toolchain::create_shim`].
+fn shim_target(shim: &Path) -> Option<PathBuf> {
+    if cfg!(windows) {
+        // The trampoline ends with the path of the executable, its length, and a magic number.
+        let bytes = fs::read(shim).ok()?;
+        let (rest, magic) = bytes.split_at(bytes.len().checked_sub(4)?);
+        if magic != b"UVUV" {
+            return None;
+        }
+        let (rest, length) = rest.split_at(rest.len().checked_sub(4)?);
+        let length = usize::try_from(u32::from_le_bytes(length.try_into().ok()?)).ok()?;
+        let path = rest.get(rest.len().checked_sub(length)?..)?;
+        Some(PathBuf::from(std::str::from_utf8(path).ok()?))
+    } else {
+        fs::read_link(shim).ok()
+    }
+}
+
+/// A policy for removing installed toolchains with [`prune`].
+///
+/// A toolchain is removed if it matches any of the enabled criteria. By default, no toolchains
+/// are removed.
+#[derive(Debug, Clone, Default)]
+pub struct PrunePolicy {
+    /// Remove toolchains that have not been used for this long.
+    unused_for: Option<Duration>,
+    /// Remove toolchains for which a newer patch release is installed.
+    superseded: bool,
+}
+
+impl PrunePolicy {
+    /// Remove toolchains that have not been used for the given duration, e.g., 30 days.
+    #[must_use]
+    pub fn with_unused_for(mut self, unused_for: Duration) -> Self {
+        self.unused_for = Some(unused_for);
+        self
+    }
+
+    /// Remove toolchains for which a newer patch release of the same minor version is installed,
+    /// e.g., 3.12.3 if 3.12.5 is installed.
+    ///
+    /// Virtual environments created from a removed toolchain will no longer be functional.
+    #[must_use]
+    pub fn with_superseded(mut self, superseded: bool) -> Self {
+        self.superseded = superseded;
+        self
+    }
+
+    /// Whether the toolchain should be removed, given all installed toolchains.
+    fn should_remove(
+        &self,
+        toolchain: &Toolchain,
+        installed: &[Toolchain],
+        now: SystemTime,
+    ) -> Result<bool, io::Error> {
+        if self.superseded && toolchain.is_superseded_by(installed) {
+            debug!("Toolchain `{toolchain}` is superseded by a newer patch release");
+            return Ok(true);
+        }
+        if let Some(unused_for) = self.unused_for {
+            let last_used = toolchain.last_used()?;
+            if now.duration_since(last_used).unwrap_or_default() >= unused_for {
+                debug!("Toolchain `{toolchain}` has not been used recently");
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// The result of [`prune`].
+#[derive(Debug, Default)]
+pub struct PruneSummary {
+    /// The toolchains that were removed.
+    pub removed: Vec<Toolchain>,
+    /// The disk space that was reclaimed, in bytes.
+    pub reclaimed: u64,
+}
+
+/// Remove the installed toolchains selected by the [`PrunePolicy`].
+pub fn prune(
+    toolchains: &InstalledToolchains,
+    policy: &PrunePolicy,
+) -> Result<PruneSummary, Error> {
+    let installed: Vec<_> = toolchains.find_all()?.collect();
+    let now = SystemTime::now();
+
+    let mut summary = PruneSummary::default();
+    for toolchain in &installed {
+        if !policy.should_remove(toolchain, &installed, now)? {
+            continue;
+        }
+        // Wait for any concurrent installation of the toolchain to finish, and skip it if it was
+        // removed by a concurrent prune in the meantime.
+        let _lock = LockedFile::acquire(lock_path(toolchains.root(), toolchain.key()), toolchain)
+            .map_err(|err| Error::RemoveError {
+            dir: toolchain.path().to_path_buf(),
+            err,
+        })?;
+        if !toolchain.path().is_dir() {
+            continue;
+        }
+        let size = toolchain.size().unwrap_or_else(|err| {
+            debug!("Failed to determine size of toolchain `{toolchain}`: {err}");
+            0
+        });
+        fs::remove_dir_all(toolchain.path()).map_err(|err| Error::RemoveError {
+            dir: toolchain.path().to_path_buf(),
+            err,
+        })?;
+        debug!("Removed toolchain `{toolchain}`");
+        summary.removed.push(toolchain.clone());
+        summary.reclaimed += size;
+    }
+    Ok(summary)
+}
+
+/// The path of the lock that serializes the installation and removal of the toolchain `key` in
+/// the toolchain directory `root`.
+pub(crate) fn lock_path(root: &Path, key: &str) -> PathBuf {
+    root.join(format!(".{key}.lock"))
+}
+
+/// The disk usage of the installed toolchains, see [`disk_usage`].
+#[derive(Debug, Default)]
+pub struct DiskUsage {
+    /// Each installed toolchain and its size, in bytes.
+    pub toolchains: Vec<(Toolchain, u64)>,
+    /// The size of the files in the toolchain directory that don't belong to a toolchain, e.g.,
+    /// partial downloads, in bytes.
+    pub other: u64,
+    /// The total size of the toolchain directory, in bytes.
+    pub total: u64,
+}
+
+/// Compute the disk usage of each installed toolchain and of the toolchain directory as a whole.
+///
+/// Toolchains are sorted descending by key. Symbolic links are not followed.
+pub fn disk_usage(toolchains: &InstalledToolchains) -> Result<DiskUsage, Error> {
+    let mut usage = DiskUsage::default();
+    for toolchain in toolchains.find_all()? {
+        let size = toolchain.size().map_err(|err| Error::ReadError {
+            dir: toolchain.path().to_path_buf(),
+            err,
+        })?;
+        usage.total += size;
+        usage.toolchains.push((toolchain, size));
+    }
+
+    // Account for the remaining files, e.g., partial downloads and invalid toolchain directories.
+    let toolchain_dirs: BTreeSet<_> = usage
+        .toolchains
+        .iter()
+        .map(|(toolchain, _)| toolchain.path().to_path_buf())
+        .collect();
+    let walker = WalkDir::new(toolchains.root())
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| !toolchain_dirs.contains(entry.path()));
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err)
+                if err
+                    .io_error()
+                    .is_some_and(|err| err.kind() == io::ErrorKind::NotFound) =>
+            {
+                continue
+            }
+            Err(err) => {
+                return Err(Error::ReadError {
+                    dir: toolchains.root().to_path_buf(),
+                    err: err.into(),
+                })
+            }
+        };
+        if entry.file_type().is_file() {
+            usage.other += entry
+                .metadata()
+                .map_err(|err| Error::ReadError {
+                    dir: toolchains.root().to_path_buf(),
+                    err: err.into(),
+                })?
+                .len();
+        }
+    }
+    usage.total += usage.other;
+    Ok(usage)
+}
+
+/// Callbacks for reporting the progress of toolchain installations, e.g., to render progress bars.
+///
+/// See [`FetchOptions::with_reporter`](crate::downloads::FetchOptions::with_reporter).
+pub trait Reporter: Send + Sync {
+    /// Callback to invoke when a download is kicked off.
+    fn on_download_start(&self, download: &PythonDownload, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a download makes progress (i.e. some number of bytes are
+    /// downloaded).
+    fn on_download_progress(&self, id: usize, inc: u64);
+
+    /// Callback to invoke when a download is complete.
+    fn on_download_complete(&self, download: &PythonDownload, id: usize);
+
+    /// Callback to invoke when the verification of an archive, i.e., of its checksum and
+    /// signature, is kicked off.
+    fn on_verify_start(&self, download: &PythonDownload) -> usize;
+
+    /// Callback to invoke when the verification of an archive is complete.
+    fn on_verify_complete(&self, download: &PythonDownload, id: usize);
+
+    /// Callback to invoke when the extraction of an archive of the given size is kicked off.
+    fn on_extract_start(&self, download: &PythonDownload, size: u64) -> usize;
+
+    /// Callback to invoke when an extraction makes progress (i.e. some number of bytes of the
+    /// archive are extracted).
+    fn on_extract_progress(&self, id: usize, inc: u64);
+
+    /// Callback to invoke when an extraction is complete.
+    fn on_extract_complete(&self, download: &PythonDownload, id: usize);
+}
+
+/// The result of [`verify`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Verification {
+    /// The files that were modified since the toolchain was installed, relative to its directory.
+    pub modified: Vec<PathBuf>,
+    /// The files that were removed since the toolchain was installed, relative to its directory.
+    pub missing: Vec<PathBuf>,
+}
+
+impl Verification {
+    /// Whether all files of the toolchain are unchanged.
+    pub fn is_intact(&self) -> bool {
+        self.modified.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Verify the files of an installed toolchain against the manifest recorded when it was installed,
+/// e.g., to detect damage from disk errors or files quarantined by antivirus software.
+///
+/// Files added since the toolchain was installed, e.g., bytecode caches, are ignored.
+pub fn verify(toolchains: &InstalledToolchains, key: &str) -> Result<Verification, Error> {
+    let path = toolchains.root().join(key);
+    if !path.is_dir() {
+        return Err(Error::NotInstalled(key.to_string()));
+    }
+
+    let manifest_path = path.join(MANIFEST_FILENAME);
+    let expected: BTreeMap<String, String> = match fs::read(&manifest_path) {
+        Ok(contents) => serde_json::from_slice(&contents).map_err(|err| Error::MetadataError {
+            path: manifest_path,
+            err,
+        })?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::MissingManifest(path))
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let actual = hash_files(&path)?;
+
+    let mut verification = Verification::default();
+    for (file, digest) in expected {
+        match actual.get(&file) {
+            Some(actual) if *actual == digest => {}
+            Some(_) => verification.modified.push(PathBuf::from(file)),
+            None => verification.missing.push(PathBuf::from(file)),
+        }
+    }
+    if !verification.is_intact() {
+        debug!(
+            "Toolchain `{key}` has {} modified and {} missing files",
+            verification.modified.len(),
+            verification.missing.len()
+        );
+    }
+    Ok(verification)
+}
+
+/// Record the SHA256 digest of each file in the toolchain directory, see [`verify`].
+#[cfg(feature = "downloads")]
+pub(crate) fn write_manifest(dir: &Path) -> Result<(), io::Error> {
+    let manifest = hash_files(dir)?;
+    fs::write(
+        dir.join(MANIFEST_FILENAME),
+        serde_json::to_vec_pretty(&manifest)?,
+    )
+}
+
+/// Compute the SHA256 digest of each file in the toolchain directory, keyed by its relative path
+/// with `/` separators.
+///
+/// Symbolic links and the files maintained by uv, e.g., the manifest itself, are skipped.
+fn hash_files(dir: &Path) -> Result<BTreeMap<String, String>, io::Error> {
+    let mut digests = BTreeMap::new();
+    for entry in WalkDir::new(dir) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .expect("walkdir starts with root");
+        if [
+            MANIFEST_FILENAME,
+            METADATA_FILENAME,
+            INTERPRETER_FILENAME,
+            LAST_USED,
+        ]
+        .iter()
+        .any(|name| relative == Path::new(name))
+        {
+            continue;
+        }
+
+        let mut hasher = Sha256::new();
+        io::copy(&mut fs::File::open(entry.path())?, &mut hasher)?;
+        let key = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .join("/");
+        digests.insert(key, format!("{:x}", hasher.finalize()));
+    }
+    Ok(digests)
+}
+
+/// Patch an extracted toolchain such that it keeps working when its directory is moved, e.g., when
+/// the toolchain directory is mounted at a different path.
+///
+/// python-build-standalone records its build prefix, `/install`, in the shebangs of the bundled
+/// scripts, e.g., `pip3`, and in the `sysconfig` data used to build extension modules. The shebangs
+/// are replaced with a `/bin/sh` preamble that locates the interpreter next to the script, and the
+/// `sysconfig` data resolves the prefix when it's imported. On Windows, neither is needed.
+///
+/// Patching is idempotent.
+#[cfg(feature = "downloads")]
+pub(crate) fn make_relocatable(dir: &Path) -> Result<(), io::Error> {
+    if cfg!(windows) {
+        return Ok(());
+    }
+
+    let install = dir.join("install");
+    match fs::read_dir(install.join("bin")) {
+        Ok(entries) => {
+            for entry in entries {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    relocate_shebang(&entry.path())?;
+                }
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    // e.g., `install/lib/python3.12/_sysconfigdata__linux_x86_64-linux-gnu.py`
+    let Ok(entries) = fs::read_dir(install.join("lib")) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let stdlib = entry?.path();
+        if !stdlib
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|name| name.starts_with("python"))
+            || !stdlib.is_dir()
+        {
+            continue;
+        }
+        for entry in fs::read_dir(&stdlib)? {
+            let path = entry?.path();
+            let Some(stem) = path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .and_then(|name| name.strip_suffix(".py"))
+                .filter(|stem| stem.starts_with("_sysconfigdata_"))
+            else {
+                continue;
+            };
+            relocate_sysconfig(&path, stem)?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace an absolute shebang to a Python interpreter, e.g., `#!/install/bin/python3.12`, with a
+/// `/bin/sh` preamble that runs the interpreter of the same name next to the (resolved) script.
+#[cfg(feature = "downloads")]
+fn relocate_shebang(path: &Path) -> Result<(), io::Error> {
+    let mut magic = [0; 2];
+    if fs::File::open(path)?.read_exact(&mut magic).is_err() || magic != *b"#!" {
+        return Ok(());
+    }
+
+    let contents = fs::read(path)?;
+    let end = contents
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .unwrap_or(contents.len());
+    let Ok(shebang) = std::str::from_utf8(&contents[2..end]) else {
+        return Ok(());
+    };
+    // Skip other interpreters, e.g., `/bin/sh`, and shebangs with arguments.
+    let interpreter = Path::new(shebang.trim());
+    let Some(name) = interpreter
+        .file_name()
+        .and_then(OsStr::to_str)
+        .filter(|name| interpreter.is_absolute() && name.starts_with("python"))
+    else {
+        return Ok(());
+    };
+
+    // Resolve symlinks to the script itself, e.g., `~/.local/bin/pip3`, before looking for the
+    // interpreter next to it.
+    let mut patched = format!(
+        r#"#!/bin/sh
+'''true'
+script="$0"
+while [ -L "$script" ]; do
+    target="$(readlink -- "$script")"
+    case "$target" in
+        /*) script="$target" ;;
+        *) script="$(dirname -- "$script")/$target" ;;
+    esac
+done
+exec "$(cd -- "$(dirname -- "$script")" && pwd -P)"/'{name}' "$0" "$@"
+' '''"#
+    )
+    .into_bytes();
+    patched.extend_from_slice(&contents[end..]);
+    fs::write(path, patched)
+}
+
+/// Append [`SYSCONFIG_RELOCATION`] to the `sysconfig` data module at `path`, removing its stale
+/// bytecode.
+#[cfg(feature = "downloads")]
+fn relocate_sysconfig(path: &Path, stem: &str) -> Result<(), io::Error> {
+    let contents = fs::read_to_string(path)?;
+    if contents.contains(SYSCONFIG_RELOCATION) {
+        return Ok(());
+    }
+    fs::write(path, contents + SYSCONFIG_RELOCATION)?;
+
+    let Some(cache) = path.parent().map(|parent| parent.join("__pycache__")) else {
+        return Ok(());
+    };
+    let Ok(entries) = fs::read_dir(cache) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|name| name.starts_with(&format!("{stem}.")))
+        {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
 }
 
 /// Generate a platform portion of a key from the environment.
 fn platform_key_from_env() -> Result<String, Error> {
-    let os = Os::from_env()?;
-    let arch = Arch::from_env()?;
-    let libc = Libc::from_env();
-    Ok(format!("{os}-{arch}-{libc}").to_lowercase())
+    Ok(Platform::from_env()?.to_string())
+}
+
+/// Download and install a managed toolchain that satisfies the request on the given platform.
+///
+/// The toolchain is extracted into the [`InstalledToolchains`] directory, where it is found by
+/// interpreter discovery, i.e., [`crate::InterpreterSource::ManagedToolchain`]. If a matching
+/// toolchain is already installed, it is returned without downloading it again.
+///
+/// Archives are downloaded and verified according to the [`FetchOptions`].
+#[cfg(feature = "downloads")]
+pub async fn install(
+    request: PythonDownloadRequest,
+    platform: &Platform,
+    toolchains: &InstalledToolchains,
+    client: &BaseClient,
+    options: &FetchOptions,
+) -> Result<Toolchain, Error> {
+    let request = request
+        .with_os(platform.os.clone())
+        .with_arch(platform.arch)
+        .with_libc(platform.libc)
+        .fill()?;
+    let download = PythonDownload::from_request(&request)
+        .ok_or_else(|| Error::NoDownloadFound(request.to_string()))?;
+
+    let toolchains = toolchains.clone().init()?;
+    let result = download
+        .fetch_with_options(client, toolchains.root(), options)
+        .await?;
+    installed_toolchain(download, result)
+}
+
+/// Upgrade a managed toolchain to the newest patch release of its minor version.
+///
+/// The new release is installed alongside the existing toolchain, into its own directory, and is
+/// only visible to interpreter discovery once it has been fully extracted. Since discovery prefers
+/// newer versions, it replaces the existing toolchain for new environments. The existing toolchain
+/// is not removed, such that virtual environments created from it remain functional; use
+/// [`ToolchainUpgrade::stale_environments`] to determine which need to be recreated.
+///
+/// Returns `None` if the toolchain is already on the newest available patch release.
+#[cfg(feature = "downloads")]
+pub async fn upgrade(
+    toolchain: &Toolchain,
+    toolchains: &InstalledToolchains,
+    client: &BaseClient,
+    options: &FetchOptions,
+) -> Result<Option<ToolchainUpgrade>, Error> {
+    let Some(download) = find_upgrade(toolchain) else {
+        debug!("Toolchain `{toolchain}` is up to date");
+        return Ok(None);
+    };
+
+    debug!("Upgrading toolchain `{toolchain}` to `{download}`");
+    let toolchains = toolchains.clone().init()?;
+    let result = download
+        .fetch_with_options(client, toolchains.root(), options)
+        .await?;
+    let upgraded = installed_toolchain(download, result)?;
+
+    Ok(Some(ToolchainUpgrade {
+        previous: toolchain.clone(),
+        upgraded,
+    }))
+}
+
+/// Install a managed toolchain from a pre-downloaded archive, e.g., in an offline environment.
+///
+/// The archive must be a python-build-standalone release archive with its original filename, e.g.,
+/// `cpython-3.12.3+20240415-x86_64-unknown-linux-gnu-install_only.tar.gz`. It is verified and
+/// installed with the same layout as a downloaded toolchain, reporting the progress to the
+/// reporter, if any.
+#[cfg(feature = "downloads")]
+pub async fn install_from_archive(
+    archive: &Path,
+    toolchains: &InstalledToolchains,
+    signature_policy: &SignaturePolicy,
+    reporter: Option<&dyn Reporter>,
+) -> Result<Toolchain, Error> {
+    let download = archive
+        .file_name()
+        .and_then(OsStr::to_str)
+        .and_then(PythonDownload::from_archive_filename)
+        .ok_or_else(|| Error::UnknownArchive(archive.to_path_buf()))?;
+
+    let toolchains = toolchains.clone().init()?;
+    let result = download
+        .install_archive(archive, toolchains.root(), signature_policy, reporter)
+        .await?;
+    installed_toolchain(download, result)
+}
+
+/// Repair a damaged toolchain by downloading and installing it again.
+///
+/// The damaged toolchain is only removed once the toolchain was reinstalled successfully; see
+/// [`verify`] to detect damaged toolchains.
+#[cfg(feature = "downloads")]
+pub async fn repair(
+    toolchains: &InstalledToolchains,
+    key: &str,
+    client: &BaseClient,
+    options: &FetchOptions,
+) -> Result<Toolchain, Error> {
+    let download =
+        PythonDownload::from_key(key).ok_or_else(|| Error::NoDownloadFound(key.to_string()))?;
+    let path = toolchains.root().join(key);
+    if !path.is_dir() {
+        return Err(Error::NotInstalled(key.to_string()));
+    }
+
+    // Move the damaged toolchain out of the way, restoring it if the reinstallation fails.
+    let temp_dir = tempfile::tempdir_in(toolchains.root())?;
+    let damaged = temp_dir.path().join(key);
+    fs::rename(&path, &damaged)?;
+    debug!("Reinstalling damaged toolchain `{key}`");
+    match download
+        .fetch_with_options(client, toolchains.root(), options)
+        .await
+    {
+        Ok(result) => installed_toolchain(download, result),
+        Err(err) => {
+            fs::rename(&damaged, &path)?;
+            Err(err)
+        }
+    }
+}
+
+/// Read the toolchain installed for the download, ensuring it contains a Python executable.
+///
+/// The interpreter info of newly installed toolchains is recorded, see [`Toolchain::interpreter`].
+#[cfg(feature = "downloads")]
+fn installed_toolchain(
+    download: &PythonDownload,
+    result: DownloadResult,
+) -> Result<Toolchain, Error> {
+    let (path, fetched) = match result {
+        DownloadResult::AlreadyAvailable(path) => {
+            debug!("Found existing toolchain `{download}`");
+            (path, false)
+        }
+        DownloadResult::Fetched(path) => {
+            debug!(
+                "Installed toolchain `{download}` to `{}`",
+                path.user_display()
+            );
+            (path, true)
+        }
+    };
+
+    let toolchain = Toolchain::new(path)?;
+    let executable = toolchain.executable();
+    if !executable.is_file() {
+        return Err(Error::MissingExecutable(executable));
+    }
+
+    // Record the interpreter info of newly installed toolchains, such that discovery doesn't need
+    // to query them. If it fails, discovery falls back to querying the toolchain.
+    if fetched {
+        if let Err(err) = toolchain.record_interpreter() {
+            debug!("Failed to record the interpreter info of toolchain `{toolchain}`: {err}");
+        }
+    }
+    Ok(toolchain)
+}
+
+/// Query the interpreter of the managed toolchain containing the given Python executable.
+///
+/// See [`Toolchain::interpreter`].
+pub(crate) fn query_interpreter(
+    executable: &Path,
+    cache: &Cache,
+) -> Result<Interpreter, crate::interpreter::Error> {
+    // The executable is at `install/python.exe` or `install/bin/python3` in the toolchain.
+    let record = executable
+        .ancestors()
+        .skip(2)
+        .take(2)
+        .map(|dir| dir.join(INTERPRETER_FILENAME))
+        .find(|path| path.is_file());
+    if let Some(record) = record {
+        match Interpreter::from_record(executable, &record) {
+            Ok(Some(interpreter)) => {
+                debug!(
+                    "Using recorded interpreter info for `{}`",
+                    executable.user_display()
+                );
+                return Ok(interpreter);
+            }
+            Ok(None) => {}
+            Err(err) => debug!(
+                "Failed to read interpreter info at `{}`: {err}",
+                record.user_display()
+            ),
+        }
+    }
+    Interpreter::query(executable, cache)
 }
 
 impl fmt::Display for Toolchain {
@@ -214,3 +1437,441 @@ impl fmt::Display for Toolchain {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use crate::implementation::ImplementationName;
+    use crate::platform::{Arch, Libc, Os, Platform};
+
+    use super::{
+        available_downloads, disk_usage, find_upgrade, lock_path, make_relocatable, prune, verify,
+        write_manifest, InstalledToolchains, PrunePolicy, ShimCollision, Toolchain,
+        ToolchainMetadata, ToolchainPin, Verification, PIN_FILENAME,
+    };
+    use crate::downloads::Error;
+    use crate::downloads::PythonDownload;
+
+    #[test]
+    fn toolchain_from_key() {
+        let toolchain =
+            Toolchain::new(PathBuf::from("/toolchains/cpython-3.12.3-linux-x86_64-gnu")).unwrap();
+        assert_eq!(toolchain.key(), "cpython-3.12.3-linux-x86_64-gnu");
+        assert_eq!(toolchain.implementation(), ImplementationName::CPython);
+        assert_eq!(toolchain.python_version().to_string(), "3.12.3");
+        assert_eq!(
+            toolchain.platform(),
+            &Platform::new(Os::Linux, Arch::X86_64, Libc::Gnu)
+        );
+        assert_eq!(toolchain.libc(), Libc::Gnu);
+
+        assert!(!toolchain.free_threaded());
+
+        let toolchain = Toolchain::new(PathBuf::from(
+            "/toolchains/cpython-3.13.0t-linux-x86_64-gnu",
+        ))
+        .unwrap();
+        assert_eq!(toolchain.python_version().to_string(), "3.13.0");
+        assert!(toolchain.free_threaded());
+
+        assert!(Toolchain::new(PathBuf::from("/toolchains/cpython-3.12.3")).is_err());
+        assert!(Toolchain::new(PathBuf::from("/toolchains/.lock")).is_err());
+    }
+
+    #[test]
+    fn available_downloads_for_platform() {
+        let platform = Platform::new(Os::Linux, Arch::X86_64, Libc::Musl);
+        let downloads: Vec<_> = available_downloads(&platform).collect();
+        assert!(!downloads.is_empty());
+        assert!(downloads
+            .iter()
+            .all(|download| download.key().ends_with("-linux-x86_64-musl")));
+        assert_eq!(downloads[0].key(), "cpython-3.12.3-linux-x86_64-musl");
+
+        let platform = Platform::new(Os::Haiku, Arch::X86_64, Libc::None);
+        assert_eq!(available_downloads(&platform).count(), 0);
+    }
+
+    #[test]
+    fn upgrade_within_minor_version() {
+        let toolchain =
+            Toolchain::new(PathBuf::from("/toolchains/cpython-3.12.1-linux-x86_64-gnu")).unwrap();
+        let download = find_upgrade(&toolchain).unwrap();
+        assert_eq!(download.key(), "cpython-3.12.3-linux-x86_64-gnu");
+
+        let toolchain =
+            Toolchain::new(PathBuf::from("/toolchains/cpython-3.12.3-linux-x86_64-gnu")).unwrap();
+        assert!(find_upgrade(&toolchain).is_none());
+    }
+
+    #[test]
+    fn prune_toolchains() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let toolchains = InstalledToolchains::from_path(temp_dir.path()).unwrap();
+        for key in [
+            "cpython-3.11.9-linux-x86_64-gnu",
+            "cpython-3.12.1-linux-x86_64-gnu",
+            "cpython-3.12.3-linux-x86_64-gnu",
+            "cpython-3.12.2-macos-aarch64-none",
+        ] {
+            let install = temp_dir.path().join(key).join("install");
+            fs_err::create_dir_all(&install).unwrap();
+            fs_err::write(install.join("python"), "python").unwrap();
+        }
+
+        // Nothing is removed by default.
+        let summary = prune(&toolchains, &PrunePolicy::default()).unwrap();
+        assert!(summary.removed.is_empty());
+
+        // Only the older patch release on the same platform is superseded.
+        let summary = prune(&toolchains, &PrunePolicy::default().with_superseded(true)).unwrap();
+        let removed: Vec<_> = summary.removed.iter().map(Toolchain::key).collect();
+        assert_eq!(removed, ["cpython-3.12.1-linux-x86_64-gnu"]);
+        assert_eq!(summary.reclaimed, 6);
+        assert!(!temp_dir.path().join(removed[0]).exists());
+        // The removal is serialized with installations of the same toolchain.
+        assert!(lock_path(temp_dir.path(), removed[0]).is_file());
+
+        // Recently used toolchains are kept.
+        let used = Toolchain::new(temp_dir.path().join("cpython-3.11.9-linux-x86_64-gnu")).unwrap();
+        used.record_use().unwrap();
+        let policy = PrunePolicy::default().with_unused_for(Duration::from_secs(60 * 60));
+        assert!(prune(&toolchains, &policy).unwrap().removed.is_empty());
+
+        let policy = PrunePolicy::default().with_unused_for(Duration::ZERO);
+        assert_eq!(prune(&toolchains, &policy).unwrap().removed.len(), 3);
+    }
+
+    #[test]
+    fn toolchain_pin() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path().join("project");
+        let member = project.join("member");
+        fs_err::create_dir_all(&member).unwrap();
+        assert_eq!(ToolchainPin::find(&member).unwrap(), None);
+
+        // The closest pin file is used.
+        fs_err::write(
+            project.join(PIN_FILENAME),
+            "# Pinned toolchain\ncpython-3.12.3-linux-x86_64-gnu\n",
+        )
+        .unwrap();
+        let pin = ToolchainPin::find(&member).unwrap().unwrap();
+        assert_eq!(pin.key(), "cpython-3.12.3-linux-x86_64-gnu");
+        assert_eq!(pin.path(), project.join(PIN_FILENAME));
+
+        // A pin without a platform is completed for the given platform.
+        let platform = Platform::new(Os::Macos, Arch::Aarch64, Libc::None);
+        let pin = ToolchainPin::parse("cpython-3.12.3", PathBuf::from(PIN_FILENAME), &platform);
+        assert_eq!(pin.unwrap().key(), "cpython-3.12.3-macos-aarch64-none");
+
+        // Plain versions do not pin a toolchain.
+        fs_err::write(member.join(PIN_FILENAME), "3.12\n").unwrap();
+        assert_eq!(ToolchainPin::find(&member).unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn create_shim() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let bin_dir = temp_dir.path().join("bin");
+        let toolchains = temp_dir.path().join("toolchains");
+        let older = Toolchain::new(toolchains.join("cpython-3.12.1-linux-x86_64-gnu")).unwrap();
+        let newer = Toolchain::new(toolchains.join("cpython-3.12.3-linux-x86_64-gnu")).unwrap();
+
+        let shim = older.create_shim(&bin_dir, ShimCollision::Fail).unwrap();
+        assert_eq!(shim, bin_dir.join("python3.12"));
+        assert_eq!(fs_err::read_link(&shim).unwrap(), older.executable());
+
+        // Shims for other managed toolchains are replaced.
+        newer.create_shim(&bin_dir, ShimCollision::Fail).unwrap();
+        assert_eq!(fs_err::read_link(&shim).unwrap(), newer.executable());
+
+        // Other files are only replaced if requested.
+        fs_err::remove_file(&shim).unwrap();
+        fs_err::write(&shim, "#!/bin/sh\n").unwrap();
+        assert!(matches!(
+            newer.create_shim(&bin_dir, ShimCollision::Fail),
+            Err(Error::ShimCollision(_))
+        ));
+        newer.create_shim(&bin_dir, ShimCollision::Replace).unwrap();
+        assert_eq!(fs_err::read_link(&shim).unwrap(), newer.executable());
+    }
+
+    #[test]
+    fn toolchain_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let download = PythonDownload::from_key("cpython-3.12.3-linux-x86_64-gnu").unwrap();
+        let toolchain = Toolchain::new(temp_dir.path().join(download.key())).unwrap();
+        fs_err::create_dir_all(
+            toolchain
+                .path()
+                .join("install")
+                .join("lib")
+                .join("python3.12")
+                .join("tkinter"),
+        )
+        .unwrap();
+        assert_eq!(toolchain.metadata().unwrap(), None);
+
+        let metadata = ToolchainMetadata::from_download(download, download.url(), toolchain.path());
+        assert_eq!(metadata.key, "cpython-3.12.3-linux-x86_64-gnu");
+        assert_eq!(metadata.build_date.as_deref(), Some("20240415"));
+        assert_eq!(metadata.libc, "gnu");
+        assert!(metadata.tkinter);
+
+        metadata.write(toolchain.path()).unwrap();
+        assert_eq!(toolchain.metadata().unwrap(), Some(metadata));
+    }
+
+    #[test]
+    fn verify_toolchain() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let toolchains = InstalledToolchains::from_path(temp_dir.path()).unwrap();
+        let key = "cpython-3.12.3-linux-x86_64-gnu";
+        let bin = temp_dir.path().join(key).join("install").join("bin");
+        fs_err::create_dir_all(&bin).unwrap();
+        fs_err::write(bin.join("python3"), "python").unwrap();
+        fs_err::write(bin.join("pip3"), "pip").unwrap();
+
+        assert!(matches!(
+            verify(&toolchains, key),
+            Err(Error::MissingManifest(_))
+        ));
+        assert!(matches!(
+            verify(&toolchains, "cpython-3.11.9-linux-x86_64-gnu"),
+            Err(Error::NotInstalled(_))
+        ));
+
+        write_manifest(&temp_dir.path().join(key)).unwrap();
+        assert!(verify(&toolchains, key).unwrap().is_intact());
+
+        // Added files are ignored.
+        fs_err::write(bin.join("python3.pyc"), "bytecode").unwrap();
+        fs_err::write(bin.join("python3"), "damaged").unwrap();
+        fs_err::remove_file(bin.join("pip3")).unwrap();
+        assert_eq!(
+            verify(&toolchains, key).unwrap(),
+            Verification {
+                modified: vec![PathBuf::from("install/bin/python3")],
+                missing: vec![PathBuf::from("install/bin/pip3")],
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_alias() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let toolchains = InstalledToolchains::from_path(temp_dir.path()).unwrap();
+        let platform = Platform::from_env().unwrap();
+        for key in [
+            format!("cpython-3.11.9-{platform}"),
+            format!("cpython-3.12.1-{platform}"),
+            format!("cpython-3.12.3-{platform}"),
+            format!("cpython-3.13.0t-{platform}"),
+            "cpython-3.13.0-illumos-s390x-none".to_string(),
+        ] {
+            fs_err::create_dir_all(temp_dir.path().join(key)).unwrap();
+        }
+
+        let resolve = |alias: &str| {
+            toolchains
+                .resolve(alias)
+                .unwrap()
+                .map(|toolchain| toolchain.key().to_string())
+        };
+        assert_eq!(resolve("3.12"), Some(format!("cpython-3.12.3-{platform}")));
+        assert_eq!(resolve("3"), Some(format!("cpython-3.12.3-{platform}")));
+        // Free-threaded toolchains and toolchains for other platforms are skipped.
+        assert_eq!(
+            resolve("latest"),
+            Some(format!("cpython-3.12.3-{platform}"))
+        );
+        assert_eq!(
+            resolve("3.13t"),
+            Some(format!("cpython-3.13.0t-{platform}"))
+        );
+        assert_eq!(resolve("3.10"), None);
+        assert_eq!(
+            resolve(&format!("cpython-3.12.1-{platform}")),
+            Some(format!("cpython-3.12.1-{platform}"))
+        );
+        assert!(matches!(
+            toolchains.resolve("newest"),
+            Err(Error::InvalidAlias(_))
+        ));
+
+        // Aliases can't refer to directories outside the toolchains directory.
+        fs_err::create_dir_all(temp_dir.path().join("cpython-3.12.1-outside")).unwrap();
+        let toolchains = InstalledToolchains::from_path(temp_dir.path().join("nested")).unwrap();
+        for alias in [
+            "../cpython-3.12.1-outside".to_string(),
+            temp_dir
+                .path()
+                .join("cpython-3.12.1-outside")
+                .to_string_lossy()
+                .to_string(),
+        ] {
+            assert!(matches!(
+                toolchains.resolve(&alias),
+                Err(Error::InvalidAlias(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn find_for_prefix() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let toolchains = InstalledToolchains::from_path(temp_dir.path()).unwrap();
+        let key = "cpython-3.12.3-linux-x86_64-gnu";
+        fs_err::create_dir_all(temp_dir.path().join(key).join("install")).unwrap();
+
+        let toolchain = toolchains
+            .find_for_prefix(&temp_dir.path().join(key).join("install"))
+            .unwrap();
+        assert_eq!(toolchain.key(), key);
+
+        // Interpreters that aren't installed toolchains, e.g., the interpreter of a toolchain
+        // installed elsewhere, don't belong to a toolchain.
+        assert!(toolchains
+            .find_for_prefix(&temp_dir.path().join(key))
+            .is_none());
+        let other = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(other.path().join(key).join("install")).unwrap();
+        assert!(toolchains
+            .find_for_prefix(&other.path().join(key).join("install"))
+            .is_none());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn relocatable_toolchain() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let install = temp_dir.path().join("install");
+        let bin = install.join("bin");
+        let stdlib = install.join("lib").join("python3.12");
+        fs_err::create_dir_all(&bin).unwrap();
+        fs_err::create_dir_all(stdlib.join("__pycache__")).unwrap();
+        fs_err::write(bin.join("pip3"), "#!/install/bin/python3.12\nimport pip\n").unwrap();
+        fs_err::write(bin.join("python3-config"), "#!/bin/sh\necho\n").unwrap();
+        fs_err::write(bin.join("python3.12"), b"\x7fELF").unwrap();
+        let sysconfig = stdlib.join("_sysconfigdata__linux_x86_64-linux-gnu.py");
+        fs_err::write(
+            &sysconfig,
+            "build_time_vars = {'prefix': '/install', 'LDFLAGS': '-L/install/lib -L/installer', 'SIZEOF_INT': 4}\n",
+        )
+        .unwrap();
+        let bytecode = stdlib
+            .join("__pycache__")
+            .join("_sysconfigdata__linux_x86_64-linux-gnu.cpython-312.pyc");
+        fs_err::write(&bytecode, "").unwrap();
+
+        make_relocatable(temp_dir.path()).unwrap();
+        let script = fs_err::read_to_string(bin.join("pip3")).unwrap();
+        assert!(script.starts_with("#!/bin/sh\n'''true'\n"));
+        assert!(script.contains("/'python3.12' \"$0\" \"$@\"\n"));
+        assert!(script.ends_with("\n' '''\nimport pip\n"));
+        assert_eq!(
+            fs_err::read_to_string(bin.join("python3-config")).unwrap(),
+            "#!/bin/sh\necho\n"
+        );
+        let patched = fs_err::read_to_string(&sysconfig).unwrap();
+        assert!(patched.contains("_uv_relocate()"));
+        assert!(!bytecode.exists());
+
+        // Patching again is a no-op.
+        make_relocatable(temp_dir.path()).unwrap();
+        assert_eq!(fs_err::read_to_string(&sysconfig).unwrap(), patched);
+
+        // The script runs the interpreter next to it, even when invoked through a symlink to its
+        // directory, or through an absolute or relative symlink to the script itself.
+        fs_err::write(bin.join("python3.12"), "#!/bin/sh\necho \"$0\"\n").unwrap();
+        fs_err::set_permissions(
+            bin.join("python3.12"),
+            std::os::unix::fs::PermissionsExt::from_mode(0o755),
+        )
+        .unwrap();
+        fs_err::os::unix::fs::symlink(&bin, temp_dir.path().join("linked")).unwrap();
+        let local_bin = temp_dir.path().join("local").join("bin");
+        fs_err::create_dir_all(&local_bin).unwrap();
+        fs_err::os::unix::fs::symlink(bin.join("pip3"), local_bin.join("pip3")).unwrap();
+        fs_err::os::unix::fs::symlink("pip3", local_bin.join("pip")).unwrap();
+        for script in [
+            temp_dir.path().join("linked").join("pip3"),
+            local_bin.join("pip3"),
+            local_bin.join("pip"),
+        ] {
+            let output = std::process::Command::new("sh")
+                .arg(&script)
+                .output()
+                .unwrap();
+            assert_eq!(
+                Path::new(String::from_utf8(output.stdout).unwrap().trim()),
+                bin.canonicalize().unwrap().join("python3.12"),
+                "{}",
+                script.display()
+            );
+        }
+
+        // Paths in the `sysconfig` data are resolved relative to the installation.
+        let python = which::which("python3").expect("`python3` is required to run this test");
+        let output = std::process::Command::new(python)
+            .arg("-c")
+            .arg(format!(
+                "import sys; sys.base_prefix = '/moved'; exec(open({:?}).read()); print(build_time_vars)",
+                sysconfig.display()
+            ))
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap().trim(),
+            "{'prefix': '/moved', 'LDFLAGS': '-L/moved/lib -L/installer', 'SIZEOF_INT': 4}"
+        );
+    }
+
+    #[test]
+    fn toolchain_disk_usage() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let toolchains = InstalledToolchains::from_path(temp_dir.path()).unwrap();
+
+        // A missing toolchain directory is empty.
+        let usage =
+            disk_usage(&InstalledToolchains::from_path(temp_dir.path().join("missing")).unwrap())
+                .unwrap();
+        assert_eq!(usage.total, 0);
+
+        for (key, contents) in [
+            ("cpython-3.11.9-linux-x86_64-gnu", "python"),
+            ("cpython-3.12.3-linux-x86_64-gnu", "python3.12"),
+        ] {
+            let install = temp_dir.path().join(key).join("install");
+            fs_err::create_dir_all(&install).unwrap();
+            fs_err::write(install.join("python"), contents).unwrap();
+        }
+        fs_err::write(
+            temp_dir
+                .path()
+                .join("cpython-3.13.0-x86_64-unknown-linux-gnu.tar.gz.part"),
+            "partial",
+        )
+        .unwrap();
+
+        let usage = disk_usage(&toolchains).unwrap();
+        let sizes: Vec<_> = usage
+            .toolchains
+            .iter()
+            .map(|(toolchain, size)| (toolchain.key(), *size))
+            .collect();
+        assert_eq!(
+            sizes,
+            [
+                ("cpython-3.12.3-linux-x86_64-gnu", 10),
+                ("cpython-3.11.9-linux-x86_64-gnu", 6)
+            ]
+        );
+        assert_eq!(usage.other, 7);
+        assert_eq!(usage.total, 23);
+    }
+}
@@ -0,0 +1,167 @@
+//! Named virtual environments in a central directory, e.g., `~/.local/share/uv/envs/<name>`.
+//!
+//! Named environments are opt-in: they're created with `uv venv --named <name>`, and requested
+//! with `env:<name>`, e.g., `--python env:<name>`.
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use thiserror::Error;
+
+use uv_state::{StateBucket, StateStore};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] io::Error),
+    #[error("Invalid environment name `{0}`: names may only contain letters, numbers, `-`, `_`, and `.`, and may not start with `.`")]
+    InvalidName(String),
+}
+
+/// A directory of named virtual environments, with one environment per subdirectory.
+#[derive(Debug, Clone)]
+pub struct NamedEnvironments {
+    /// The path to the top-level directory of the named environments.
+    root: PathBuf,
+}
+
+impl NamedEnvironments {
+    /// A directory for named environments at `root`.
+    pub fn from_path(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Prefer, in order:
+    /// 1. The specific environment directory specified by the user, i.e., `UV_ENVIRONMENT_DIR`
+    /// 2. A directory in the system-appropriate user-level data directory, e.g., `~/.local/share/uv/envs`
+    /// 3. A directory in the local data directory, e.g., `./.uv/envs`
+    pub fn from_settings() -> Result<Self, io::Error> {
+        if let Some(environment_dir) = std::env::var_os("UV_ENVIRONMENT_DIR") {
+            Ok(Self::from_path(environment_dir))
+        } else {
+            Ok(Self::from_path(
+                StateStore::from_settings(None)?.bucket(StateBucket::Environments),
+            ))
+        }
+    }
+
+    /// Initialize the named environment directory.
+    ///
+    /// Ensures the directory is created.
+    pub fn init(self) -> Result<Self, io::Error> {
+        let root = &self.root;
+
+        // Create the directory, if it doesn't exist.
+        fs::create_dir_all(root)?;
+
+        // Add a .gitignore.
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(root.join(".gitignore"))
+        {
+            Ok(mut file) => file.write_all(b"*")?,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => (),
+            Err(err) => return Err(err),
+        }
+
+        Ok(self)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The path of the environment with the given name, whether or not it exists.
+    pub fn path(&self, name: &str) -> Result<PathBuf, Error> {
+        if !is_valid_name(name) {
+            return Err(Error::InvalidName(name.to_string()));
+        }
+        Ok(self.root.join(name))
+    }
+
+    /// Find the environment with the given name.
+    ///
+    /// Returns `None` if there's no environment with the given name, i.e., no directory with a
+    /// `pyvenv.cfg`.
+    pub fn find(&self, name: &str) -> Result<Option<PathBuf>, Error> {
+        let path = self.path(name)?;
+        if path.join("pyvenv.cfg").is_file() {
+            Ok(Some(path))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List the names and paths of all environments, sorted by name.
+    pub fn list(&self) -> Result<Vec<(String, PathBuf)>, Error> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut environments = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if is_valid_name(name) && path.join("pyvenv.cfg").is_file() {
+                environments.push((name.to_string(), path));
+            }
+        }
+        environments.sort();
+        Ok(environments)
+    }
+}
+
+/// Returns `true` if the name can be used for a named environment, i.e., it's a single,
+/// non-hidden path component.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NamedEnvironments;
+
+    #[test]
+    fn named_environments() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let environments = NamedEnvironments::from_path(temp_dir.path().join("envs"))
+            .init()
+            .unwrap();
+        assert!(environments.list().unwrap().is_empty());
+
+        for name in ["foo", "bar-1.0"] {
+            let path = environments.path(name).unwrap();
+            fs_err::create_dir_all(&path).unwrap();
+            fs_err::write(path.join("pyvenv.cfg"), "").unwrap();
+        }
+        // Directories without a `pyvenv.cfg` aren't environments.
+        fs_err::create_dir_all(environments.root().join("baz")).unwrap();
+
+        assert_eq!(
+            environments
+                .list()
+                .unwrap()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["bar-1.0", "foo"]
+        );
+        assert_eq!(
+            environments.find("foo").unwrap(),
+            Some(environments.root().join("foo"))
+        );
+        assert_eq!(environments.find("baz").unwrap(), None);
+
+        for name in ["", ".venv", "..", "foo/bar", "foo\\bar"] {
+            assert!(environments.path(name).is_err(), "{name}");
+        }
+    }
+}
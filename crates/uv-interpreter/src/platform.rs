@@ -2,8 +2,12 @@ use std::{
     fmt::{self},
     str::FromStr,
 };
+
+use once_cell::sync::Lazy;
 use thiserror::Error;
 
+use crate::probe::elf_interpreter;
+
 /// All supported operating systems.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Os {
@@ -31,6 +35,14 @@ pub enum Arch {
     S390X,
 }
 
+/// The platform of a managed toolchain, e.g., `linux-x86_64-gnu`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Platform {
+    pub os: Os,
+    pub arch: Arch,
+    pub libc: Libc,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum Libc {
     Gnu,
@@ -46,6 +58,29 @@ pub enum Error {
     ArchNotSupported(String),
     #[error("Libc type could not be detected")]
     LibcNotDetected,
+    #[error("Unknown libc: {0}")]
+    UnknownLibc(String),
+}
+
+impl Platform {
+    pub fn new(os: Os, arch: Arch, libc: Libc) -> Self {
+        Self { os, arch, libc }
+    }
+
+    /// The platform of the current machine.
+    pub fn from_env() -> Result<Self, Error> {
+        Ok(Self::new(
+            Os::from_env()?,
+            Arch::from_env()?,
+            Libc::from_env()?,
+        ))
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{}-{}-{}", self.os, self.arch, self.libc).to_lowercase())
+    }
 }
 
 impl fmt::Display for Os {
@@ -129,14 +164,70 @@ impl Arch {
 }
 
 impl Libc {
-    pub(crate) fn from_env() -> Self {
-        // TODO(zanieb): Perform this lookup
-        match std::env::consts::OS {
+    /// The libc of the current machine.
+    ///
+    /// On Linux, the libc is detected from the system unless overridden with `UV_PYTHON_INSTALL_LIBC`,
+    /// e.g., to use `gnu` toolchains in an Alpine container with a glibc compatibility layer.
+    pub(crate) fn from_env() -> Result<Self, Error> {
+        if let Some(libc) = std::env::var("UV_PYTHON_INSTALL_LIBC")
+            .ok()
+            .filter(|libc| !libc.is_empty())
+        {
+            return Self::from_str(&libc);
+        }
+        Ok(match std::env::consts::OS {
             // Supported platforms.
-            "linux" => Libc::Gnu,
+            "linux" => Self::detect_linux(),
             "windows" | "macos" => Libc::None,
             // Platforms without explicit support.
             _ => Libc::None,
+        })
+    }
+
+    /// Detect the libc of the system from the dynamic loader requested by `/bin/sh`.
+    ///
+    /// The libc uv itself was built against can't be used, since uv may be statically linked
+    /// against musl on glibc-based distributions. The result is cached, since the system libc
+    /// doesn't change while uv is running.
+    fn detect_linux() -> Self {
+        static LIBC: Lazy<Libc> = Lazy::new(Libc::detect_linux_uncached);
+        *LIBC
+    }
+
+    fn detect_linux_uncached() -> Self {
+        let interpreter = fs_err::read("/bin/sh")
+            .ok()
+            .and_then(|bytes| elf_interpreter(&bytes));
+        let is_musl = match interpreter {
+            Some(interpreter) => interpreter.contains("musl"),
+            // If `/bin/sh` isn't a dynamically linked binary, look for the musl loader instead,
+            // e.g., `/lib/ld-musl-x86_64.so.1`.
+            None => fs_err::read_dir("/lib").is_ok_and(|entries| {
+                entries.flatten().any(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|name| name.starts_with("ld-musl-"))
+                })
+            }),
+        };
+        if is_musl {
+            Libc::Musl
+        } else {
+            Libc::Gnu
+        }
+    }
+}
+
+impl FromStr for Libc {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gnu" => Ok(Self::Gnu),
+            "musl" => Ok(Self::Musl),
+            "none" => Ok(Self::None),
+            _ => Err(Error::UnknownLibc(s.to_string())),
         }
     }
 }
@@ -20,8 +20,14 @@ impl Prefix {
     }
 
     /// Return an iterator over the `site-packages` directories inside the environment.
+    ///
+    /// As for interpreters, `platlib` is only included if it differs from `purelib`, e.g., for
+    /// schemes that place compiled packages in `lib64`.
     pub fn site_packages(&self, virtualenv: &Scheme) -> impl Iterator<Item = PathBuf> {
-        std::iter::once(self.0.join(&virtualenv.purelib))
+        let purelib = self.0.join(&virtualenv.purelib);
+        let platlib =
+            (virtualenv.platlib != virtualenv.purelib).then(|| self.0.join(&virtualenv.platlib));
+        std::iter::once(purelib).chain(platlib)
     }
 
     /// Initialize the `--prefix` directory.
@@ -41,3 +47,42 @@ impl From<PathBuf> for Prefix {
         Self(path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pypi_types::Scheme;
+
+    use super::Prefix;
+
+    #[test]
+    fn prefix_scheme() {
+        let prefix = Prefix::from(PathBuf::from("/prefix"));
+        let mut virtualenv = Scheme {
+            purelib: PathBuf::from("lib/python3.12/site-packages"),
+            platlib: PathBuf::from("lib/python3.12/site-packages"),
+            scripts: PathBuf::from("bin"),
+            data: PathBuf::new(),
+            include: PathBuf::from("include/site/python3.12"),
+        };
+
+        let scheme = prefix.scheme(&virtualenv);
+        assert_eq!(
+            scheme.purelib,
+            PathBuf::from("/prefix/lib/python3.12/site-packages")
+        );
+        assert_eq!(scheme.scripts, PathBuf::from("/prefix/bin"));
+        assert_eq!(scheme.data, PathBuf::from("/prefix"));
+        assert_eq!(prefix.site_packages(&virtualenv).count(), 1);
+
+        virtualenv.platlib = PathBuf::from("lib64/python3.12/site-packages");
+        assert_eq!(
+            prefix.site_packages(&virtualenv).collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("/prefix/lib/python3.12/site-packages"),
+                PathBuf::from("/prefix/lib64/python3.12/site-packages"),
+            ]
+        );
+    }
+}
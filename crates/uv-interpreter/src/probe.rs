@@ -0,0 +1,711 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use once_cell::sync::Lazy;
+use pep440_rs::Version;
+use regex::bytes::Regex;
+
+use crate::pointer_size::PointerSize;
+
+/// Binaries larger than this are not probed, since they're read into memory in full.
+const MAX_PROBE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// The name of the Python library or standard library directory, e.g., `libpython3.12.so`,
+/// `python312.dll`, or `lib/python3.13t`.
+static LIBRARY_NAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?-u)(?:libpython|lib/python)(3)\.(\d{1,2})(t?)|python(3)(\d{1,2})(t?)(?:_d)?\.dll",
+    )
+    .unwrap()
+});
+
+/// A NUL-terminated version string, i.e., the `PY_VERSION` constant, e.g., `3.12.3` or `3.13.0rc1`.
+static VERSION_STRING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?-u)\x00(3\.\d{1,2}\.\d{1,2}(?:(?:a|b|rc)\d{1,2})?)\+?\x00").unwrap()
+});
+
+/// The executable format of a binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFormat {
+    /// An ELF binary, e.g., on Linux.
+    Elf,
+    /// A PE binary, i.e., on Windows.
+    Pe,
+    /// A Mach-O binary, i.e., on macOS.
+    MachO,
+}
+
+/// Version and build information read from a Python executable without running it.
+///
+/// Used to identify interpreters that cannot run the query script, e.g., because their standard
+/// library is missing or truncated, so that we can report what they are rather than a raw query
+/// failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticProbe {
+    version: Version,
+    format: BinaryFormat,
+    pointer_size: Option<PointerSize>,
+    gil_disabled: bool,
+}
+
+impl StaticProbe {
+    /// Probe the Python executable at the given path.
+    ///
+    /// Returns `None` if the file is not a binary or no Python version could be found in it, e.g.,
+    /// for wrapper scripts.
+    pub(crate) fn from_executable(path: &Path) -> Result<Option<Self>, io::Error> {
+        if fs_err::metadata(path)?.len() > MAX_PROBE_SIZE {
+            return Ok(None);
+        }
+        Ok(Self::from_bytes(&fs_err::read(path)?))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (format, pointer_size) = binary_format(bytes)?;
+
+        // Find the minor version from the name of the Python library the binary links against, or
+        // embeds.
+        let captures = LIBRARY_NAME.captures(bytes)?;
+        let group = |indices: [usize; 2]| {
+            indices
+                .into_iter()
+                .find_map(|index| captures.get(index))
+                .map(|group| group.as_bytes())
+        };
+        let major = std::str::from_utf8(group([1, 4])?).ok()?;
+        let minor = std::str::from_utf8(group([2, 5])?).ok()?;
+        let gil_disabled = group([3, 6]).is_some_and(|suffix| suffix == b"t");
+
+        // If the binary contains the full version string, prefer it. Other libraries may be
+        // linked in with their own version strings, so only consider those that agree with the
+        // library name.
+        let prefix = format!("{major}.{minor}.");
+        let full = VERSION_STRING
+            .captures_iter(bytes)
+            .filter_map(|captures| std::str::from_utf8(captures.get(1)?.as_bytes()).ok())
+            .find(|version| version.starts_with(&prefix));
+        let version = Version::from_str(full.unwrap_or(&format!("{major}.{minor}"))).ok()?;
+
+        Some(Self {
+            version,
+            format,
+            pointer_size,
+            gil_disabled,
+        })
+    }
+
+    /// The Python version, which may only include the major and minor version.
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn format(&self) -> BinaryFormat {
+        self.format
+    }
+
+    /// The pointer size of the binary, if it could be determined from its headers.
+    pub fn pointer_size(&self) -> Option<PointerSize> {
+        self.pointer_size
+    }
+
+    /// Whether the interpreter is a free-threaded build.
+    pub fn gil_disabled(&self) -> bool {
+        self.gil_disabled
+    }
+}
+
+impl fmt::Display for StaticProbe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Python {}", self.version)?;
+        if self.gil_disabled {
+            f.write_str(" (free-threaded)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Determine the executable format and pointer size of a binary from its headers.
+///
+/// Returns `None` if the file is not a known binary format, e.g., for scripts.
+fn binary_format(bytes: &[u8]) -> Option<(BinaryFormat, Option<PointerSize>)> {
+    match bytes {
+        [0x7f, b'E', b'L', b'F', class, ..] => {
+            let pointer_size = match class {
+                1 => Some(PointerSize::_32),
+                2 => Some(PointerSize::_64),
+                _ => None,
+            };
+            Some((BinaryFormat::Elf, pointer_size))
+        }
+        [0xce, 0xfa, 0xed, 0xfe, ..] => Some((BinaryFormat::MachO, Some(PointerSize::_32))),
+        [0xcf, 0xfa, 0xed, 0xfe, ..] => Some((BinaryFormat::MachO, Some(PointerSize::_64))),
+        // Universal binaries contain multiple architectures.
+        [0xca, 0xfe, 0xba, 0xbe, ..] => Some((BinaryFormat::MachO, None)),
+        [b'M', b'Z', ..] => {
+            // The offset of the PE header is stored at `0x3c`, followed by the machine type.
+            let offset =
+                usize::try_from(u32::from_le_bytes(bytes.get(0x3c..0x40)?.try_into().ok()?))
+                    .ok()?;
+            let pointer_size = match bytes.get(offset..offset + 6) {
+                Some([b'P', b'E', 0, 0, low, high]) => match u16::from_le_bytes([*low, *high]) {
+                    // `IMAGE_FILE_MACHINE_I386` and `IMAGE_FILE_MACHINE_ARMNT`.
+                    0x014c | 0x01c4 => Some(PointerSize::_32),
+                    // `IMAGE_FILE_MACHINE_AMD64` and `IMAGE_FILE_MACHINE_ARM64`.
+                    0x8664 | 0xaa64 => Some(PointerSize::_64),
+                    _ => None,
+                },
+                _ => None,
+            };
+            Some((BinaryFormat::Pe, pointer_size))
+        }
+        _ => None,
+    }
+}
+
+/// The `PT_LOAD` program header type, i.e., a segment that's mapped into memory.
+const PT_LOAD: usize = 1;
+/// The `PT_DYNAMIC` program header type, i.e., the dynamic linking information.
+const PT_DYNAMIC: usize = 2;
+/// The `PT_INTERP` program header type, i.e., the path of the dynamic loader.
+const PT_INTERP: usize = 3;
+
+/// The dynamic section tags for the end of the section, a needed library, the string table, and
+/// the (deprecated) rpath and runpath.
+const DT_NULL: usize = 0;
+const DT_NEEDED: usize = 1;
+const DT_STRTAB: usize = 5;
+const DT_RPATH: usize = 15;
+const DT_RUNPATH: usize = 29;
+
+/// The Mach-O load commands for a linked library and for a runpath.
+const LC_LOAD_DYLIB: usize = 0xc;
+const LC_LOAD_WEAK_DYLIB: usize = 0x8000_0018;
+const LC_RPATH: usize = 0x8000_001c;
+const LC_REEXPORT_DYLIB: usize = 0x8000_001f;
+
+/// An ELF binary, read without copying.
+struct Elf<'a> {
+    bytes: &'a [u8],
+    little_endian: bool,
+    pointer_size: PointerSize,
+}
+
+/// A program header of an ELF binary, i.e., the location of a segment.
+struct ProgramHeader {
+    kind: usize,
+    offset: usize,
+    address: usize,
+    size: usize,
+}
+
+impl<'a> Elf<'a> {
+    /// Returns `None` if the file isn't an ELF binary.
+    fn parse(bytes: &'a [u8]) -> Option<Self> {
+        let (BinaryFormat::Elf, Some(pointer_size)) = binary_format(bytes)? else {
+            return None;
+        };
+        let little_endian = match bytes.get(5)? {
+            1 => true,
+            2 => false,
+            _ => return None,
+        };
+        Some(Self {
+            bytes,
+            little_endian,
+            pointer_size,
+        })
+    }
+
+    /// The size of an address or offset, in bytes.
+    fn word(&self) -> usize {
+        match self.pointer_size {
+            PointerSize::_64 => 8,
+            PointerSize::_32 => 4,
+        }
+    }
+
+    /// Read an unsigned integer of `size` bytes at `offset`.
+    fn read(&self, offset: usize, size: usize) -> Option<usize> {
+        let field = self.bytes.get(offset..offset.checked_add(size)?)?;
+        let mut value: u64 = 0;
+        if self.little_endian {
+            for byte in field.iter().rev() {
+                value = (value << 8) | u64::from(*byte);
+            }
+        } else {
+            for byte in field {
+                value = (value << 8) | u64::from(*byte);
+            }
+        }
+        usize::try_from(value).ok()
+    }
+
+    /// Read a NUL-terminated string at `offset`.
+    fn string(&self, offset: usize) -> Option<&'a str> {
+        let bytes = self.bytes.get(offset..)?;
+        let end = bytes.iter().position(|byte| *byte == 0)?;
+        std::str::from_utf8(&bytes[..end]).ok()
+    }
+
+    /// Iterate over the program headers, stopping at the first truncated header.
+    fn program_headers(&self) -> impl Iterator<Item = ProgramHeader> + '_ {
+        // The offsets of the program headers in the file header, and of the fields in each header.
+        let (phoff, phentsize, phnum, p_offset, p_vaddr, p_filesz) = match self.pointer_size {
+            PointerSize::_64 => (0x20, 0x36, 0x38, 0x08, 0x10, 0x20),
+            PointerSize::_32 => (0x1c, 0x2a, 0x2c, 0x04, 0x08, 0x10),
+        };
+        let word = self.word();
+        let phoff = self.read(phoff, word);
+        let phentsize = self.read(phentsize, 2);
+        let phnum = self.read(phnum, 2).unwrap_or(0);
+        (0..phnum).map_while(move |index| {
+            let header = phoff?.checked_add(index.checked_mul(phentsize?)?)?;
+            Some(ProgramHeader {
+                kind: self.read(header, 4)?,
+                offset: self.read(header.checked_add(p_offset)?, word)?,
+                address: self.read(header.checked_add(p_vaddr)?, word)?,
+                size: self.read(header.checked_add(p_filesz)?, word)?,
+            })
+        })
+    }
+
+    /// Iterate over the entries of the dynamic section as tag and value pairs.
+    fn dynamic(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let word = self.word();
+        let (offset, size) = self
+            .program_headers()
+            .find(|header| header.kind == PT_DYNAMIC)
+            .map_or((0, 0), |header| (header.offset, header.size));
+        (0..size / (2 * word))
+            .map_while(move |index| {
+                let entry = offset.checked_add(index * 2 * word)?;
+                Some((
+                    self.read(entry, word)?,
+                    self.read(entry.checked_add(word)?, word)?,
+                ))
+            })
+            .take_while(|(tag, _)| *tag != DT_NULL)
+    }
+
+    /// Map a virtual address to its offset in the file, using the loaded segments.
+    fn file_offset(&self, address: usize) -> Option<usize> {
+        let header = self.program_headers().find(|header| {
+            header.kind == PT_LOAD
+                && header.address <= address
+                && address - header.address < header.size
+        })?;
+        (address - header.address).checked_add(header.offset)
+    }
+}
+
+/// Read the path of the dynamic loader, i.e., the `PT_INTERP` segment, of an ELF binary.
+///
+/// Returns `None` for statically linked binaries and files that aren't ELF binaries.
+pub(crate) fn elf_interpreter(bytes: &[u8]) -> Option<String> {
+    let elf = Elf::parse(bytes)?;
+    let header = elf
+        .program_headers()
+        .find(|header| header.kind == PT_INTERP)?;
+    let interpreter = bytes.get(header.offset..header.offset.checked_add(header.size)?)?;
+    let interpreter = interpreter.strip_suffix(b"\0").unwrap_or(interpreter);
+    std::str::from_utf8(interpreter)
+        .ok()
+        .map(ToString::to_string)
+}
+
+/// Find the shared libraries that a Python executable loads from `../lib` relative to its own
+/// path, e.g., `libpython3.12.so.1.0` for python-build-standalone builds, which use a
+/// `$ORIGIN/../lib` runpath on Linux and `@executable_path/../lib` on macOS.
+///
+/// Returns the paths of the libraries that exist in that directory. Interpreters that link against
+/// `libpython` in a system directory, statically linked interpreters, and scripts don't load any.
+pub fn relative_libraries(executable: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let Some(lib) = executable
+        .parent()
+        .and_then(Path::parent)
+        .map(|prefix| prefix.join("lib"))
+    else {
+        return Ok(Vec::new());
+    };
+    if fs_err::metadata(executable)?.len() > MAX_PROBE_SIZE {
+        return Ok(Vec::new());
+    }
+    let bytes = fs_err::read(executable)?;
+    Ok(relative_library_names(&bytes)
+        .into_iter()
+        .filter(|name| !name.contains('/'))
+        .map(|name| lib.join(name))
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+/// Read the names of the shared libraries a binary loads from `../lib` relative to its own path.
+fn relative_library_names(bytes: &[u8]) -> Vec<String> {
+    match binary_format(bytes) {
+        Some((BinaryFormat::Elf, _)) => elf_relative_libraries(bytes),
+        Some((BinaryFormat::MachO, _)) => macho_relative_libraries(bytes),
+        _ => None,
+    }
+    .unwrap_or_default()
+}
+
+/// Read the `DT_NEEDED` libraries of an ELF binary, if its runpath includes `$ORIGIN/../lib`.
+fn elf_relative_libraries(bytes: &[u8]) -> Option<Vec<String>> {
+    let elf = Elf::parse(bytes)?;
+    let dynamic = elf.dynamic().collect::<Vec<_>>();
+    let strtab = dynamic
+        .iter()
+        .find(|(tag, _)| *tag == DT_STRTAB)
+        .and_then(|(_, address)| elf.file_offset(*address))?;
+    let string = |offset: usize| elf.string(strtab.checked_add(offset)?);
+
+    let relative = dynamic
+        .iter()
+        .filter(|(tag, _)| matches!(*tag, DT_RPATH | DT_RUNPATH))
+        .filter_map(|(_, offset)| string(*offset))
+        .flat_map(|runpath| runpath.split(':'))
+        .any(|path| {
+            matches!(
+                path.trim_end_matches('/'),
+                "$ORIGIN/../lib" | "${ORIGIN}/../lib"
+            )
+        });
+    if !relative {
+        return Some(Vec::new());
+    }
+    Some(
+        dynamic
+            .iter()
+            .filter(|(tag, _)| *tag == DT_NEEDED)
+            .filter_map(|(_, offset)| string(*offset))
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+/// Read the libraries of a Mach-O binary that are loaded from `@executable_path/../lib`, either
+/// directly or through `@rpath` and a matching `LC_RPATH` command.
+///
+/// For universal binaries, the first architecture is read.
+fn macho_relative_libraries(bytes: &[u8]) -> Option<Vec<String>> {
+    let read = |offset: usize, big_endian: bool| -> Option<usize> {
+        let field: [u8; 4] = bytes.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+        let value = if big_endian {
+            u32::from_be_bytes(field)
+        } else {
+            u32::from_le_bytes(field)
+        };
+        usize::try_from(value).ok()
+    };
+    let header_size = match bytes.get(..4)? {
+        // Universal binaries start with a big-endian table of the architectures, i.e., their CPU
+        // type, subtype, offset, size, and alignment.
+        [0xca, 0xfe, 0xba, 0xbe] => {
+            if read(4, true)? == 0 {
+                return None;
+            }
+            let offset = read(16, true).filter(|offset| *offset > 0)?;
+            return macho_relative_libraries(bytes.get(offset..)?);
+        }
+        [0xce, 0xfa, 0xed, 0xfe] => 28,
+        [0xcf, 0xfa, 0xed, 0xfe] => 32,
+        _ => return None,
+    };
+
+    // The path in a load command is stored at an offset from the start of the command.
+    let path = |command: usize, size: usize| -> Option<&str> {
+        let start = command.checked_add(read(command.checked_add(8)?, false)?)?;
+        let path = bytes.get(start..command.checked_add(size)?)?;
+        let path = path.split(|byte| *byte == 0).next()?;
+        std::str::from_utf8(path).ok()
+    };
+    let mut libraries = Vec::new();
+    let mut rpaths = Vec::new();
+    let mut command = header_size;
+    for _ in 0..read(16, false)? {
+        let kind = read(command, false)?;
+        let size = read(command.checked_add(4)?, false).filter(|size| *size > 0)?;
+        match kind {
+            LC_LOAD_DYLIB | LC_LOAD_WEAK_DYLIB | LC_REEXPORT_DYLIB => {
+                libraries.extend(path(command, size));
+            }
+            LC_RPATH => rpaths.extend(path(command, size)),
+            _ => {}
+        }
+        command = command.checked_add(size)?;
+    }
+
+    let prefixes = ["@executable_path/../lib/", "@loader_path/../lib/"];
+    let relative_rpath = rpaths.iter().any(|rpath| {
+        prefixes
+            .iter()
+            .any(|prefix| rpath.trim_end_matches('/') == prefix.trim_end_matches('/'))
+    });
+    Some(
+        libraries
+            .into_iter()
+            .filter_map(|library| {
+                prefixes
+                    .iter()
+                    .find_map(|prefix| library.strip_prefix(prefix))
+                    .or_else(|| library.strip_prefix("@rpath/").filter(|_| relative_rpath))
+            })
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::str::FromStr;
+
+    use pep440_rs::Version;
+
+    use crate::pointer_size::PointerSize;
+
+    use super::{
+        elf_interpreter, relative_libraries, relative_library_names, BinaryFormat, StaticProbe,
+        LC_LOAD_DYLIB, LC_RPATH,
+    };
+
+    fn elf(contents: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x7fELF\x02\x01\x01".to_vec();
+        bytes.resize(64, 0);
+        bytes.extend_from_slice(contents);
+        bytes
+    }
+
+    #[test]
+    fn dynamically_linked() {
+        let probe = StaticProbe::from_bytes(&elf(b"\x00libpython3.12.so.1.0\x00")).unwrap();
+        assert_eq!(probe.version(), &Version::from_str("3.12").unwrap());
+        assert_eq!(probe.format(), BinaryFormat::Elf);
+        assert_eq!(probe.pointer_size(), Some(PointerSize::_64));
+        assert!(!probe.gil_disabled());
+    }
+
+    #[test]
+    fn statically_linked() {
+        // Versions of other libraries are ignored.
+        let probe = StaticProbe::from_bytes(&elf(
+            b"\x003.45.1\x00/install/lib/python3.13t\x00\x003.13.0rc1\x00",
+        ))
+        .unwrap();
+        assert_eq!(probe.version(), &Version::from_str("3.13.0rc1").unwrap());
+        assert!(probe.gil_disabled());
+        assert_eq!(probe.to_string(), "Python 3.13.0rc1 (free-threaded)");
+    }
+
+    #[test]
+    fn portable_executable() {
+        let mut bytes = b"MZ".to_vec();
+        bytes.resize(0x40, 0);
+        bytes[0x3c] = 0x40;
+        bytes.extend_from_slice(b"PE\x00\x00\x64\x86");
+        bytes.extend_from_slice(b"\x00python311.dll\x00");
+        let probe = StaticProbe::from_bytes(&bytes).unwrap();
+        assert_eq!(probe.version(), &Version::from_str("3.11").unwrap());
+        assert_eq!(probe.format(), BinaryFormat::Pe);
+        assert_eq!(probe.pointer_size(), Some(PointerSize::_64));
+    }
+
+    #[test]
+    fn not_a_binary() {
+        assert_eq!(
+            StaticProbe::from_bytes(b"#!/bin/sh\nexec python3.12 \"$@\"\n"),
+            None
+        );
+        assert_eq!(StaticProbe::from_bytes(&elf(b"\x00libc.so.6\x00")), None);
+    }
+
+    /// Build a 64-bit little-endian ELF binary with a single `PT_INTERP` program header.
+    fn elf_with_interpreter(interpreter: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x7fELF\x02\x01\x01".to_vec();
+        bytes.resize(0x40, 0);
+        // `e_phoff`, `e_phentsize` and `e_phnum`
+        bytes[0x20] = 0x40;
+        bytes[0x36] = 0x38;
+        bytes[0x38] = 1;
+        // `p_type`, `p_offset` and `p_filesz`
+        let mut header = vec![0; 0x38];
+        header[0] = 3;
+        header[0x08] = 0x78;
+        header[0x20] = u8::try_from(interpreter.len()).unwrap();
+        bytes.extend(header);
+        bytes.extend_from_slice(interpreter);
+        bytes
+    }
+
+    #[test]
+    fn detect_elf_interpreter() {
+        assert_eq!(
+            elf_interpreter(&elf_with_interpreter(b"/lib/ld-musl-x86_64.so.1\0")).as_deref(),
+            Some("/lib/ld-musl-x86_64.so.1")
+        );
+        assert_eq!(
+            elf_interpreter(&elf_with_interpreter(b"/lib64/ld-linux-x86-64.so.2\0")).as_deref(),
+            Some("/lib64/ld-linux-x86-64.so.2")
+        );
+        assert_eq!(elf_interpreter(b"#!/bin/sh\n"), None);
+        // Truncated binaries are rejected rather than read out of bounds.
+        assert_eq!(
+            elf_interpreter(&elf_with_interpreter(b"/lib/ld-musl-x86_64.so.1")[..0x80]),
+            None
+        );
+    }
+
+    /// Build a 64-bit little-endian ELF binary with a dynamic section listing the given needed
+    /// libraries and runpath.
+    pub(crate) fn elf_with_dynamic(needed: &[&str], runpath: Option<&str>) -> Vec<u8> {
+        // The address the file is loaded at, such that addresses differ from file offsets.
+        const BASE: u64 = 0x1000;
+        const DYNAMIC: usize = 0xb0;
+
+        let mut strings = vec![0];
+        let mut entries = Vec::new();
+        for (tag, string) in needed
+            .iter()
+            .map(|library| (1, library))
+            .chain(runpath.as_ref().map(|runpath| (29, runpath)))
+        {
+            entries.push((tag, u64::try_from(strings.len()).unwrap()));
+            strings.extend_from_slice(string.as_bytes());
+            strings.push(0);
+        }
+        let strtab = DYNAMIC + (entries.len() + 2) * 16;
+        entries.push((5, BASE + u64::try_from(strtab).unwrap()));
+        entries.push((0, 0));
+        let size = u64::try_from(strtab + strings.len()).unwrap();
+
+        let mut bytes = b"\x7fELF\x02\x01\x01".to_vec();
+        bytes.resize(0x40, 0);
+        // `e_phoff`, `e_phentsize` and `e_phnum`
+        bytes[0x20] = 0x40;
+        bytes[0x36] = 0x38;
+        bytes[0x38] = 2;
+        // `p_type`, `p_offset`, `p_vaddr` and `p_filesz` of the `PT_LOAD` and `PT_DYNAMIC` headers
+        for (kind, offset, size) in [(1, 0, size), (2, DYNAMIC as u64, 16 * entries.len() as u64)] {
+            let mut header = vec![0; 0x38];
+            header[..4].copy_from_slice(&u32::to_le_bytes(kind));
+            header[0x08..0x10].copy_from_slice(&u64::to_le_bytes(offset));
+            header[0x10..0x18].copy_from_slice(&u64::to_le_bytes(BASE + offset));
+            header[0x20..0x28].copy_from_slice(&u64::to_le_bytes(size));
+            bytes.extend(header);
+        }
+        for (tag, value) in entries {
+            bytes.extend_from_slice(&u64::to_le_bytes(tag));
+            bytes.extend_from_slice(&u64::to_le_bytes(value));
+        }
+        bytes.extend(strings);
+        bytes
+    }
+
+    /// Build a 64-bit Mach-O binary with the given load commands, i.e., a command type and path.
+    fn macho(commands: &[(usize, &str)]) -> Vec<u8> {
+        let mut bytes = b"\xcf\xfa\xed\xfe".to_vec();
+        bytes.resize(32, 0);
+        bytes[16..20].copy_from_slice(&u32::to_le_bytes(commands.len().try_into().unwrap()));
+        for (kind, path) in commands {
+            // The path follows the `dylib_command` or `rpath_command` structure.
+            let offset = if *kind == LC_RPATH { 12 } else { 24 };
+            let size = (offset + path.len() + 1).next_multiple_of(8);
+            let mut command = vec![0; size];
+            command[..4].copy_from_slice(&u32::to_le_bytes((*kind).try_into().unwrap()));
+            command[4..8].copy_from_slice(&u32::to_le_bytes(size.try_into().unwrap()));
+            command[8..12].copy_from_slice(&u32::to_le_bytes(offset.try_into().unwrap()));
+            command[offset..offset + path.len()].copy_from_slice(path.as_bytes());
+            bytes.extend(command);
+        }
+        bytes
+    }
+
+    #[test]
+    fn elf_relative_libraries() {
+        // python-build-standalone builds look up `libpython` relative to the executable.
+        assert_eq!(
+            relative_library_names(&elf_with_dynamic(
+                &["libpython3.12.so.1.0", "libc.so.6"],
+                Some("/opt/lib:$ORIGIN/../lib/"),
+            )),
+            ["libpython3.12.so.1.0", "libc.so.6"]
+        );
+
+        // Interpreters that link against a system `libpython` don't.
+        assert!(relative_library_names(&elf_with_dynamic(
+            &["libpython3.12.so.1.0"],
+            Some("/usr/lib")
+        ))
+        .is_empty());
+        assert!(
+            relative_library_names(&elf_with_dynamic(&["libpython3.12.so.1.0"], None)).is_empty()
+        );
+
+        // Strings outside the dynamic section are ignored.
+        assert!(
+            relative_library_names(&elf(b"\0$ORIGIN/../lib\0libpython3.12.so.1.0\0")).is_empty()
+        );
+    }
+
+    #[test]
+    fn macho_relative_libraries() {
+        let bytes = macho(&[
+            (LC_LOAD_DYLIB, "@executable_path/../lib/libpython3.12.dylib"),
+            (LC_LOAD_DYLIB, "/usr/lib/libSystem.B.dylib"),
+        ]);
+        assert_eq!(relative_library_names(&bytes), ["libpython3.12.dylib"]);
+
+        // Libraries loaded through `@rpath` are only relative with a matching runpath.
+        let bytes = macho(&[
+            (LC_LOAD_DYLIB, "@rpath/libpython3.12.dylib"),
+            (LC_RPATH, "@executable_path/../lib"),
+        ]);
+        assert_eq!(relative_library_names(&bytes), ["libpython3.12.dylib"]);
+        let bytes = macho(&[
+            (LC_LOAD_DYLIB, "@rpath/libpython3.12.dylib"),
+            (LC_RPATH, "/opt/python/lib"),
+        ]);
+        assert!(relative_library_names(&bytes).is_empty());
+
+        // Universal binaries are read from the first architecture.
+        let mut universal = b"\xca\xfe\xba\xbe\x00\x00\x00\x01".to_vec();
+        universal.resize(32, 0);
+        universal[16..20].copy_from_slice(&u32::to_be_bytes(32));
+        universal.extend(macho(&[(
+            LC_LOAD_DYLIB,
+            "@executable_path/../lib/libpython3.12.dylib",
+        )]));
+        assert_eq!(relative_library_names(&universal), ["libpython3.12.dylib"]);
+    }
+
+    #[test]
+    fn find_relative_libraries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let prefix = temp_dir.path();
+        fs_err::create_dir_all(prefix.join("bin")).unwrap();
+        fs_err::create_dir_all(prefix.join("lib")).unwrap();
+        for library in ["libpython3.12.so.1.0", "libtcl8.6.so"] {
+            fs_err::write(prefix.join("lib").join(library), library).unwrap();
+        }
+        let executable = prefix.join("bin").join("python3.12");
+
+        // Only the libraries the executable loads are returned, and those that aren't in `../lib`,
+        // e.g., `libc`, are loaded from a system directory instead.
+        fs_err::write(
+            &executable,
+            elf_with_dynamic(
+                &["libpython3.12.so.1.0", "libc.so.6"],
+                Some("$ORIGIN/../lib"),
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            relative_libraries(&executable).unwrap(),
+            [prefix.join("lib").join("libpython3.12.so.1.0")]
+        );
+
+        fs_err::write(&executable, "#!/bin/sh\nexec python3.12 \"$@\"\n").unwrap();
+        assert!(relative_libraries(&executable).unwrap().is_empty());
+    }
+}
@@ -5,9 +5,9 @@ use std::process::{Command, ExitStatus};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use thiserror::Error;
-use tracing::info_span;
+use tracing::{info_span, warn};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct PyListPath {
     pub(crate) major: u8,
     pub(crate) minor: u8,
@@ -30,18 +30,28 @@ pub enum Error {
     NotFound,
 }
 
-/// ```text
-/// -V:3.12          C:\Users\Ferris\AppData\Local\Programs\Python\Python312\python.exe
-/// -V:3.8           C:\Users\Ferris\AppData\Local\Programs\Python\Python38\python.exe
-/// ```
-static PY_LIST_PATHS: Lazy<Regex> = Lazy::new(|| {
-    // Without the `R` flag, paths have trailing \r
-    Regex::new(r"(?mR)^ -(?:V:)?(\d).(\d+)-?(?:arm)?\d*\s*\*?\s*(.*)$").unwrap()
-});
+/// A line of `py --list-paths` output that could not be used.
+///
+/// These are not fatal: the remaining entries are still returned.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PyListPathsWarning {
+    #[error("The output of `py --list-paths` is not UTF-8 encoded")]
+    NonUtf8,
+    #[error("Unrecognized Python version tag `{tag}` in `py --list-paths` output")]
+    UnknownTag { tag: String },
+    #[error("No executable path for Python version tag `{tag}` in `py --list-paths` output")]
+    MissingPath { tag: String },
+}
+
+/// The start of an absolute Windows path, e.g., `C:\` or `\\server\share`.
+static WINDOWS_PATH_START: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z]:[\\/]|\\\\").unwrap());
 
 /// Use the `py` launcher to find installed Python versions.
 ///
 /// Calls `py --list-paths`.
+///
+/// Entries that cannot be parsed are skipped with a warning rather than failing the entire
+/// source, since the output format has changed across releases of the launcher.
 pub(crate) fn py_list_paths() -> Result<Vec<PyListPath>, Error> {
     // konstin: The command takes 8ms on my machine.
     let output = info_span!("py_list_paths")
@@ -67,28 +77,250 @@ pub(crate) fn py_list_paths() -> Result<Vec<PyListPath>, Error> {
         });
     }
 
-    // Find the first python of the version we want in the list
-    let stdout = String::from_utf8(output.stdout).map_err(|err| Error::StatusCode {
-        message: format!("The stdout of `py --list-paths` isn't UTF-8 encoded: {err}"),
-        exit_code: output.status,
-        stdout: String::from_utf8_lossy(err.as_bytes()).trim().to_string(),
-        stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
-    })?;
-
-    Ok(PY_LIST_PATHS
-        .captures_iter(&stdout)
-        .filter_map(|captures| {
-            let (_, [major, minor, path]) = captures.extract();
-            if let (Some(major), Some(minor)) = (major.parse::<u8>().ok(), minor.parse::<u8>().ok())
-            {
-                Some(PyListPath {
-                    major,
-                    minor,
-                    executable_path: PathBuf::from(path),
-                })
-            } else {
-                None
-            }
-        })
-        .collect())
+    // Some locales produce output in a legacy code page; decode lossily so that ASCII paths are
+    // still usable.
+    let mut warnings = Vec::new();
+    let stdout = match String::from_utf8(output.stdout) {
+        Ok(stdout) => stdout,
+        Err(err) => {
+            warnings.push(PyListPathsWarning::NonUtf8);
+            String::from_utf8_lossy(err.as_bytes()).into_owned()
+        }
+    };
+
+    let (entries, parse_warnings) = parse_py_list_paths(&stdout);
+    for warning in warnings.iter().chain(&parse_warnings) {
+        warn!("{warning}");
+    }
+
+    Ok(entries)
+}
+
+/// Parse the output of `py --list-paths`.
+///
+/// Supports the formats of the legacy launcher (Python 3.9 and 3.10):
+///
+/// ```text
+/// Installed Pythons found by py Launcher for Windows
+///  -3.10-64       C:\Users\Ferris\AppData\Local\Programs\Python\Python310\python.exe *
+///  -3.9-32        C:\Users\Ferris\AppData\Local\Programs\Python\Python39-32\python.exe
+/// ```
+///
+/// And of the PEP 514 launcher (Python 3.11 and newer):
+///
+/// ```text
+///  -V:3.12 *        C:\Users\Ferris\AppData\Local\Programs\Python\Python312\python.exe
+///  -V:3.8           C:\Users\Ferris\AppData\Local\Programs\Python\Python38\python.exe
+/// ```
+///
+/// Lines that do not start with a `-` (e.g., headers in any locale) are ignored. Entries with
+/// a tag that is not a Python version or without an executable path are returned as warnings.
+pub(crate) fn parse_py_list_paths(stdout: &str) -> (Vec<PyListPath>, Vec<PyListPathsWarning>) {
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+
+    for line in stdout.lines() {
+        let line = line.trim();
+        let Some(line) = line.strip_prefix('-') else {
+            continue;
+        };
+
+        // The tag is the first column, e.g. `V:3.12`, `3.9-64` or `V:Company/Tag`.
+        let (tag, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+
+        // Any columns between the tag and the path (e.g., the `*` default marker) are skipped.
+        let Some(start) = WINDOWS_PATH_START.find(rest).map(|found| found.start()) else {
+            warnings.push(PyListPathsWarning::MissingPath {
+                tag: tag.to_string(),
+            });
+            continue;
+        };
+        // The legacy launcher marks the default interpreter with a trailing `*`.
+        let path = rest[start..].trim_end_matches('*').trim_end();
+
+        let Some((major, minor)) = parse_tag(tag) else {
+            warnings.push(PyListPathsWarning::UnknownTag {
+                tag: tag.to_string(),
+            });
+            continue;
+        };
+
+        entries.push(PyListPath {
+            major,
+            minor,
+            executable_path: PathBuf::from(path),
+        });
+    }
+
+    (entries, warnings)
+}
+
+/// Parse the major and minor version from a `py --list-paths` tag.
+///
+/// e.g., `V:3.12`, `V:3.13t`, `V:3.11-arm64`, `V:PythonCore/3.12`, or `3.9-64`.
+fn parse_tag(tag: &str) -> Option<(u8, u8)> {
+    let tag = tag.strip_prefix("V:").unwrap_or(tag);
+
+    // Only the `PythonCore` company uses the Python version as its tag.
+    let tag = match tag.split_once('/') {
+        Some((company, tag)) if company.eq_ignore_ascii_case("PythonCore") => tag,
+        Some(_) => return None,
+        None => tag,
+    };
+
+    // Drop any suffix, e.g., the architecture or free-threading marker.
+    let end = tag
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(tag.len());
+    let (major, minor) = tag[..end].split_once('.')?;
+    let minor = minor.split('.').next()?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use indoc::indoc;
+
+    use super::{parse_py_list_paths, PyListPath, PyListPathsWarning};
+
+    fn entry(major: u8, minor: u8, path: &str) -> PyListPath {
+        PyListPath {
+            major,
+            minor,
+            executable_path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn legacy_launcher_py39() {
+        let stdout = indoc! {r"
+            Installed Pythons found by py Launcher for Windows
+             -3.9-64        C:\Users\Ferris\AppData\Local\Programs\Python\Python39\python.exe *
+             -3.8-32        C:\Users\Ferris\AppData\Local\Programs\Python\Python38-32\python.exe
+        "};
+        let (entries, warnings) = parse_py_list_paths(stdout);
+        assert_eq!(
+            entries,
+            vec![
+                entry(
+                    3,
+                    9,
+                    r"C:\Users\Ferris\AppData\Local\Programs\Python\Python39\python.exe"
+                ),
+                entry(
+                    3,
+                    8,
+                    r"C:\Users\Ferris\AppData\Local\Programs\Python\Python38-32\python.exe"
+                ),
+            ]
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn legacy_launcher_py310_crlf() {
+        let stdout = "Installed Pythons found by py Launcher for Windows\r\n -3.10-64       C:\\Program Files\\Python310\\python.exe *\r\n -3.9-64        C:\\Program Files\\Python39\\python.exe\r\n";
+        let (entries, warnings) = parse_py_list_paths(stdout);
+        assert_eq!(
+            entries,
+            vec![
+                entry(3, 10, r"C:\Program Files\Python310\python.exe"),
+                entry(3, 9, r"C:\Program Files\Python39\python.exe"),
+            ]
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn pep514_launcher_py311() {
+        let stdout = indoc! {r"
+             -V:3.11 *        C:\Users\Ferris\AppData\Local\Programs\Python\Python311\python.exe
+             -V:3.10          C:\Users\Ferris\AppData\Local\Programs\Python\Python310\python.exe
+        "};
+        let (entries, warnings) = parse_py_list_paths(stdout);
+        assert_eq!(
+            entries,
+            vec![
+                entry(
+                    3,
+                    11,
+                    r"C:\Users\Ferris\AppData\Local\Programs\Python\Python311\python.exe"
+                ),
+                entry(
+                    3,
+                    10,
+                    r"C:\Users\Ferris\AppData\Local\Programs\Python\Python310\python.exe"
+                ),
+            ]
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn pep514_launcher_py312_companies() {
+        let stdout = indoc! {r"
+             -V:3.12 *        C:\Python312\python.exe
+             -V:3.12-arm64    C:\Python312-arm64\python.exe
+             -V:PythonCore/3.11 C:\Python311\python.exe
+             -V:ContinuumAnalytics/Anaconda39-64 C:\Users\Ferris\anaconda3\python.exe
+        "};
+        let (entries, warnings) = parse_py_list_paths(stdout);
+        assert_eq!(
+            entries,
+            vec![
+                entry(3, 12, r"C:\Python312\python.exe"),
+                entry(3, 12, r"C:\Python312-arm64\python.exe"),
+                entry(3, 11, r"C:\Python311\python.exe"),
+            ]
+        );
+        assert_eq!(
+            warnings,
+            vec![PyListPathsWarning::UnknownTag {
+                tag: "V:ContinuumAnalytics/Anaconda39-64".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn pep514_launcher_py313_free_threaded() {
+        let stdout = indoc! {r"
+             -V:3.13 *        C:\Python313\python.exe
+             -V:3.13t         C:\Python313\python3.13t.exe
+        "};
+        let (entries, warnings) = parse_py_list_paths(stdout);
+        assert_eq!(
+            entries,
+            vec![
+                entry(3, 13, r"C:\Python313\python.exe"),
+                entry(3, 13, r"C:\Python313\python3.13t.exe"),
+            ]
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn unknown_columns_and_missing_paths() {
+        let stdout = indoc! {r"
+            Gefundene Python-Installationen
+             -V:3.14 * (Standard) 64-bit C:\Python314\python.exe
+             -V:3.12          (nicht verfügbar)
+        "};
+        let (entries, warnings) = parse_py_list_paths(stdout);
+        assert_eq!(entries, vec![entry(3, 14, r"C:\Python314\python.exe")]);
+        assert_eq!(
+            warnings,
+            vec![PyListPathsWarning::MissingPath {
+                tag: "V:3.12".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn no_installations() {
+        let (entries, warnings) = parse_py_list_paths("No Installed Pythons Found!\n");
+        assert!(entries.is_empty());
+        assert!(warnings.is_empty());
+    }
 }
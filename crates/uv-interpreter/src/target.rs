@@ -5,39 +5,130 @@ use pypi_types::Scheme;
 /// A `--target` directory into which packages can be installed, separate from a virtual environment
 /// or system Python interpreter.
 #[derive(Debug, Clone)]
-pub struct Target(PathBuf);
+pub struct Target {
+    root: PathBuf,
+    layout: TargetLayout,
+}
+
+/// The layout of the files installed into a `--target` directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TargetLayout {
+    /// Install packages directly into the `--target` directory, as with `pip install --target`,
+    /// with scripts in `bin` and headers in `include`.
+    #[default]
+    Flat,
+    /// Install packages with the full scheme of a virtual environment under the `--target`
+    /// directory, e.g., into `lib/python3.12/site-packages`, with scripts in `bin` (or `Scripts`
+    /// on Windows), such that the location of each kind of file is predictable.
+    Scheme,
+}
 
 impl Target {
+    /// Set the [`TargetLayout`] of the `--target` directory.
+    #[must_use]
+    pub fn with_layout(mut self, layout: TargetLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Return the [`TargetLayout`] of the `--target` directory.
+    pub fn layout(&self) -> TargetLayout {
+        self.layout
+    }
+
     /// Return the [`Scheme`] for the `--target` directory.
-    pub fn scheme(&self) -> Scheme {
-        Scheme {
-            purelib: self.0.clone(),
-            platlib: self.0.clone(),
-            scripts: self.0.join("bin"),
-            data: self.0.clone(),
-            include: self.0.join("include"),
+    ///
+    /// The virtual environment scheme of the interpreter is used for the [`TargetLayout::Scheme`]
+    /// layout.
+    pub fn scheme(&self, virtualenv: &Scheme) -> Scheme {
+        match self.layout {
+            TargetLayout::Flat => Scheme {
+                purelib: self.root.clone(),
+                platlib: self.root.clone(),
+                scripts: self.root.join("bin"),
+                data: self.root.clone(),
+                include: self.root.join("include"),
+            },
+            TargetLayout::Scheme => Scheme {
+                purelib: self.root.join(&virtualenv.purelib),
+                platlib: self.root.join(&virtualenv.platlib),
+                scripts: self.root.join(&virtualenv.scripts),
+                data: self.root.join(&virtualenv.data),
+                include: self.root.join(&virtualenv.include),
+            },
         }
     }
 
     /// Return an iterator over the `site-packages` directories inside the environment.
-    pub fn site_packages(&self) -> impl Iterator<Item = &Path> {
-        std::iter::once(self.0.as_path())
+    pub fn site_packages(&self, virtualenv: &Scheme) -> impl Iterator<Item = PathBuf> {
+        let scheme = self.scheme(virtualenv);
+        let platlib = (scheme.platlib != scheme.purelib).then_some(scheme.platlib);
+        std::iter::once(scheme.purelib).chain(platlib)
     }
 
     /// Initialize the `--target` directory.
     pub fn init(&self) -> std::io::Result<()> {
-        fs_err::create_dir_all(&self.0)?;
+        fs_err::create_dir_all(&self.root)?;
         Ok(())
     }
 
     /// Return the path to the `--target` directory.
     pub fn root(&self) -> &Path {
-        &self.0
+        &self.root
     }
 }
 
 impl From<PathBuf> for Target {
     fn from(path: PathBuf) -> Self {
-        Self(path)
+        Self {
+            root: path,
+            layout: TargetLayout::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pypi_types::Scheme;
+
+    use super::{Target, TargetLayout};
+
+    #[test]
+    fn target_layout() {
+        let virtualenv = Scheme {
+            purelib: PathBuf::from("lib/python3.12/site-packages"),
+            platlib: PathBuf::from("lib/python3.12/site-packages"),
+            scripts: PathBuf::from("bin"),
+            data: PathBuf::new(),
+            include: PathBuf::from("include/site/python3.12"),
+        };
+
+        let target = Target::from(PathBuf::from("/target"));
+        assert_eq!(target.layout(), TargetLayout::Flat);
+        let scheme = target.scheme(&virtualenv);
+        assert_eq!(scheme.purelib, PathBuf::from("/target"));
+        assert_eq!(scheme.scripts, PathBuf::from("/target/bin"));
+        assert_eq!(
+            target.site_packages(&virtualenv).collect::<Vec<_>>(),
+            vec![PathBuf::from("/target")]
+        );
+
+        let target = target.with_layout(TargetLayout::Scheme);
+        let scheme = target.scheme(&virtualenv);
+        assert_eq!(
+            scheme.purelib,
+            PathBuf::from("/target/lib/python3.12/site-packages")
+        );
+        assert_eq!(scheme.scripts, PathBuf::from("/target/bin"));
+        assert_eq!(
+            scheme.include,
+            PathBuf::from("/target/include/site/python3.12")
+        );
+        assert_eq!(
+            target.site_packages(&virtualenv).collect::<Vec<_>>(),
+            vec![PathBuf::from("/target/lib/python3.12/site-packages")]
+        );
     }
 }
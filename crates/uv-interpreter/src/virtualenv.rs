@@ -1,11 +1,26 @@
 use std::{
-    env, io,
+    env, fmt, io,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 use fs_err as fs;
+use indexmap::IndexMap;
+use pep440_rs::Version;
+use pep508_rs::PackageName;
 use pypi_types::Scheme;
 use thiserror::Error;
+use tracing::debug;
+
+use uv_cache::Cache;
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+
+use crate::discovery::{
+    find_interpreter, InterpreterRequest, SourceSelector, SystemPython, VersionRequest,
+};
+use crate::implementation::ImplementationName;
+use crate::Interpreter;
 
 /// The layout of a virtual environment.
 #[derive(Debug)]
@@ -23,18 +38,406 @@ pub struct VirtualEnvironment {
 
 /// A parsed `pyvenv.cfg`
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct PyVenvConfiguration {
     /// If the `virtualenv` package was used to create the virtual environment.
     pub(crate) virtualenv: bool,
     /// If the `uv` package was used to create the virtual environment.
     pub(crate) uv: bool,
+    /// The version of the tool that created the virtual environment, i.e., the value of the `uv`
+    /// or `virtualenv` key.
+    pub(crate) tool_version: Option<String>,
+    /// The directory containing the base interpreter's executable, i.e., the `home` key.
+    pub(crate) home: Option<PathBuf>,
+    /// The implementation of the base interpreter, e.g., `CPython`.
+    pub(crate) implementation: Option<String>,
+    /// The base interpreter's executable, i.e., the `base-executable` key written by uv.
+    pub(crate) base_executable: Option<PathBuf>,
+    /// The source revision the base interpreter was built from, i.e., the `build-revision` key
+    /// written by uv.
+    pub(crate) build_revision: Option<String>,
+    /// The Python version of the base interpreter, i.e., the `version_info` or `version` key.
+    pub(crate) version: Option<String>,
+    /// Whether the base interpreter's `site-packages` is visible, i.e., the
+    /// `include-system-site-packages` key.
+    pub(crate) include_system_site_packages: bool,
+    /// Whether the base interpreter is a free-threaded build, i.e., the `free-threaded` key.
+    pub(crate) free_threaded: bool,
+    /// All keys in the file, in order, including those that aren't recognized above.
+    pub(crate) entries: IndexMap<String, String>,
+}
+
+/// A problem with a virtual environment, as detected by [`VirtualEnvironment::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VirtualEnvironmentProblem {
+    /// The `pyvenv.cfg` file is missing.
+    MissingPyVenvCfg,
+    /// The Python executable is a symbolic link to an interpreter that no longer exists, e.g.,
+    /// because the base interpreter was uninstalled.
+    DanglingExecutable {
+        executable: PathBuf,
+        target: PathBuf,
+    },
+    /// The Python executable is missing.
+    MissingExecutable(PathBuf),
+    /// The Python executable could not be queried, e.g., because the base interpreter's standard
+    /// library was removed.
+    BrokenExecutable { executable: PathBuf, reason: String },
+    /// The Python version of the interpreter doesn't match the version recorded in `pyvenv.cfg`,
+    /// e.g., because the base interpreter was replaced by a different minor version.
+    VersionMismatch { recorded: String, found: String },
+    /// The `site-packages` directory is missing.
+    MissingSitePackages(PathBuf),
+}
+
+impl VirtualEnvironmentProblem {
+    /// Returns `true` if the problem can be fixed by [`VirtualEnvironment::repair`], i.e., by
+    /// relinking the environment to a compatible interpreter.
+    pub fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            Self::DanglingExecutable { .. }
+                | Self::MissingExecutable(_)
+                | Self::BrokenExecutable { .. }
+                | Self::VersionMismatch { .. }
+        )
+    }
+}
+
+impl fmt::Display for VirtualEnvironmentProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPyVenvCfg => f.write_str("`pyvenv.cfg` is missing"),
+            Self::DanglingExecutable { executable, target } => write!(
+                f,
+                "`{}` links to `{}`, which does not exist",
+                executable.user_display(),
+                target.user_display()
+            ),
+            Self::MissingExecutable(executable) => {
+                write!(f, "`{}` does not exist", executable.user_display())
+            }
+            Self::BrokenExecutable { executable, reason } => {
+                write!(f, "`{}` is not usable: {reason}", executable.user_display())
+            }
+            Self::VersionMismatch { recorded, found } => write!(
+                f,
+                "`pyvenv.cfg` records Python {recorded}, but the interpreter is Python {found}"
+            ),
+            Self::MissingSitePackages(site_packages) => {
+                write!(f, "`{}` does not exist", site_packages.user_display())
+            }
+        }
+    }
+}
+
+impl VirtualEnvironment {
+    /// Read the layout of an existing virtual environment from its `pyvenv.cfg`, without querying
+    /// its interpreter, e.g., to validate an environment whose interpreter is broken.
+    pub fn from_root(root: impl Into<PathBuf>) -> Result<Self, Error> {
+        let root = root.into();
+        let cfg = root.join("pyvenv.cfg");
+        if !cfg.is_file() {
+            return Err(Error::MissingPyVenvCfg(root));
+        }
+        let cfg = PyVenvConfiguration::parse(cfg)?;
+
+        let (scripts, site_packages) = if cfg!(windows) {
+            (root.join("Scripts"), root.join("Lib").join("site-packages"))
+        } else {
+            (root.join("bin"), unix_site_packages(&root, &cfg))
+        };
+
+        // The interpreter may be a dangling link to a deleted base interpreter, which
+        // `virtualenv_python_executable` skips over since it follows links.
+        let executable = Some(virtualenv_python_executable(&root))
+            .filter(|executable| executable.exists())
+            .or_else(|| {
+                ["python3", "python"]
+                    .into_iter()
+                    .map(|name| scripts.join(name))
+                    .find(|executable| executable.is_symlink())
+            })
+            .unwrap_or_else(|| virtualenv_python_executable(&root));
+
+        Ok(Self {
+            executable,
+            scheme: Scheme {
+                purelib: site_packages.clone(),
+                platlib: site_packages,
+                scripts,
+                data: root.clone(),
+                include: root.join("include"),
+            },
+            root,
+        })
+    }
+
+    /// Detect problems with the virtual environment, e.g., a dangling symbolic link to a deleted
+    /// base interpreter.
+    ///
+    /// Returns an empty list if the environment is intact.
+    pub fn validate(&self, cache: &Cache) -> Result<Vec<VirtualEnvironmentProblem>, Error> {
+        let mut problems = Vec::new();
+
+        let cfg = self.root.join("pyvenv.cfg");
+        let cfg = if cfg.is_file() {
+            Some(PyVenvConfiguration::parse(cfg)?)
+        } else {
+            problems.push(VirtualEnvironmentProblem::MissingPyVenvCfg);
+            None
+        };
+
+        if self.executable.try_exists()? {
+            match Interpreter::query(&self.executable, cache) {
+                Ok(interpreter) => {
+                    if let Some(recorded) = cfg.as_ref().and_then(PyVenvConfiguration::version) {
+                        let found = interpreter.python_version();
+                        let matches = Version::from_str(recorded).is_ok_and(|recorded| {
+                            recorded.release().get(..2) == found.release().get(..2)
+                        });
+                        if !matches {
+                            problems.push(VirtualEnvironmentProblem::VersionMismatch {
+                                recorded: recorded.to_string(),
+                                found: found.to_string(),
+                            });
+                        }
+                    }
+                }
+                Err(err) => problems.push(VirtualEnvironmentProblem::BrokenExecutable {
+                    executable: self.executable.clone(),
+                    reason: err.to_string(),
+                }),
+            }
+        } else if fs::symlink_metadata(&self.executable)
+            .is_ok_and(|metadata| metadata.file_type().is_symlink())
+        {
+            problems.push(VirtualEnvironmentProblem::DanglingExecutable {
+                executable: self.executable.clone(),
+                target: fs::read_link(&self.executable)?,
+            });
+        } else {
+            problems.push(VirtualEnvironmentProblem::MissingExecutable(
+                self.executable.clone(),
+            ));
+        }
+
+        if !self.scheme.purelib.is_dir() {
+            problems.push(VirtualEnvironmentProblem::MissingSitePackages(
+                self.scheme.purelib.clone(),
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    /// Repair the virtual environment by relinking it to a compatible interpreter, i.e., a system
+    /// interpreter with the implementation and minor version recorded in `pyvenv.cfg`.
+    ///
+    /// Returns the interpreter the environment was relinked to, or `None` if no compatible
+    /// interpreter was found.
+    pub fn repair(
+        &self,
+        preview: PreviewMode,
+        cache: &Cache,
+    ) -> Result<Option<Interpreter>, crate::Error> {
+        let cfg = PyVenvConfiguration::parse(self.root.join("pyvenv.cfg"))?;
+        let Some(version) = cfg
+            .version()
+            .and_then(|version| Version::from_str(version).ok())
+        else {
+            return Ok(None);
+        };
+        let (Some(major), Some(minor)) = (
+            version
+                .release()
+                .first()
+                .and_then(|part| u8::try_from(*part).ok()),
+            version
+                .release()
+                .get(1)
+                .and_then(|part| u8::try_from(*part).ok()),
+        ) else {
+            return Ok(None);
+        };
+        let version = VersionRequest::MajorMinor(major, minor);
+        let request = match cfg
+            .implementation()
+            .and_then(|implementation| ImplementationName::from_str(implementation).ok())
+        {
+            Some(implementation) => {
+                InterpreterRequest::ImplementationVersion(implementation, version)
+            }
+            None => InterpreterRequest::Version(version),
+        };
+
+        let Ok(found) = find_interpreter(
+            &request,
+            SystemPython::Allowed,
+            &SourceSelector::System(preview),
+            cache,
+        )?
+        else {
+            return Ok(None);
+        };
+        let interpreter = found.into_interpreter();
+        self.relink(&interpreter)?;
+        Ok(Some(interpreter))
+    }
+
+    /// Relink the virtual environment to the given base interpreter.
+    ///
+    /// On Unix, the Python executables are replaced, preserving whether they were symbolic links
+    /// or copies. Copies are accompanied by the shared libraries the base interpreter loads
+    /// relative to itself, see [`crate::relative_libraries`]. On Windows, the launcher reads the
+    /// base interpreter from `pyvenv.cfg`, so only `pyvenv.cfg` is updated.
+    fn relink(&self, interpreter: &Interpreter) -> Result<(), Error> {
+        let base_python = interpreter.resolved_executable()?;
+        let Some(home) = base_python.parent() else {
+            return Err(Error::IO(io::Error::new(
+                io::ErrorKind::NotFound,
+                "The Python interpreter needs to have a parent directory",
+            )));
+        };
+        debug!(
+            "Relinking `{}` to `{}`",
+            self.root.user_display(),
+            base_python.user_display()
+        );
+
+        #[cfg(unix)]
+        {
+            let python = self.scheme.scripts.join("python");
+            let copies =
+                fs::symlink_metadata(&python).is_ok_and(|metadata| metadata.file_type().is_file());
+            if copies {
+                for name in [
+                    "python".to_string(),
+                    format!("python{}", interpreter.python_major()),
+                    format!(
+                        "python{}.{}",
+                        interpreter.python_major(),
+                        interpreter.python_minor()
+                    ),
+                ] {
+                    let path = self.scheme.scripts.join(name);
+                    if fs::symlink_metadata(&path)
+                        .is_ok_and(|metadata| metadata.file_type().is_file())
+                    {
+                        fs::remove_file(&path)?;
+                        fs::copy(&base_python, &path)?;
+                    }
+                }
+
+                let libraries = crate::relative_libraries(&base_python)?;
+                if !libraries.is_empty() {
+                    let lib = self.root.join("lib");
+                    fs::create_dir_all(&lib)?;
+                    for library in libraries {
+                        let Some(name) = library.file_name() else {
+                            continue;
+                        };
+                        let path = lib.join(name);
+                        match fs::remove_file(&path) {
+                            Ok(()) => {}
+                            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                            Err(err) => return Err(err.into()),
+                        }
+                        fs::copy(&library, &path)?;
+                    }
+                }
+            } else {
+                uv_fs::replace_symlink(&base_python, &python)?;
+            }
+        }
+
+        update_pyvenv_cfg(
+            &self.root.join("pyvenv.cfg"),
+            &[
+                ("home", home.simplified_display().to_string()),
+                ("version_info", interpreter.python_version().to_string()),
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+impl VirtualEnvironment {
+    /// Give or revoke access to the system `site-packages` by updating
+    /// `include-system-site-packages` in `pyvenv.cfg`.
+    ///
+    /// Returns `true` if the setting changed, or `false` if it already had the requested value.
+    pub fn set_system_site_packages(&self, enabled: bool) -> Result<bool, Error> {
+        let path = self.root.join("pyvenv.cfg");
+        if !path.is_file() {
+            return Err(Error::MissingPyVenvCfg(self.root.clone()));
+        }
+        let cfg = PyVenvConfiguration::parse(&path)?;
+        if cfg.include_system_site_packages() == enabled {
+            return Ok(false);
+        }
+
+        debug!(
+            "{} system site packages for `{}`",
+            if enabled { "Enabling" } else { "Disabling" },
+            self.root.user_display()
+        );
+        update_pyvenv_cfg(
+            &path,
+            &[("include-system-site-packages", enabled.to_string())],
+        )?;
+        Ok(true)
+    }
+}
+
+/// Determine the `site-packages` directory of a virtual environment on Unix from its
+/// `pyvenv.cfg`, e.g., `lib/python3.12/site-packages`.
+fn unix_site_packages(root: &Path, cfg: &PyVenvConfiguration) -> PathBuf {
+    let lib = root.join("lib");
+    let prefix = if cfg
+        .implementation()
+        .is_some_and(|implementation| implementation.eq_ignore_ascii_case("pypy"))
+    {
+        "pypy"
+    } else {
+        "python"
+    };
+    if let Some(version) = cfg
+        .version()
+        .and_then(|version| Version::from_str(version).ok())
+    {
+        if let [major, minor, ..] = version.release() {
+            // Free-threaded builds use, e.g., `lib/python3.13t`. Environments created by other
+            // tools don't record the `free-threaded` key, so also check for the directory.
+            let free_threaded = lib
+                .join(format!("{prefix}{major}.{minor}t"))
+                .join("site-packages");
+            if cfg.is_free_threaded() || free_threaded.is_dir() {
+                return free_threaded;
+            }
+            return lib
+                .join(format!("{prefix}{major}.{minor}"))
+                .join("site-packages");
+        }
+    }
+    lib.join("site-packages")
+}
+
+/// Update the given keys in a `pyvenv.cfg` file, preserving all other keys. Keys that are not
+/// present are appended.
+fn update_pyvenv_cfg(path: &Path, updates: &[(&str, String)]) -> Result<(), Error> {
+    let mut cfg = PyVenvConfiguration::parse(path)?;
+    for (key, value) in updates {
+        cfg.set(*key, value.clone());
+    }
+    cfg.write(path)
 }
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("Broken virtualenv `{0}`: `pyvenv.cfg` is missing")]
+    #[error("Broken virtualenv `{}`: `pyvenv.cfg` is missing", _0.user_display())]
     MissingPyVenvCfg(PathBuf),
-    #[error("Broken virtualenv `{0}`: `pyvenv.cfg` could not be parsed")]
+    #[error("Broken virtualenv `{}`: `pyvenv.cfg` could not be parsed", _0.user_display())]
     ParsePyVenvCfg(PathBuf, #[source] io::Error),
     #[error(transparent)]
     IO(#[from] io::Error),
@@ -67,6 +470,9 @@ pub(crate) fn conda_prefix_from_env() -> Option<PathBuf> {
 /// Searches for a `.venv` directory in the current or any parent directory. If the current
 /// directory is itself a virtual environment (or a subdirectory of a virtual environment), the
 /// containing virtual environment is returned.
+///
+/// Within a member of a uv workspace, the `.venv` at the workspace root is preferred over a
+/// `.venv` in the member, so that all members share one environment.
 pub(crate) fn virtualenv_from_working_dir() -> Result<Option<PathBuf>, Error> {
     let current_dir = crate::current_dir()?;
 
@@ -82,6 +488,22 @@ pub(crate) fn virtualenv_from_working_dir() -> Result<Option<PathBuf>, Error> {
             if !dot_venv.join("pyvenv.cfg").is_file() {
                 return Err(Error::MissingPyVenvCfg(dot_venv));
             }
+
+            // If the environment belongs to a workspace member, use the workspace environment.
+            if let Some(workspace_root) = workspace_root(&current_dir) {
+                let workspace_venv = workspace_root.join(".venv");
+                if workspace_root != dir
+                    && dir.starts_with(&workspace_root)
+                    && workspace_venv.join("pyvenv.cfg").is_file()
+                {
+                    debug!(
+                        "Preferring the workspace environment at `{}`",
+                        workspace_venv.user_display()
+                    );
+                    return Ok(Some(workspace_venv));
+                }
+            }
+
             return Ok(Some(dot_venv));
         }
     }
@@ -89,6 +511,133 @@ pub(crate) fn virtualenv_from_working_dir() -> Result<Option<PathBuf>, Error> {
     Ok(None)
 }
 
+/// Returns the root of the uv workspace that the project containing `dir` is a member of, if any.
+///
+/// This follows the workspace discovery of `uv-distribution` (which depends on this crate), such
+/// that at most two `pyproject.toml` files are read: the project is the closest directory with a
+/// `pyproject.toml`, and it is the workspace root if it declares a `tool.uv.workspace` table.
+/// Otherwise, the closest `pyproject.toml` above it must declare a workspace whose `members`
+/// globs include the project and whose `exclude` globs don't.
+///
+/// Unreadable or invalid `pyproject.toml` files are ignored; they are reported once the
+/// workspace itself is loaded.
+fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    let project_root = dir
+        .ancestors()
+        .find(|dir| dir.join("pyproject.toml").is_file())?;
+    let project = read_pyproject_toml(project_root)?;
+    if workspace_table(&project).is_some() {
+        return Some(project_root.to_path_buf());
+    }
+
+    let workspace_root = project_root
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("pyproject.toml").is_file())?;
+    let workspace = read_pyproject_toml(workspace_root)?;
+    let workspace = workspace_table(&workspace)?;
+    let relative = project_root.strip_prefix(workspace_root).ok()?;
+    is_workspace_member(relative, project_name(&project).as_ref(), workspace)
+        .then(|| workspace_root.to_path_buf())
+}
+
+/// Read the `pyproject.toml` in the given directory.
+fn read_pyproject_toml(dir: &Path) -> Option<toml::Table> {
+    let contents = fs::read_to_string(dir.join("pyproject.toml")).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+/// Returns the `tool.uv.workspace` table of a `pyproject.toml`, if any.
+fn workspace_table(pyproject_toml: &toml::Table) -> Option<&toml::Table> {
+    pyproject_toml
+        .get("tool")
+        .and_then(|tool| tool.get("uv"))
+        .and_then(|uv| uv.get("workspace"))
+        .and_then(toml::Value::as_table)
+}
+
+/// Returns the `project.name` of a `pyproject.toml`, if any.
+fn project_name(pyproject_toml: &toml::Table) -> Option<PackageName> {
+    pyproject_toml
+        .get("project")
+        .and_then(|project| project.get("name"))
+        .and_then(toml::Value::as_str)
+        .and_then(|name| PackageName::from_str(name).ok())
+}
+
+/// Returns `true` if the project at the `relative` path from the workspace root is a member of
+/// the workspace.
+///
+/// The project must be matched by the `members` globs, where the last matching glob must not be
+/// negated, and it must not be excluded by path or by name. Conditional members whose environment
+/// variable isn't set are skipped.
+fn is_workspace_member(
+    relative: &Path,
+    name: Option<&PackageName>,
+    workspace: &toml::Table,
+) -> bool {
+    let matches = |pattern: &str| {
+        glob::Pattern::new(pattern).is_ok_and(|pattern| {
+            pattern.matches_path_with(
+                relative,
+                glob::MatchOptions {
+                    require_literal_separator: true,
+                    ..glob::MatchOptions::new()
+                },
+            )
+        })
+    };
+
+    let excluded = workspace
+        .get("exclude")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+        .any(|exclude| {
+            matches(exclude)
+                || name.is_some_and(|name| {
+                    PackageName::from_str(exclude).is_ok_and(|exclude| exclude == *name)
+                })
+        });
+    if excluded {
+        return false;
+    }
+
+    let mut member = false;
+    for glob in workspace
+        .get("members")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let pattern = match glob {
+            toml::Value::String(pattern) => pattern.as_str(),
+            toml::Value::Table(table) => {
+                let enabled = table
+                    .get("env")
+                    .and_then(toml::Value::as_str)
+                    .map_or(true, |var| {
+                        env::var_os(var).is_some_and(|value| !value.is_empty())
+                    });
+                match table.get("path").and_then(toml::Value::as_str) {
+                    Some(pattern) if enabled => pattern,
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        if let Some(negated) = pattern.strip_prefix('!') {
+            if matches(negated) {
+                member = false;
+            }
+        } else if matches(pattern) {
+            member = true;
+        }
+    }
+    member
+}
+
 /// Returns the path to the `python` executable inside a virtual environment.
 pub(crate) fn virtualenv_python_executable(venv: impl AsRef<Path>) -> PathBuf {
     let venv = venv.as_ref();
@@ -134,30 +683,116 @@ pub(crate) fn virtualenv_python_executable(venv: impl AsRef<Path>) -> PathBuf {
 impl PyVenvConfiguration {
     /// Parse a `pyvenv.cfg` file into a [`PyVenvConfiguration`].
     pub fn parse(cfg: impl AsRef<Path>) -> Result<Self, Error> {
-        let mut virtualenv = false;
-        let mut uv = false;
-
         // Per https://snarky.ca/how-virtual-environments-work/, the `pyvenv.cfg` file is not a
         // valid INI file, and is instead expected to be parsed by partitioning each line on the
         // first equals sign.
         let content = fs::read_to_string(&cfg)
             .map_err(|err| Error::ParsePyVenvCfg(cfg.as_ref().to_path_buf(), err))?;
-        for line in content.lines() {
-            let Some((key, _value)) = line.split_once('=') else {
-                continue;
-            };
-            match key.trim() {
+        let entries = content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Interpret the recognized keys of a `pyvenv.cfg` file.
+    fn from_entries(entries: IndexMap<String, String>) -> Self {
+        let mut virtualenv = false;
+        let mut uv = false;
+        let mut tool_version = None;
+        let mut home = None;
+        let mut implementation = None;
+        let mut base_executable = None;
+        let mut build_revision = None;
+        let mut version = None;
+        let mut include_system_site_packages = false;
+        let mut free_threaded = false;
+
+        for (key, value) in &entries {
+            match key.as_str() {
                 "virtualenv" => {
                     virtualenv = true;
+                    tool_version = Some(value.clone());
                 }
                 "uv" => {
                     uv = true;
+                    tool_version = Some(value.clone());
+                }
+                "home" => {
+                    home = Some(PathBuf::from(value));
+                }
+                "implementation" => {
+                    implementation = Some(value.clone());
+                }
+                "base-executable" => {
+                    base_executable = Some(PathBuf::from(value));
+                }
+                "build-revision" => {
+                    build_revision = Some(value.clone());
+                }
+                "version_info" => {
+                    version = Some(value.clone());
+                }
+                // Written by `venv`, while `virtualenv` and uv write `version_info`.
+                "version" => {
+                    version.get_or_insert_with(|| value.clone());
+                }
+                "include-system-site-packages" => {
+                    include_system_site_packages = value.eq_ignore_ascii_case("true");
+                }
+                "free-threaded" => {
+                    free_threaded = value.eq_ignore_ascii_case("true");
                 }
                 _ => {}
             }
         }
 
-        Ok(Self { virtualenv, uv })
+        Self {
+            virtualenv,
+            uv,
+            tool_version,
+            home,
+            implementation,
+            base_executable,
+            build_revision,
+            version,
+            include_system_site_packages,
+            free_threaded,
+            entries,
+        }
+    }
+
+    /// Write the configuration to a `pyvenv.cfg` file, atomically replacing any existing file.
+    pub fn write(&self, cfg: impl AsRef<Path>) -> Result<(), Error> {
+        uv_fs::write_atomic_sync(cfg, self.to_string())?;
+        Ok(())
+    }
+
+    /// Returns all keys and their values, in the order they appear in the file, including keys
+    /// that have no dedicated accessor.
+    pub fn entries(&self) -> &IndexMap<String, String> {
+        &self.entries
+    }
+
+    /// Returns the value of the given key, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Set the value of the given key, appending the key if it's not already present.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.insert(key.into(), value.into());
+        *self = Self::from_entries(entries);
+    }
+
+    /// Remove the given key, returning its value, if present.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let mut entries = std::mem::take(&mut self.entries);
+        let value = entries.shift_remove(key);
+        *self = Self::from_entries(entries);
+        value
     }
 
     /// Returns true if the virtual environment was created with the `virtualenv` package.
@@ -169,4 +804,226 @@ impl PyVenvConfiguration {
     pub fn is_uv(&self) -> bool {
         self.uv
     }
+
+    /// Returns the directory containing the base interpreter of the virtual environment, if set.
+    pub fn home(&self) -> Option<&Path> {
+        self.home.as_deref()
+    }
+
+    /// Returns the version of the tool (`uv` or `virtualenv`) that created the virtual
+    /// environment, if recorded.
+    pub fn tool_version(&self) -> Option<&str> {
+        self.tool_version.as_deref()
+    }
+
+    /// Returns the implementation of the base interpreter, e.g., `CPython`, if recorded.
+    pub fn implementation(&self) -> Option<&str> {
+        self.implementation.as_deref()
+    }
+
+    /// Returns the base interpreter's executable, if recorded.
+    pub fn base_executable(&self) -> Option<&Path> {
+        self.base_executable.as_deref()
+    }
+
+    /// Returns the source revision the base interpreter was built from, e.g., a `CPython` git
+    /// commit hash, if recorded.
+    pub fn build_revision(&self) -> Option<&str> {
+        self.build_revision.as_deref()
+    }
+
+    /// Returns the Python version of the base interpreter, if recorded.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns `true` if the environment has access to the system `site-packages`.
+    pub fn include_system_site_packages(&self) -> bool {
+        self.include_system_site_packages
+    }
+
+    /// Returns `true` if the base interpreter is a free-threaded build.
+    pub fn is_free_threaded(&self) -> bool {
+        self.free_threaded
+    }
+}
+
+impl fmt::Display for PyVenvConfiguration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in &self.entries {
+            writeln!(f, "{key} = {value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use uv_cache::Cache;
+
+    use super::{PyVenvConfiguration, VirtualEnvironment, VirtualEnvironmentProblem};
+
+    #[test]
+    #[cfg(unix)]
+    fn validate_broken_environment() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = Cache::temp().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs_err::create_dir_all(venv.join("bin")).unwrap();
+        fs_err::write(
+            venv.join("pyvenv.cfg"),
+            "home = /opt/python/bin\nimplementation = CPython\nversion_info = 3.12.3\n",
+        )
+        .unwrap();
+        let target = temp_dir.path().join("python3.12");
+        std::os::unix::fs::symlink(&target, venv.join("bin").join("python")).unwrap();
+
+        let virtualenv = VirtualEnvironment::from_root(&venv).unwrap();
+        let site_packages = venv.join("lib").join("python3.12").join("site-packages");
+        assert_eq!(virtualenv.scheme.purelib, site_packages);
+
+        let problems = virtualenv.validate(&cache).unwrap();
+        assert_eq!(
+            problems,
+            vec![
+                VirtualEnvironmentProblem::DanglingExecutable {
+                    executable: venv.join("bin").join("python"),
+                    target,
+                },
+                VirtualEnvironmentProblem::MissingSitePackages(site_packages),
+            ]
+        );
+        assert!(problems[0].is_repairable());
+        assert!(!problems[1].is_repairable());
+    }
+
+    #[test]
+    fn update_pyvenv_cfg() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cfg = temp_dir.path().join("pyvenv.cfg");
+        fs_err::write(&cfg, "home = /old/bin\nuv = 0.2.5\n").unwrap();
+        super::update_pyvenv_cfg(
+            &cfg,
+            &[
+                ("home", "/new/bin".to_string()),
+                ("version_info", "3.12.4".to_string()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            fs_err::read_to_string(&cfg).unwrap(),
+            "home = /new/bin\nuv = 0.2.5\nversion_info = 3.12.4\n"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn free_threaded_layout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs_err::create_dir_all(&venv).unwrap();
+        fs_err::write(
+            venv.join("pyvenv.cfg"),
+            "home = /usr/bin\nversion_info = 3.13.0\nfree-threaded = true\n",
+        )
+        .unwrap();
+
+        let virtualenv = VirtualEnvironment::from_root(&venv).unwrap();
+        assert_eq!(
+            virtualenv.scheme.purelib,
+            venv.join("lib").join("python3.13t").join("site-packages")
+        );
+    }
+
+    #[test]
+    fn set_system_site_packages() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs_err::create_dir_all(&venv).unwrap();
+        fs_err::write(
+            venv.join("pyvenv.cfg"),
+            "home = /usr/bin\ninclude-system-site-packages = false\nversion_info = 3.12.3\n",
+        )
+        .unwrap();
+        let virtualenv = VirtualEnvironment::from_root(&venv).unwrap();
+
+        assert!(!virtualenv.set_system_site_packages(false).unwrap());
+        assert!(virtualenv.set_system_site_packages(true).unwrap());
+        let cfg = PyVenvConfiguration::parse(venv.join("pyvenv.cfg")).unwrap();
+        assert!(cfg.include_system_site_packages());
+        assert_eq!(cfg.home(), Some(Path::new("/usr/bin")));
+
+        assert!(!virtualenv.set_system_site_packages(true).unwrap());
+        assert!(virtualenv.set_system_site_packages(false).unwrap());
+        let cfg = PyVenvConfiguration::parse(venv.join("pyvenv.cfg")).unwrap();
+        assert!(!cfg.include_system_site_packages());
+    }
+
+    #[test]
+    fn parse_provenance() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cfg = temp_dir.path().join("pyvenv.cfg");
+        fs_err::write(
+            &cfg,
+            "home = /usr/bin\n\
+             implementation = CPython\n\
+             uv = 0.2.5\n\
+             version_info = 3.12.3\n\
+             include-system-site-packages = false\n\
+             base-executable = /usr/bin/python3.12\n\
+             build-revision = f6650f9ad7\n",
+        )
+        .unwrap();
+
+        let cfg = PyVenvConfiguration::parse(cfg).unwrap();
+        assert!(cfg.is_uv());
+        assert!(!cfg.is_virtualenv());
+        assert_eq!(cfg.tool_version(), Some("0.2.5"));
+        assert_eq!(cfg.home(), Some(Path::new("/usr/bin")));
+        assert_eq!(cfg.implementation(), Some("CPython"));
+        assert_eq!(
+            cfg.base_executable(),
+            Some(Path::new("/usr/bin/python3.12"))
+        );
+        assert_eq!(cfg.build_revision(), Some("f6650f9ad7"));
+    }
+
+    #[test]
+    fn write_back() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cfg = temp_dir.path().join("pyvenv.cfg");
+        fs_err::write(
+            &cfg,
+            "home = /usr/bin\n\
+             include-system-site-packages = false\n\
+             version = 3.12.3\n\
+             prompt = my-project\n",
+        )
+        .unwrap();
+
+        let mut pyvenv_cfg = PyVenvConfiguration::parse(&cfg).unwrap();
+        assert_eq!(pyvenv_cfg.get("prompt"), Some("my-project"));
+        assert_eq!(
+            pyvenv_cfg.entries().keys().collect::<Vec<_>>(),
+            vec!["home", "include-system-site-packages", "version", "prompt"]
+        );
+
+        pyvenv_cfg.set("include-system-site-packages", "true");
+        pyvenv_cfg.set("uv", "0.2.5");
+        assert_eq!(pyvenv_cfg.remove("version"), Some("3.12.3".to_string()));
+        assert!(pyvenv_cfg.include_system_site_packages());
+        assert!(pyvenv_cfg.is_uv());
+        assert_eq!(pyvenv_cfg.version(), None);
+
+        pyvenv_cfg.write(&cfg).unwrap();
+        assert_eq!(
+            fs_err::read_to_string(&cfg).unwrap(),
+            "home = /usr/bin\n\
+             include-system-site-packages = true\n\
+             prompt = my-project\n\
+             uv = 0.2.5\n"
+        );
+    }
 }
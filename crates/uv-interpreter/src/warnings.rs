@@ -0,0 +1,120 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use pep440_rs::Version;
+use uv_fs::Simplified;
+use uv_warnings::warn_user_once;
+
+/// The sink for user-facing warnings, if any.
+///
+/// See [`set_warning_sink`].
+static SINK: RwLock<Option<Arc<dyn WarningSink>>> = RwLock::new(None);
+
+/// A user-facing warning emitted during interpreter discovery.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// The discovered interpreter has a Python version that is not supported by uv.
+    UnsupportedPython(Version),
+    /// The managed toolchain pinned in a `.python-version` file is not installed.
+    MissingPinnedToolchain { key: String, path: PathBuf },
+    /// The `.python-version` file pinning a managed toolchain could not be read.
+    InvalidToolchainPin(String),
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedPython(version) => write!(
+                f,
+                "uv is only compatible with Python 3.8+, found Python {version}."
+            ),
+            Self::MissingPinnedToolchain { key, path } => write!(
+                f,
+                "The toolchain `{key}` pinned in `{}` is not installed.",
+                path.user_display()
+            ),
+            Self::InvalidToolchainPin(err) => write!(f, "Ignoring the toolchain pin: {err}"),
+        }
+    }
+}
+
+/// A receiver of user-facing warnings, e.g., to display them in the UI of an IDE.
+///
+/// Implemented for closures, e.g., `|warning| log(warning.to_string())`.
+pub trait WarningSink: Send + Sync {
+    /// Handle a warning.
+    ///
+    /// Unlike [`warn_user_once!`], warnings are not de-duplicated before they are passed to the
+    /// sink.
+    fn warn(&self, warning: &Warning);
+}
+
+impl<F> WarningSink for F
+where
+    F: Fn(&Warning) + Send + Sync,
+{
+    fn warn(&self, warning: &Warning) {
+        self(warning);
+    }
+}
+
+/// Set the process-wide sink for user-facing warnings.
+///
+/// By default (or when reset with `None`), warnings are written to stderr with
+/// [`warn_user_once!`].
+pub fn set_warning_sink(sink: Option<Arc<dyn WarningSink>>) {
+    if let Ok(mut current) = SINK.write() {
+        *current = sink;
+    }
+}
+
+/// Emit a user-facing warning to the configured [`WarningSink`].
+pub(crate) fn warn(warning: &Warning) {
+    let sink = SINK.read().ok().and_then(|sink| sink.clone());
+    if let Some(sink) = sink {
+        sink.warn(warning);
+    } else {
+        warn_user_once!("{warning}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+
+    use pep440_rs::Version;
+
+    use super::{set_warning_sink, warn, Warning};
+
+    #[test]
+    fn warning_sink() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let received = received.clone();
+            move |warning: &Warning| received.lock().unwrap().push(warning.clone())
+        };
+        set_warning_sink(Some(Arc::new(sink)));
+
+        let warning = Warning::UnsupportedPython(Version::from_str("3.7.9").unwrap());
+        warn(&warning);
+        warn(&warning);
+        set_warning_sink(None);
+
+        // Warnings from other tests may be received as well.
+        let received = received.lock().unwrap();
+        assert_eq!(
+            received
+                .iter()
+                .filter(|received| **received == warning)
+                .count(),
+            2
+        );
+        assert_eq!(
+            warning.to_string(),
+            "uv is only compatible with Python 3.8+, found Python 3.7.9."
+        );
+    }
+}
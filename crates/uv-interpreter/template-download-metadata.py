@@ -46,6 +46,8 @@ def prepare_value(value: dict) -> dict:
     for key in ["arch", "os", "libc"]:
         value[key] = value[key].title()
     value["name"] = prepare_name(value["name"])
+    # Render booleans as Rust literals
+    value["free_threaded"] = "true" if value["free_threaded"] else "false"
     return value
 
 
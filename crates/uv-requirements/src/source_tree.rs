@@ -180,7 +180,8 @@ impl<'a, Context: BuildContext> SourceTreeResolver<'a, Context> {
         // manual match.
         let hashes = match self.hasher {
             HashStrategy::None => HashPolicy::None,
-            HashStrategy::Generate => HashPolicy::Generate,
+            // Hashes can only be pinned for remote archives, so there's nothing to verify.
+            HashStrategy::Generate | HashStrategy::Verify { .. } => HashPolicy::Generate,
             HashStrategy::Validate { .. } => {
                 return Err(anyhow::anyhow!(
                     "Hash-checking is not supported for local directories: {}",
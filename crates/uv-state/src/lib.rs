@@ -97,12 +97,15 @@ impl StateStore {
 pub enum StateBucket {
     // Managed toolchain
     Toolchains,
+    // Named virtual environments
+    Environments,
 }
 
 impl StateBucket {
     fn to_str(self) -> &'static str {
         match self {
             Self::Toolchains => "toolchains",
+            Self::Environments => "envs",
         }
     }
 }
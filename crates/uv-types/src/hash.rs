@@ -18,6 +18,9 @@ pub enum HashStrategy {
     /// Hashes should be validated against a pre-defined list of hashes. If necessary, hashes should
     /// be generated so as to ensure that the archive is valid.
     Validate(FxHashMap<PackageId, Vec<HashDigest>>),
+    /// Hashes should be validated for packages with a pre-defined list of hashes, and generated
+    /// (but not validated) for all other packages, which are allowed regardless.
+    Verify(FxHashMap<PackageId, Vec<HashDigest>>),
 }
 
 impl HashStrategy {
@@ -32,6 +35,7 @@ impl HashStrategy {
                     .map(Vec::as_slice)
                     .unwrap_or_default(),
             ),
+            Self::Verify(hashes) => Self::verify(hashes, &distribution.package_id()),
         }
     }
 
@@ -46,6 +50,7 @@ impl HashStrategy {
                     .map(Vec::as_slice)
                     .unwrap_or_default(),
             ),
+            Self::Verify(hashes) => Self::verify(hashes, &PackageId::from_registry(name.clone())),
         }
     }
 
@@ -60,6 +65,7 @@ impl HashStrategy {
                     .map(Vec::as_slice)
                     .unwrap_or_default(),
             ),
+            Self::Verify(hashes) => Self::verify(hashes, &PackageId::from_url(url)),
         }
     }
 
@@ -69,6 +75,7 @@ impl HashStrategy {
             Self::None => true,
             Self::Generate => true,
             Self::Validate(hashes) => hashes.contains_key(&PackageId::from_registry(name.clone())),
+            Self::Verify(_) => true,
         }
     }
 
@@ -78,9 +85,21 @@ impl HashStrategy {
             Self::None => true,
             Self::Generate => true,
             Self::Validate(hashes) => hashes.contains_key(&PackageId::from_url(url)),
+            Self::Verify(_) => true,
         }
     }
 
+    /// Validate the package against its hashes, if any, and generate them otherwise.
+    fn verify<'a>(
+        hashes: &'a FxHashMap<PackageId, Vec<HashDigest>>,
+        id: &PackageId,
+    ) -> HashPolicy<'a> {
+        hashes
+            .get(id)
+            .map(Vec::as_slice)
+            .map_or(HashPolicy::Generate, HashPolicy::Validate)
+    }
+
     /// Generate the required hashes from a set of [`UnresolvedRequirement`] entries.
     ///
     /// When the environment is not given, this treats all marker expressions
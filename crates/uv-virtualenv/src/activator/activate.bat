@@ -19,6 +19,14 @@
 @REM OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 @REM WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+@REM This file is UTF-8 encoded, so we need to update the current code page while executing it
+@for /f "tokens=2 delims=:." %%a in ('"%SystemRoot%\System32\chcp.com"') do (
+    @set _OLD_CODEPAGE=%%a
+)
+@if defined _OLD_CODEPAGE (
+    @"%SystemRoot%\System32\chcp.com" 65001 > nul
+)
+
 @set "VIRTUAL_ENV={{ VIRTUAL_ENV_DIR }}"
 
 @set "VIRTUAL_ENV_PROMPT={{ VIRTUAL_PROMPT }}"
@@ -57,3 +65,9 @@
 :ENDIFVPATH2
 
 @set "PATH=%VIRTUAL_ENV%\{{ BIN_NAME }};%PATH%"
+{{ ACTIVATE_ENV }}
+
+@if defined _OLD_CODEPAGE (
+    @"%SystemRoot%\System32\chcp.com" %_OLD_CODEPAGE% > nul
+    @set _OLD_CODEPAGE=
+)
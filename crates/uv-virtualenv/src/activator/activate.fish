@@ -51,6 +51,7 @@ function deactivate -d 'Exit virtualenv mode and return to the normal environmen
         set -gx PYTHONHOME "$_OLD_VIRTUAL_PYTHONHOME"
         set -e _OLD_VIRTUAL_PYTHONHOME
     end
+{{ DEACTIVATE_ENV }}
 
     if test -n "$_OLD_FISH_PROMPT_OVERRIDE"
        and functions -q _old_fish_prompt
@@ -79,7 +80,7 @@ end
 # Unset irrelevant variables.
 deactivate nondestructive
 
-set -gx VIRTUAL_ENV '{{ VIRTUAL_ENV_DIR }}'
+set -gx VIRTUAL_ENV {{ VIRTUAL_ENV_DIR }}
 
 # https://github.com/fish-shell/fish-shell/issues/436 altered PATH handling
 if test (echo $FISH_VERSION | head -c 1) -lt 3
@@ -91,8 +92,8 @@ set -gx PATH "$VIRTUAL_ENV"'/{{ BIN_NAME }}' $PATH
 
 # Prompt override provided?
 # If not, just use the environment name.
-if test -n '{{ VIRTUAL_PROMPT }}'
-    set -gx VIRTUAL_ENV_PROMPT '{{ VIRTUAL_PROMPT }}'
+if test -n {{ VIRTUAL_PROMPT }}
+    set -gx VIRTUAL_ENV_PROMPT {{ VIRTUAL_PROMPT }}
 else
     set -gx VIRTUAL_ENV_PROMPT (basename "$VIRTUAL_ENV")
 end
@@ -102,6 +103,7 @@ if set -q PYTHONHOME
     set -gx _OLD_VIRTUAL_PYTHONHOME $PYTHONHOME
     set -e PYTHONHOME
 end
+{{ ACTIVATE_ENV }}
 
 function pydoc
     python -m pydoc $argv
@@ -40,6 +40,7 @@ function global:deactivate([switch] $NonDestructive) {
     if ($env:VIRTUAL_ENV_PROMPT) {
         Remove-Item env:VIRTUAL_ENV_PROMPT -ErrorAction SilentlyContinue
     }
+{{ DEACTIVATE_ENV }}
 
     if (!$NonDestructive) {
         # Self destruct!
@@ -58,8 +59,8 @@ deactivate -nondestructive
 $VIRTUAL_ENV = $BASE_DIR
 $env:VIRTUAL_ENV = $VIRTUAL_ENV
 
-if ("{{ VIRTUAL_PROMPT }}" -ne "") {
-    $env:VIRTUAL_ENV_PROMPT = "{{ VIRTUAL_PROMPT }}"
+if ({{ VIRTUAL_PROMPT }} -ne "") {
+    $env:VIRTUAL_ENV_PROMPT = {{ VIRTUAL_PROMPT }}
 }
 else {
     $env:VIRTUAL_ENV_PROMPT = $( Split-Path $env:VIRTUAL_ENV -Leaf )
@@ -68,6 +69,7 @@ else {
 New-Variable -Scope global -Name _OLD_VIRTUAL_PATH -Value $env:PATH
 
 $env:PATH = "$env:VIRTUAL_ENV/{{ BIN_NAME }}{{ PATH_SEP }}" + $env:PATH
+{{ ACTIVATE_ENV }}
 if (!$env:VIRTUAL_ENV_DISABLE_PROMPT) {
     function global:_old_virtual_prompt {
         ""
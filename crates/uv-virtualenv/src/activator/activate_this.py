@@ -46,11 +46,12 @@ base = bin_dir[: -len("{{ BIN_NAME }}") - 1]  # strip away the bin part from the
 # prepend bin to PATH (this file is inside the bin directory)
 os.environ["PATH"] = os.pathsep.join([bin_dir, *os.environ.get("PATH", "").split(os.pathsep)])
 os.environ["VIRTUAL_ENV"] = base  # virtual env is right above bin directory
-os.environ["VIRTUAL_ENV_PROMPT"] = "{{ VIRTUAL_PROMPT }}" or os.path.basename(base)  # noqa: SIM222
+os.environ["VIRTUAL_ENV_PROMPT"] = {{ VIRTUAL_PROMPT }} or os.path.basename(base)  # noqa: SIM222
+{{ ACTIVATE_ENV }}
 
 # add the virtual environments libraries to the host python import mechanism
 prev_length = len(sys.path)
-for lib in "{{ RELATIVE_SITE_PACKAGES }}".split(os.pathsep):
+for lib in {{ RELATIVE_SITE_PACKAGES }}.split(os.pathsep):
     path = os.path.realpath(os.path.join(bin_dir, lib))
     site.addsitedir(path)
 sys.path[:] = sys.path[prev_length:] + sys.path[0:prev_length]
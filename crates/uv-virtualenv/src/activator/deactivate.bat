@@ -36,4 +36,5 @@
 @if not defined _OLD_VIRTUAL_PATH @goto ENDIFVPATH
     @set "PATH=%_OLD_VIRTUAL_PATH%"
     @set _OLD_VIRTUAL_PATH=
-:ENDIFVPATH
\ No newline at end of file
+:ENDIFVPATH
+{{ DEACTIVATE_ENV }}
\ No newline at end of file
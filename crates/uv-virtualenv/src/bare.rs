@@ -4,37 +4,269 @@ use std::env;
 use std::env::consts::EXE_SUFFIX;
 use std::io;
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use fs_err as fs;
 use fs_err::File;
 use itertools::Itertools;
-use tracing::info;
+use tracing::{debug, info, trace};
 
 use pypi_types::Scheme;
 use uv_fs::{cachedir, Simplified};
 use uv_interpreter::{Interpreter, VirtualEnvironment};
 use uv_version::version;
 
-use crate::{Error, Prompt};
-
-/// The bash activate scripts with the venv dependent paths patches out
-const ACTIVATE_TEMPLATES: &[(&str, &str)] = &[
-    ("activate", include_str!("activator/activate")),
-    ("activate.csh", include_str!("activator/activate.csh")),
-    ("activate.fish", include_str!("activator/activate.fish")),
-    ("activate.nu", include_str!("activator/activate.nu")),
-    ("activate.ps1", include_str!("activator/activate.ps1")),
-    ("activate.bat", include_str!("activator/activate.bat")),
-    ("deactivate.bat", include_str!("activator/deactivate.bat")),
-    ("pydoc.bat", include_str!("activator/pydoc.bat")),
+use crate::{Error, OnExisting, Permissions, Prompt, VenvBuilder};
+
+/// The name of the `.pth` file in `site-packages` with the extra `sys.path` entries of a venv.
+const EXTRA_PATHS_PTH: &str = "_uv_extra_paths.pth";
+
+/// The name of the `.pth` file in `site-packages` that adds the DLL directories of a conda base
+/// interpreter.
+const CONDA_DLLS_PTH: &str = "_uv_conda_dlls.pth";
+
+/// The bash activate scripts with the venv dependent paths patches out, and the quoting rules
+/// for the values substituted into them.
+const ACTIVATE_TEMPLATES: &[(&str, &str, Quoting)] = &[
+    (
+        "activate",
+        include_str!("activator/activate"),
+        Quoting::Posix,
+    ),
+    (
+        "activate.csh",
+        include_str!("activator/activate.csh"),
+        Quoting::Csh,
+    ),
+    (
+        "activate.fish",
+        include_str!("activator/activate.fish"),
+        Quoting::Fish,
+    ),
+    (
+        "activate.nu",
+        include_str!("activator/activate.nu"),
+        Quoting::Nushell,
+    ),
+    (
+        "activate.ps1",
+        include_str!("activator/activate.ps1"),
+        Quoting::PowerShell,
+    ),
+    (
+        "activate.bat",
+        include_str!("activator/activate.bat"),
+        Quoting::Batch,
+    ),
+    (
+        "deactivate.bat",
+        include_str!("activator/deactivate.bat"),
+        Quoting::Batch,
+    ),
+    (
+        "pydoc.bat",
+        include_str!("activator/pydoc.bat"),
+        Quoting::Batch,
+    ),
     (
         "activate_this.py",
         include_str!("activator/activate_this.py"),
+        Quoting::Python,
     ),
 ];
 const VIRTUALENV_PATCH: &str = include_str!("_virtualenv.py");
 
+/// The quoting rules of the language an activation script is written in.
+///
+/// Paths and prompts are substituted into the activation scripts as string literals, so that
+/// spaces, quotes, and shell metacharacters in them are preserved verbatim.
+#[derive(Debug, Copy, Clone)]
+enum Quoting {
+    /// `sh`, `bash`, and `zsh`.
+    Posix,
+    Csh,
+    Fish,
+    Nushell,
+    PowerShell,
+    /// `cmd.exe`, where values are substituted into an already quoted `set "NAME=value"`.
+    Batch,
+    Python,
+}
+
+impl Quoting {
+    /// Quote a value as a string literal.
+    fn quote(self, value: &str) -> String {
+        match self {
+            // Like Python's `shlex.quote`, close the quotes around single quotes.
+            Self::Posix => format!("'{}'", value.replace('\'', r"'\''")),
+            // `!` triggers history substitution in `csh`, even within single quotes.
+            Self::Csh => format!("'{}'", value.replace('\'', r"'\''").replace('!', r"\!")),
+            Self::Fish => format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'")),
+            Self::Nushell | Self::Python => {
+                format!("\"{}\"", value.replace('\\', r"\\").replace('"', r#"\""#))
+            }
+            // PowerShell also treats typographic single quotes as quotes, which are escaped by
+            // doubling them.
+            Self::PowerShell => {
+                let mut quoted = String::with_capacity(value.len() + 2);
+                quoted.push('\'');
+                for c in value.chars() {
+                    if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                        quoted.push(c);
+                    }
+                    quoted.push(c);
+                }
+                quoted.push('\'');
+                quoted
+            }
+            Self::Batch => value.replace('%', "%%"),
+        }
+    }
+
+    /// The commands that export the given environment variables on activation, saving their
+    /// previous values to be restored by [`Quoting::deactivate_env`].
+    ///
+    /// The names are expected to be valid identifiers, i.e., `[A-Za-z_][A-Za-z0-9_]*`.
+    fn activate_env(self, env: &[(String, String)]) -> String {
+        let mut script = String::new();
+        for (name, value) in env {
+            let value = self.quote(value);
+            let lines = match self {
+                Self::Posix => format!(
+                    "if ! [ -z \"${{{name}+_}}\" ] ; then\n    \
+                     _OLD_VIRTUAL_ENV_{name}=\"${name}\"\n\
+                     fi\n\
+                     _VIRTUAL_ENV_SET_{name}=1\n\
+                     {name}={value}\n\
+                     export {name}\n"
+                ),
+                Self::Csh => format!(
+                    "if ( $?{name} ) then\n    \
+                     set _OLD_VIRTUAL_ENV_{name}=\"${name}:q\"\n\
+                     endif\n\
+                     set _VIRTUAL_ENV_SET_{name}=1\n\
+                     setenv {name} {value}\n"
+                ),
+                Self::Fish => format!(
+                    "if set -q {name}\n    \
+                     set -g _OLD_VIRTUAL_ENV_{name} ${name}\n\
+                     end\n\
+                     set -g _VIRTUAL_ENV_SET_{name} 1\n\
+                     set -gx {name} {value}\n"
+                ),
+                // Nushell restores the environment when the overlay is hidden on deactivation.
+                Self::Nushell => format!("        {name} : {value}\n"),
+                Self::PowerShell => format!(
+                    "if (Test-Path env:{name}) {{\n    \
+                     New-Variable -Scope global -Name _OLD_VIRTUAL_ENV_{name} -Value $env:{name} -Force\n\
+                     }}\n\
+                     New-Variable -Scope global -Name _VIRTUAL_ENV_SET_{name} -Value $true -Force\n\
+                     $env:{name} = {value}\n"
+                ),
+                Self::Batch => format!(
+                    "@if defined _VIRTUAL_ENV_SET_{name} @goto ENDIFVENV_{name}\r\n    \
+                     @set \"_OLD_VIRTUAL_ENV_{name}=%{name}%\"\r\n    \
+                     @set \"_VIRTUAL_ENV_SET_{name}=1\"\r\n\
+                     :ENDIFVENV_{name}\r\n\
+                     @set \"{name}={value}\"\r\n"
+                ),
+                // `activate_this.py` has no deactivation.
+                Self::Python => format!("os.environ[\"{name}\"] = {value}\n"),
+            };
+            script.push_str(&lines);
+        }
+        script
+    }
+
+    /// The commands that restore the environment variables exported by [`Quoting::activate_env`]
+    /// on deactivation, or unset them if they weren't set before activation.
+    fn deactivate_env(self, env: &[(String, String)]) -> String {
+        let mut script = String::new();
+        for (name, _) in env {
+            let lines = match self {
+                Self::Posix => format!(
+                    "    if ! [ -z \"${{_VIRTUAL_ENV_SET_{name}+_}}\" ] ; then\n        \
+                     if ! [ -z \"${{_OLD_VIRTUAL_ENV_{name}+_}}\" ] ; then\n            \
+                     {name}=\"$_OLD_VIRTUAL_ENV_{name}\"\n            \
+                     export {name}\n            \
+                     unset _OLD_VIRTUAL_ENV_{name}\n        \
+                     else\n            \
+                     unset {name}\n        \
+                     fi\n        \
+                     unset _VIRTUAL_ENV_SET_{name}\n    \
+                     fi\n"
+                ),
+                // The `deactivate` alias is a single line.
+                Self::Csh => format!(
+                    "test $?_VIRTUAL_ENV_SET_{name} != 0 && unsetenv {name} && unset _VIRTUAL_ENV_SET_{name}; \
+                     test $?_OLD_VIRTUAL_ENV_{name} != 0 && setenv {name} \"$_OLD_VIRTUAL_ENV_{name}:q\" && unset _OLD_VIRTUAL_ENV_{name}; "
+                ),
+                Self::Fish => format!(
+                    "    if set -q _VIRTUAL_ENV_SET_{name}\n        \
+                     if set -q _OLD_VIRTUAL_ENV_{name}\n            \
+                     set -gx {name} $_OLD_VIRTUAL_ENV_{name}\n            \
+                     set -e _OLD_VIRTUAL_ENV_{name}\n        \
+                     else\n            \
+                     set -e {name}\n        \
+                     end\n        \
+                     set -e _VIRTUAL_ENV_SET_{name}\n    \
+                     end\n"
+                ),
+                Self::PowerShell => format!(
+                    "    if (Test-Path variable:_VIRTUAL_ENV_SET_{name}) {{\n        \
+                     if (Test-Path variable:_OLD_VIRTUAL_ENV_{name}) {{\n            \
+                     $env:{name} = $variable:_OLD_VIRTUAL_ENV_{name}\n            \
+                     Remove-Variable \"_OLD_VIRTUAL_ENV_{name}\" -Scope global\n        \
+                     }}\n        \
+                     else {{\n            \
+                     Remove-Item env:{name} -ErrorAction SilentlyContinue\n        \
+                     }}\n        \
+                     Remove-Variable \"_VIRTUAL_ENV_SET_{name}\" -Scope global\n    \
+                     }}\n"
+                ),
+                Self::Batch => format!(
+                    "@if not defined _VIRTUAL_ENV_SET_{name} @goto ENDIFVENV_{name}\r\n    \
+                     @set \"{name}=%_OLD_VIRTUAL_ENV_{name}%\"\r\n    \
+                     @set _OLD_VIRTUAL_ENV_{name}=\r\n    \
+                     @set _VIRTUAL_ENV_SET_{name}=\r\n\
+                     :ENDIFVENV_{name}\r\n"
+                ),
+                Self::Nushell | Self::Python => String::new(),
+            };
+            script.push_str(&lines);
+        }
+        script
+    }
+}
+
+impl Quoting {
+    /// Encode an activation script for writing.
+    ///
+    /// Windows `PowerShell` 5.1 reads scripts without a byte order mark in the legacy code page, so
+    /// `PowerShell` scripts with non-ASCII characters, e.g., in the prompt, are written with a UTF-8
+    /// BOM. `activate.bat` switches the code page to UTF-8 itself, and the other shells assume
+    /// UTF-8.
+    fn encode(self, script: String) -> Vec<u8> {
+        if matches!(self, Self::PowerShell) && !script.is_ascii() {
+            let mut bytes = "\u{feff}".as_bytes().to_vec();
+            bytes.extend_from_slice(script.as_bytes());
+            bytes
+        } else {
+            script.into_bytes()
+        }
+    }
+}
+
+/// Substitute a placeholder that occupies its own line with `value`, which is either empty or
+/// ends in a line break, or substitute an inline placeholder.
+fn replace_placeholder(template: &str, placeholder: &str, value: &str) -> String {
+    template
+        .replace(&format!("{placeholder}\r\n"), value)
+        .replace(&format!("{placeholder}\n"), value)
+        .replace(placeholder, value)
+}
+
 /// Very basic `.cfg` file format writer.
 fn write_cfg(f: &mut impl Write, data: &[(String, String)]) -> io::Result<()> {
     for (key, value) in data {
@@ -44,21 +276,63 @@ fn write_cfg(f: &mut impl Write, data: &[(String, String)]) -> io::Result<()> {
 }
 
 /// Write all the files that belong to a venv without any packages installed.
-pub fn create_bare_venv(
-    location: &Path,
+///
+/// The venv is created according to the options of the [`VenvBuilder`], with the given base
+/// interpreter.
+///
+/// On Unix, the Python executables in the venv are symlinks to the base interpreter unless
+/// `copies` is set, like `python -m venv --copies`, e.g., for filesystems or deployment targets
+/// where symlinks break. On Windows, the executables are always copied. With `hardlinks`, copied
+/// files are hard-linked instead, where possible.
+///
+/// A `deterministic` venv doesn't record the base executable; its timestamps are normalized by the
+/// caller once it's seeded, see [`normalize_timestamps`].
+///
+/// A `minimal` venv has no activation scripts and no executables besides `python` (and its
+/// runtime), for tools that run the venv's interpreter directly.
+///
+/// If the location already exists, it's handled according to the [`OnExisting`] policy.
+///
+/// Unless writing into an existing, non-empty directory, the venv is created in a sibling
+/// directory and renamed into place once complete, such that an interrupted or failed creation
+/// never leaves behind a partial venv at the location.
+pub(crate) fn create_bare_venv(
+    builder: &VenvBuilder,
     interpreter: &Interpreter,
-    prompt: Prompt,
-    system_site_packages: bool,
-    allow_existing: bool,
 ) -> Result<VirtualEnvironment, Error> {
+    let &VenvBuilder {
+        ref location,
+        ref prompt,
+        system_site_packages,
+        on_existing,
+        copies,
+        hardlinks,
+        ref extra_paths,
+        ref activation_env,
+        permissions,
+        minimal,
+        deterministic,
+        ..
+    } = builder;
+
+    // The link count of files can't be determined on Windows, so deterministic venvs don't
+    // hard-link the base interpreter there, since normalizing its timestamps would modify it.
+    let hardlinks = hardlinks && !(cfg!(windows) && deterministic);
+
+    // Permissions can't be applied on Windows, so reject them before touching the location.
+    if cfg!(not(unix)) && permissions != Permissions::Default {
+        return Err(Error::UnsupportedPermissions);
+    }
+
     // Determine the base Python executable; that is, the Python executable that should be
     // considered the "base" for the virtual environment. This is typically the Python executable
     // from the [`Interpreter`]; however, if the interpreter is a virtual environment itself, then
     // the base Python executable is the Python executable of the interpreter's base interpreter.
     let base_python = if cfg!(unix) {
         // On Unix, follow symlinks to resolve the base interpreter, since the Python executable in
-        // a virtual environment is a symlink to the base interpreter.
-        uv_fs::canonicalize_executable(interpreter.sys_executable())?
+        // a virtual environment is a symlink to the base interpreter. Depending on the
+        // interpreter's `CanonicalizationPolicy`, symlinks into the Nix store are preserved.
+        interpreter.resolved_executable()?
     } else if cfg!(windows) {
         // On Windows, follow `virtualenv`. If we're in a virtual environment, use
         // `sys._base_executable` if it exists; if not, use `sys.base_prefix`. For example, with
@@ -76,14 +350,14 @@ pub fn create_bare_venv(
                 interpreter.sys_base_prefix().join("python.exe")
             }
         } else {
-            uv_fs::canonicalize_executable(interpreter.sys_executable())?
+            interpreter.resolved_executable()?
         }
     } else {
         unimplemented!("Only Windows and Unix are supported")
     };
 
-    // Validate the existing location.
-    match location.metadata() {
+    // Validate the existing location, and determine whether to write into it directly.
+    let in_place = match location.metadata() {
         Ok(metadata) => {
             if metadata.is_file() {
                 return Err(Error::IO(io::Error::new(
@@ -91,17 +365,23 @@ pub fn create_bare_venv(
                     format!("File exists at `{}`", location.user_display()),
                 )));
             } else if metadata.is_dir() {
-                if allow_existing {
-                    info!("Allowing existing directory");
-                } else if location.join("pyvenv.cfg").is_file() {
-                    info!("Removing existing directory");
-                    fs::remove_dir_all(location)?;
-                    fs::create_dir_all(location)?;
-                } else if location
+                if location
                     .read_dir()
                     .is_ok_and(|mut dir| dir.next().is_none())
                 {
                     info!("Ignoring empty directory");
+                    false
+                } else if on_existing == OnExisting::Allow {
+                    info!("Allowing existing directory");
+                    true
+                } else if on_existing == OnExisting::Fail {
+                    return Err(Error::IO(io::Error::new(
+                        io::ErrorKind::AlreadyExists,
+                        format!("The directory `{}` exists", location.user_display()),
+                    )));
+                } else if is_removable_virtualenv(location)? {
+                    info!("Replacing existing directory");
+                    false
                 } else {
                     return Err(Error::IO(io::Error::new(
                         io::ErrorKind::AlreadyExists,
@@ -111,15 +391,38 @@ pub fn create_bare_venv(
                         ),
                     )));
                 }
+            } else {
+                false
             }
         }
-        Err(err) if err.kind() == io::ErrorKind::NotFound => {
-            fs::create_dir_all(location)?;
-        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => false,
         Err(err) => return Err(Error::IO(err)),
-    }
+    };
 
-    let location = location.canonicalize()?;
+    // Determine the absolute location of the venv, and the directory to create it in.
+    let (location, staging) = if let (false, Some(parent), Some(name)) =
+        (in_place, location.parent(), location.file_name())
+    {
+        let parent = if parent.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            parent
+        };
+        let location = if location.exists() {
+            location.canonicalize()?
+        } else {
+            fs::create_dir_all(parent)?;
+            parent.canonicalize()?.join(name)
+        };
+        let staging = StagingDir::new(&location)?;
+        (location, Some(staging))
+    } else {
+        fs::create_dir_all(location)?;
+        (location.canonicalize()?, None)
+    };
+    let root = staging
+        .as_ref()
+        .map_or(location.as_path(), StagingDir::path);
 
     let bin_name = if cfg!(unix) {
         "bin"
@@ -128,20 +431,39 @@ pub fn create_bare_venv(
     } else {
         unimplemented!("Only Windows and Unix are supported")
     };
-    let scripts = location.join(&interpreter.virtualenv().scripts);
+    let scripts = root.join(&interpreter.virtualenv().scripts);
     let prompt = match prompt {
-        Prompt::CurrentDirectoryName => env::current_dir()?
-            .file_name()
-            .map(|name| name.to_string_lossy().to_string()),
-        Prompt::Static(value) => Some(value),
+        Prompt::CurrentDirectoryName => {
+            let current_dir = env::current_dir()?;
+            project_name(&current_dir).or_else(|| {
+                current_dir
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+        }
+        Prompt::Static(value) => Some(value.clone()),
+        Prompt::Derived => location.parent().and_then(|parent| {
+            project_name(parent).or_else(|| {
+                let hidden = location
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+                if hidden {
+                    parent
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                } else {
+                    None
+                }
+            })
+        }),
         Prompt::None => None,
     };
 
     // Add the CACHEDIR.TAG.
-    cachedir::ensure_tag(&location)?;
+    cachedir::ensure_tag(root)?;
 
     // Create a `.gitignore` file to ignore all files in the venv.
-    fs::write(location.join(".gitignore"), "*")?;
+    fs::write(root.join(".gitignore"), "*")?;
 
     // Per PEP 405, the Python `home` is the parent directory of the interpreter.
     let python_home = base_python.parent().ok_or_else(|| {
@@ -153,23 +475,61 @@ pub fn create_bare_venv(
 
     // Different names for the python interpreter
     fs::create_dir_all(&scripts)?;
-    let executable = scripts.join(format!("python{EXE_SUFFIX}"));
+    let python = format!("python{EXE_SUFFIX}");
 
-    #[cfg(unix)]
-    {
-        uv_fs::replace_symlink(&base_python, &executable)?;
-        uv_fs::replace_symlink(
-            "python",
-            scripts.join(format!("python{}", interpreter.python_major())),
-        )?;
-        uv_fs::replace_symlink(
-            "python",
-            scripts.join(format!(
+    // The versioned aliases of `python`, e.g., `python3` and `python3.12`, and `python3.13t` for
+    // free-threaded builds. For PyPy, also `pypy`, `pypy3`, and `pypy3.10`. Minimal venvs only
+    // have `python`.
+    let aliases = if minimal {
+        Vec::new()
+    } else {
+        let mut aliases = vec![
+            format!("python{}", interpreter.python_major()),
+            format!(
                 "python{}.{}",
                 interpreter.python_major(),
                 interpreter.python_minor(),
-            )),
-        )?;
+            ),
+        ];
+        if interpreter.gil_disabled() {
+            aliases.push(format!(
+                "python{}.{}t",
+                interpreter.python_major(),
+                interpreter.python_minor(),
+            ));
+        }
+        if is_pypy(interpreter) {
+            aliases.extend([
+                "pypy".to_string(),
+                format!("pypy{}", interpreter.python_major()),
+                format!(
+                    "pypy{}.{}",
+                    interpreter.python_major(),
+                    interpreter.python_minor(),
+                ),
+            ]);
+        }
+        aliases
+    };
+
+    if cfg!(unix) {
+        if copies {
+            for name in std::iter::once(&python).chain(&aliases) {
+                replace_copy(&base_python, &scripts.join(name), hardlinks)?;
+            }
+            copy_relative_libraries(&base_python, root, hardlinks)?;
+        } else {
+            uv_fs::replace_symlink(&base_python, scripts.join(&python))?;
+            for name in &aliases {
+                uv_fs::replace_symlink("python", scripts.join(name))?;
+            }
+        }
+
+        // PyPy locates its runtime libraries relative to the executable, so they need to be
+        // linked into the venv alongside it.
+        if is_pypy(interpreter) {
+            link_pypy_runtime(interpreter, python_home, root, copies, hardlinks)?;
+        }
     }
 
     // No symlinking on Windows, at least not on a regular non-dev non-admin Windows install.
@@ -180,14 +540,28 @@ pub fn create_bare_venv(
             &base_python,
             &scripts,
             python_home,
+            hardlinks,
         )?;
-        copy_launcher_windows(
-            WindowsExecutable::Pythonw,
-            interpreter,
-            &base_python,
-            &scripts,
-            python_home,
-        )?;
+        if !minimal {
+            copy_launcher_windows(
+                WindowsExecutable::Pythonw,
+                interpreter,
+                &base_python,
+                &scripts,
+                python_home,
+                hardlinks,
+            )?;
+
+            // Like `python -m venv`, add the free-threaded aliases, e.g., `python3.13t.exe`.
+            if interpreter.gil_disabled() {
+                for executable in [WindowsExecutable::Python, WindowsExecutable::Pythonw] {
+                    fs::copy(
+                        scripts.join(executable.exe()),
+                        scripts.join(executable.free_threaded_exe(interpreter)),
+                    )?;
+                }
+            }
+        }
     }
 
     #[cfg(not(any(unix, windows)))]
@@ -195,8 +569,9 @@ pub fn create_bare_venv(
         compile_error!("Only Windows and Unix are supported")
     }
 
-    // Add all the activate scripts for different shells
-    for (name, template) in ACTIVATE_TEMPLATES {
+    // Add all the activate scripts for different shells, unless the venv is minimal.
+    let activators: &[_] = if minimal { &[] } else { ACTIVATE_TEMPLATES };
+    for (name, template, quoting) in activators {
         let path_sep = if cfg!(windows) { ";" } else { ":" };
 
         let relative_site_packages = [
@@ -209,23 +584,36 @@ pub fn create_bare_venv(
             pathdiff::diff_paths(path, &interpreter.virtualenv().scripts)
                 .expect("Failed to calculate relative path to site-packages")
         })
-        .map(|path| path.simplified().to_str().unwrap().replace('\\', "\\\\"))
+        .map(|path| path.simplified().to_str().unwrap().to_string())
         .join(path_sep);
 
         let activator = template
             .replace(
                 "{{ VIRTUAL_ENV_DIR }}",
                 // SAFETY: `unwrap` is guaranteed to succeed because `location` is an `Utf8PathBuf`.
-                location.simplified().to_str().unwrap(),
+                &quoting.quote(location.simplified().to_str().unwrap()),
             )
             .replace("{{ BIN_NAME }}", bin_name)
             .replace(
                 "{{ VIRTUAL_PROMPT }}",
-                prompt.as_deref().unwrap_or_default(),
+                &quoting.quote(prompt.as_deref().unwrap_or_default()),
             )
             .replace("{{ PATH_SEP }}", path_sep)
-            .replace("{{ RELATIVE_SITE_PACKAGES }}", &relative_site_packages);
-        fs::write(scripts.join(name), activator)?;
+            .replace(
+                "{{ RELATIVE_SITE_PACKAGES }}",
+                &quoting.quote(&relative_site_packages),
+            );
+        let activator = replace_placeholder(
+            &activator,
+            "{{ ACTIVATE_ENV }}",
+            &quoting.activate_env(activation_env),
+        );
+        let activator = replace_placeholder(
+            &activator,
+            "{{ DEACTIVATE_ENV }}",
+            &quoting.deactivate_env(activation_env),
+        );
+        fs::write(scripts.join(name), quoting.encode(activator))?;
     }
 
     let mut pyvenv_cfg_data: Vec<(String, String)> = vec![
@@ -259,12 +647,29 @@ pub fn create_bare_venv(
         pyvenv_cfg_data.push(("prompt".to_string(), prompt));
     }
 
-    let mut pyvenv_cfg = BufWriter::new(File::create(location.join("pyvenv.cfg"))?);
+    // Record free-threaded builds, whose `site-packages` is, e.g., `lib/python3.13t`.
+    if interpreter.gil_disabled() {
+        pyvenv_cfg_data.push(("free-threaded".to_string(), "true".to_string()));
+    }
+
+    // Record the provenance of the base interpreter, for auditing. The base executable varies
+    // between machines, so deterministic venvs omit it.
+    if !deterministic {
+        pyvenv_cfg_data.push((
+            "base-executable".to_string(),
+            base_python.simplified_display().to_string(),
+        ));
+    }
+    if let Some(build_revision) = interpreter.build_revision() {
+        pyvenv_cfg_data.push(("build-revision".to_string(), build_revision.to_string()));
+    }
+
+    let mut pyvenv_cfg = BufWriter::new(File::create(root.join("pyvenv.cfg"))?);
     write_cfg(&mut pyvenv_cfg, &pyvenv_cfg_data)?;
     drop(pyvenv_cfg);
 
     // Construct the path to the `site-packages` directory.
-    let site_packages = location.join(&interpreter.virtualenv().purelib);
+    let site_packages = root.join(&interpreter.virtualenv().purelib);
     fs::create_dir_all(&site_packages)?;
 
     // If necessary, create a symlink from `lib64` to `lib`.
@@ -274,9 +679,8 @@ pub fn create_bare_venv(
         && interpreter.markers().os_name() == "posix"
         && interpreter.markers().sys_platform() != "darwin"
     {
-        let lib64 = location.join("lib64");
-        let lib = location.join("lib");
-        match std::os::unix::fs::symlink(lib, lib64) {
+        let lib64 = root.join("lib64");
+        match std::os::unix::fs::symlink("lib", lib64) {
             Ok(()) => {}
             Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
             Err(err) => {
@@ -289,6 +693,42 @@ pub fn create_bare_venv(
     fs::write(site_packages.join("_virtualenv.py"), VIRTUALENV_PATCH)?;
     fs::write(site_packages.join("_virtualenv.pth"), "import _virtualenv")?;
 
+    // Add the extra `sys.path` entries, if any.
+    if !extra_paths.is_empty() {
+        fs::write(
+            site_packages.join(EXTRA_PATHS_PTH),
+            extra_paths_pth(extra_paths)?,
+        )?;
+    }
+
+    // On Windows, conda interpreters load DLLs, e.g., `libssl`, from `Library\bin`, which conda
+    // adds to the DLL search path based on `sys.prefix`. In a venv, `sys.prefix` is the venv, so
+    // add the base environment's DLL directories explicitly.
+    if cfg!(windows) {
+        let dll_dirs = conda_dll_dirs(interpreter.sys_base_prefix());
+        if !dll_dirs.is_empty() {
+            debug!(
+                "Adding conda DLL directories: {}",
+                dll_dirs.iter().map(Simplified::user_display).join(", ")
+            );
+            fs::write(
+                site_packages.join(CONDA_DLLS_PTH),
+                conda_dlls_pth(&dll_dirs),
+            )?;
+        }
+    }
+
+    // Apply the requested permissions, before the venv becomes visible at its location.
+    apply_permissions(root, permissions)?;
+
+    // Move the venv into place.
+    if let Some(staging) = staging {
+        staging.persist(&location)?;
+    }
+
+    let executable = location
+        .join(&interpreter.virtualenv().scripts)
+        .join(python);
     Ok(VirtualEnvironment {
         scheme: Scheme {
             purelib: location.join(&interpreter.virtualenv().purelib),
@@ -302,6 +742,406 @@ pub fn create_bare_venv(
     })
 }
 
+/// Render the contents of the `.pth` file for the extra `sys.path` entries of a venv.
+///
+/// Each entry is made absolute, such that it doesn't depend on the location of `site-packages`,
+/// and such that it can't be mistaken for an `import` line.
+fn extra_paths_pth(extra_paths: &[PathBuf]) -> Result<String, io::Error> {
+    let mut contents = String::new();
+    for path in extra_paths {
+        let path = uv_fs::absolutize_path(path)?;
+        let path = path.simplified_display().to_string();
+        if path.contains(['\n', '\r']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Path can't be added to `sys.path`: `{path}`"),
+            ));
+        }
+        contents.push_str(&path);
+        contents.push('\n');
+    }
+    Ok(contents)
+}
+
+/// The DLL directories of a conda environment on Windows, if the prefix is a conda environment,
+/// i.e., it has a `conda-meta` directory.
+///
+/// These are the directories that conda's activation adds to the `PATH`.
+fn conda_dll_dirs(prefix: &Path) -> Vec<PathBuf> {
+    if !prefix.join("conda-meta").is_dir() {
+        return Vec::new();
+    }
+    [
+        prefix.join("Library").join("mingw-w64").join("bin"),
+        prefix.join("Library").join("usr").join("bin"),
+        prefix.join("Library").join("bin"),
+    ]
+    .into_iter()
+    .filter(|dir| dir.is_dir())
+    .collect()
+}
+
+/// The contents of a `.pth` file that adds the given DLL directories when the interpreter starts.
+///
+/// The directories are added with `os.add_dll_directory`, for extension modules, and prepended to
+/// the `PATH`, for older Pythons and subprocesses.
+fn conda_dlls_pth(dll_dirs: &[PathBuf]) -> String {
+    let dirs = dll_dirs
+        .iter()
+        .map(|dir| Quoting::Python.quote(&dir.simplified_display().to_string()))
+        .join(", ");
+    // `.pth` lines are only executed if they start with `import`, and must be a single line.
+    format!(
+        "import os; dirs = [{dirs}]; \
+         [os.add_dll_directory(d) for d in dirs] if hasattr(os, 'add_dll_directory') else None; \
+         os.environ['PATH'] = os.pathsep.join([*dirs, os.environ.get('PATH', '')])\n"
+    )
+}
+
+/// A sibling directory of a venv to create the venv in, before moving it into place.
+///
+/// The directory is removed on drop, unless it was persisted.
+#[derive(Debug)]
+struct StagingDir(Option<PathBuf>);
+
+impl StagingDir {
+    /// Create a staging directory for the venv at the given (absolute) location.
+    fn new(location: &Path) -> Result<Self, io::Error> {
+        let path = sibling(location, "tmp");
+        // Remove any leftovers from an interrupted run, e.g., with a reused process ID.
+        match fs::remove_dir_all(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        fs::create_dir(&path)?;
+        Ok(Self(Some(path)))
+    }
+
+    fn path(&self) -> &Path {
+        self.0.as_deref().expect("Staging directory was persisted")
+    }
+
+    /// Move the staging directory to the given location, replacing any existing directory.
+    ///
+    /// An existing directory is first moved aside, rather than removed in place, such that the
+    /// location never contains a partially removed venv.
+    fn persist(mut self, location: &Path) -> Result<(), io::Error> {
+        let path = self.0.take().expect("Staging directory was persisted");
+        let old = if location.exists() {
+            let old = sibling(location, "old");
+            if let Err(err) = fs::rename(location, &old) {
+                self.0 = Some(path);
+                return Err(err);
+            }
+            Some(old)
+        } else {
+            None
+        };
+        if let Err(err) = fs::rename(&path, location) {
+            // Restore the previous directory, if any.
+            if let Some(old) = old {
+                let _ = fs::rename(old, location);
+            }
+            self.0 = Some(path);
+            return Err(err);
+        }
+        if let Some(old) = old {
+            fs::remove_dir_all(old)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            if let Err(err) = fs::remove_dir_all(&path) {
+                debug!(
+                    "Failed to remove staging directory `{}`: {err}",
+                    path.user_display()
+                );
+            }
+        }
+    }
+}
+
+/// A hidden, process-specific sibling path of the given location, e.g., `.venv.tmp-1234` for
+/// `.venv`.
+fn sibling(location: &Path, suffix: &str) -> PathBuf {
+    let mut name = std::ffi::OsString::from(".");
+    if let Some(file_name) = location.file_name() {
+        name.push(file_name.to_string_lossy().trim_start_matches('.'));
+    }
+    name.push(format!(".{suffix}-{}", std::process::id()));
+    location.with_file_name(name)
+}
+
+/// Find the name of the project in the nearest `pyproject.toml` in the given directory or its
+/// ancestors, i.e., its `project.name`.
+///
+/// Stops at the first `pyproject.toml`, even if it has no `[project]` table, e.g., for a virtual
+/// workspace root.
+fn project_name(path: &Path) -> Option<String> {
+    let contents = path
+        .ancestors()
+        .find_map(|dir| fs::read_to_string(dir.join("pyproject.toml")).ok())?;
+    let pyproject = toml::from_str::<toml::Table>(&contents).ok()?;
+    pyproject
+        .get("project")?
+        .get("name")?
+        .as_str()
+        .map(ToString::to_string)
+}
+
+/// Returns `true` if the directory looks like a virtualenv that's safe to remove and recreate.
+///
+/// The directory must contain a `pyvenv.cfg` file, and must not contain the current working
+/// directory, to guard against removing, e.g., a project that happens to contain a `pyvenv.cfg`.
+fn is_removable_virtualenv(location: &Path) -> Result<bool, Error> {
+    if !location.join("pyvenv.cfg").is_file() {
+        return Ok(false);
+    }
+    let location = location.canonicalize()?;
+    if env::current_dir()?.canonicalize()?.starts_with(location) {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Copy a file, replacing an existing file or symlink at the destination rather than writing
+/// through it, e.g., when an existing venv that symlinks to the base interpreter is recreated.
+fn replace_copy(from: &Path, to: &Path, hardlink: bool) -> Result<(), Error> {
+    match fs::remove_file(to) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    link_or_copy(from, to, hardlink)?;
+    Ok(())
+}
+
+/// Apply the [`Permissions`] to all files and directories in the venv.
+///
+/// Symlinks, e.g., to the base interpreter, are skipped, since changing their permissions would
+/// change the permissions of their target. Likewise, files with other hard links, e.g., the base
+/// interpreter with `hardlinks`, are skipped.
+#[cfg(unix)]
+fn apply_permissions(root: &Path, permissions: Permissions) -> Result<(), Error> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (dir_mode, file_mode, exec_mode) = match permissions {
+        Permissions::Default => return Ok(()),
+        Permissions::Shared => (0o2775, 0o664, 0o775),
+        Permissions::Private => (0o700, 0o600, 0o700),
+    };
+    debug!(
+        "Applying {permissions:?} permissions to `{}`",
+        root.user_display()
+    );
+
+    for entry in walkdir::WalkDir::new(root) {
+        let entry = entry.map_err(io::Error::from)?;
+        let file_type = entry.file_type();
+        let mode = if file_type.is_dir() {
+            dir_mode
+        } else if file_type.is_file() {
+            if is_hard_linked(&entry)? {
+                trace!(
+                    "Skipping hard-linked file `{}`",
+                    entry.path().user_display()
+                );
+                continue;
+            }
+            let metadata = entry.metadata().map_err(io::Error::from)?;
+            if metadata.permissions().mode() & 0o111 != 0 {
+                exec_mode
+            } else {
+                file_mode
+            }
+        } else {
+            continue;
+        };
+        fs::set_permissions(entry.path(), std::fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+/// Permissions other than [`Permissions::Default`] are rejected on this platform before the venv
+/// is created.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+fn apply_permissions(_root: &Path, permissions: Permissions) -> Result<(), Error> {
+    debug_assert_eq!(permissions, Permissions::Default);
+    Ok(())
+}
+
+/// The timestamp for the files in a deterministic venv: `SOURCE_DATE_EPOCH`, if set, like other
+/// reproducible build tools, or 1980-01-01, the earliest timestamp that ZIP archives support.
+pub(crate) fn source_date_epoch() -> SystemTime {
+    let seconds = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(315_532_800);
+    SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+/// Returns `true` if the file has other hard links, e.g., to the base interpreter with `hardlinks`.
+#[cfg(unix)]
+fn is_hard_linked(entry: &walkdir::DirEntry) -> Result<bool, io::Error> {
+    use std::os::unix::fs::MetadataExt;
+
+    Ok(entry.metadata()?.nlink() > 1)
+}
+
+/// The link count isn't available on this platform; callers that must not modify shared files
+/// avoid creating hard links instead.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+fn is_hard_linked(_entry: &walkdir::DirEntry) -> Result<bool, io::Error> {
+    Ok(false)
+}
+
+/// Set the modification time of all files in the venv to the given timestamp, such that the venv
+/// is byte-identical across creations, including when archived.
+///
+/// Symlinks are skipped, since setting their timestamp would set their target's timestamp.
+/// Likewise, files with other hard links, e.g., the base interpreter with `hardlinks`, are
+/// skipped. Directories are skipped on Windows, where they can't be opened as files.
+pub(crate) fn normalize_timestamps(root: &Path, timestamp: SystemTime) -> Result<(), Error> {
+    debug!("Normalizing timestamps in `{}`", root.user_display());
+    // Visit directories after their contents, such that their timestamps aren't changed by
+    // subsequent writes.
+    for entry in walkdir::WalkDir::new(root).contents_first(true) {
+        let entry = entry.map_err(io::Error::from)?;
+        let file_type = entry.file_type();
+        if file_type.is_file() && is_hard_linked(&entry)? {
+            trace!(
+                "Skipping hard-linked file `{}`",
+                entry.path().user_display()
+            );
+            continue;
+        }
+        if file_type.is_file() || (cfg!(unix) && file_type.is_dir()) {
+            // On Windows, setting the timestamp requires write access; on Unix, it only requires
+            // ownership, which also covers read-only files, e.g., copied interpreters.
+            let file = if cfg!(windows) {
+                fs::OpenOptions::new().write(true).open(entry.path())?
+            } else {
+                File::open(entry.path())?
+            };
+            file.file().set_modified(timestamp)?;
+        }
+    }
+    Ok(())
+}
+
+/// Hard-link a file if `hardlink` is set, falling back to copying it if the link fails, e.g.,
+/// because the source and destination are on different volumes.
+fn link_or_copy(from: &Path, to: &Path, hardlink: bool) -> Result<(), io::Error> {
+    if hardlink {
+        match fs::hard_link(from, to) {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                debug!(
+                    "Failed to hard-link `{}`, copying instead: {err}",
+                    from.user_display()
+                );
+            }
+        }
+    }
+    fs::copy(from, to)?;
+    Ok(())
+}
+
+/// Copy the shared libraries that the base interpreter loads from `../lib` relative to its own
+/// path into the venv, such that a copied executable finds them at the same relative path.
+///
+/// Interpreters like python-build-standalone's look up `libpython` relative to the executable,
+/// see [`uv_interpreter::relative_libraries`]. Interpreters that link against `libpython` in a
+/// system directory find it there, so nothing is copied for them.
+fn copy_relative_libraries(
+    base_python: &Path,
+    location: &Path,
+    hardlink: bool,
+) -> Result<(), Error> {
+    let libraries = uv_interpreter::relative_libraries(base_python)?;
+    if libraries.is_empty() {
+        return Ok(());
+    }
+    let lib = location.join("lib");
+    fs::create_dir_all(&lib)?;
+    for library in libraries {
+        if let Some(name) = library.file_name() {
+            replace_copy(&library, &lib.join(name), hardlink)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the interpreter is `PyPy`.
+fn is_pypy(interpreter: &Interpreter) -> bool {
+    interpreter.implementation_name() == "pypy"
+}
+
+/// Link the runtime of a `PyPy` base interpreter into the venv, like `virtualenv`.
+///
+/// `PyPy` looks up its shared library, e.g., `libpypy3.10-c.so`, next to the executable, and, for
+/// portable builds, its bundled libraries (e.g., Tcl/Tk) in `../lib`. The standard library, e.g.,
+/// `lib/pypy3.10`, is skipped, since it would collide with the venv's `site-packages`.
+///
+/// Files are copied (or hard-linked) if `copies` is set; directories are always symlinked.
+fn link_pypy_runtime(
+    interpreter: &Interpreter,
+    python_home: &Path,
+    location: &Path,
+    copies: bool,
+    hardlinks: bool,
+) -> Result<(), Error> {
+    let link = |path: &Path, target: &Path| -> Result<(), Error> {
+        if copies && path.is_file() {
+            replace_copy(path, target, hardlinks)
+        } else {
+            uv_fs::replace_symlink(path, target)?;
+            Ok(())
+        }
+    };
+
+    // The shared library next to the executable, e.g., `bin/libpypy3.10-c.so`.
+    for entry in fs::read_dir(python_home)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        if name.to_string_lossy().starts_with("libpypy") {
+            link(&path, &location.join("bin").join(name))?;
+        }
+    }
+
+    // Distribution builds, e.g., `/usr/bin/pypy3`, only have the standard library in `lib`.
+    if interpreter.sys_base_prefix() == Path::new("/usr") {
+        return Ok(());
+    }
+    let entries = match fs::read_dir(interpreter.sys_base_prefix().join("lib")) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    let lib = location.join("lib");
+    fs::create_dir_all(&lib)?;
+    for entry in entries {
+        let path = entry?.path();
+        if path == interpreter.stdlib() {
+            continue;
+        }
+        if let Some(name) = path.file_name() {
+            debug!("Linking PyPy runtime: `{}`", path.user_display());
+            link(&path, &lib.join(name))?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Copy, Clone)]
 enum WindowsExecutable {
     /// The `python.exe` executable (or `venvlauncher.exe` launcher shim).
@@ -319,11 +1159,36 @@ impl WindowsExecutable {
         }
     }
 
-    /// The name of the launcher shim.
-    fn launcher(self) -> &'static str {
-        match self {
-            WindowsExecutable::Python => "venvlauncher.exe",
-            WindowsExecutable::Pythonw => "venvwlauncher.exe",
+    /// The name of the free-threaded Python executable, e.g., `python3.13t.exe`.
+    fn free_threaded_exe(self, interpreter: &Interpreter) -> String {
+        let name = match self {
+            WindowsExecutable::Python => "python",
+            WindowsExecutable::Pythonw => "pythonw",
+        };
+        format!(
+            "{name}{}.{}t.exe",
+            interpreter.python_major(),
+            interpreter.python_minor()
+        )
+    }
+
+    /// The name of the base interpreter's executable, which is versioned for free-threaded builds.
+    fn base_exe(self, interpreter: &Interpreter) -> String {
+        if interpreter.gil_disabled() {
+            self.free_threaded_exe(interpreter)
+        } else {
+            self.exe().to_string()
+        }
+    }
+
+    /// The name of the launcher shim, which launches the free-threaded executable for
+    /// free-threaded builds.
+    fn launcher(self, gil_disabled: bool) -> &'static str {
+        match (self, gil_disabled) {
+            (WindowsExecutable::Python, false) => "venvlauncher.exe",
+            (WindowsExecutable::Pythonw, false) => "venvwlauncher.exe",
+            (WindowsExecutable::Python, true) => "venvlaunchert.exe",
+            (WindowsExecutable::Pythonw, true) => "venvwlaunchert.exe",
         }
     }
 }
@@ -339,6 +1204,7 @@ fn copy_launcher_windows(
     base_python: &Path,
     scripts: &Path,
     python_home: &Path,
+    hardlink: bool,
 ) -> Result<(), Error> {
     // First priority: the `python.exe` and `pythonw.exe` shims.
     let shim = interpreter
@@ -363,7 +1229,7 @@ fn copy_launcher_windows(
         .join("venv")
         .join("scripts")
         .join("nt")
-        .join(executable.launcher());
+        .join(executable.launcher(interpreter.gil_disabled()));
     match fs_err::copy(shim, scripts.join(executable.exe())) {
         Ok(_) => return Ok(()),
         Err(err) if err.kind() == io::ErrorKind::NotFound => {}
@@ -374,7 +1240,7 @@ fn copy_launcher_windows(
 
     // Third priority: on Conda at least, we can look for the launcher shim next to
     // the Python executable itself.
-    let shim = base_python.with_file_name(executable.launcher());
+    let shim = base_python.with_file_name(executable.launcher(interpreter.gil_disabled()));
     match fs_err::copy(shim, scripts.join(executable.exe())) {
         Ok(_) => return Ok(()),
         Err(err) if err.kind() == io::ErrorKind::NotFound => {}
@@ -383,71 +1249,425 @@ fn copy_launcher_windows(
         }
     }
 
-    // Fourth priority: if the launcher shim doesn't exist, assume this is
-    // an embedded Python. Copy the Python executable itself, along with
-    // the DLLs, `.pyd` files, and `.zip` files in the same directory.
-    match fs_err::copy(
-        base_python.with_file_name(executable.exe()),
-        scripts.join(executable.exe()),
-    ) {
-        Ok(_) => {
-            // Copy `.dll` and `.pyd` files from the top-level, and from the
-            // `DLLs` subdirectory (if it exists).
-            for directory in [
-                python_home,
-                interpreter.sys_base_prefix().join("DLLs").as_path(),
-            ] {
-                let entries = match fs_err::read_dir(directory) {
-                    Ok(read_dir) => read_dir,
-                    Err(err) if err.kind() == io::ErrorKind::NotFound => {
-                        continue;
-                    }
-                    Err(err) => {
-                        return Err(err.into());
-                    }
-                };
-                for entry in entries {
-                    let entry = entry?;
-                    let path = entry.path();
-                    if path.extension().is_some_and(|ext| {
-                        ext.eq_ignore_ascii_case("dll") || ext.eq_ignore_ascii_case("pyd")
-                    }) {
-                        if let Some(file_name) = path.file_name() {
-                            fs_err::copy(&path, scripts.join(file_name))?;
-                        }
-                    }
+    // Fourth priority: if the launcher shim doesn't exist, e.g., for an embedded Python, copy (or
+    // hard-link) the Python executable itself, along with the DLLs, `.pyd` files, and `.zip` files
+    // in the same directory. Unlike a trampoline that re-launches the base interpreter, the copy
+    // runs in a single process, so it receives `Ctrl-C` and its exit code directly.
+    let base_executable = base_python.with_file_name(executable.base_exe(interpreter));
+    if copy_executable_windows(
+        &base_executable,
+        &scripts.join(executable.exe()),
+        python_home,
+        interpreter.sys_base_prefix(),
+        hardlink,
+    )? {
+        return Ok(());
+    }
+
+    Err(Error::NotFound(base_python.user_display().to_string()))
+}
+
+/// Copy (or hard-link) the base Python executable to `target`, along with the DLLs, `.pyd` files,
+/// and `.zip` files it needs to run from the `Scripts` directory.
+///
+/// Returns `false` if the base executable doesn't exist.
+fn copy_executable_windows(
+    base_executable: &Path,
+    target: &Path,
+    python_home: &Path,
+    sys_base_prefix: &Path,
+    hardlink: bool,
+) -> Result<bool, Error> {
+    match link_or_copy(base_executable, target, hardlink) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err.into()),
+    }
+    let Some(scripts) = target.parent() else {
+        return Ok(true);
+    };
+
+    // Copy `.dll` and `.pyd` files from the top-level, and from the `DLLs` subdirectory (if it
+    // exists).
+    for directory in [python_home, sys_base_prefix.join("DLLs").as_path()] {
+        let entries = match fs_err::read_dir(directory) {
+            Ok(read_dir) => read_dir,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                continue;
+            }
+            Err(err) => {
+                return Err(err.into());
+            }
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| {
+                ext.eq_ignore_ascii_case("dll") || ext.eq_ignore_ascii_case("pyd")
+            }) {
+                if let Some(file_name) = path.file_name() {
+                    link_or_copy(&path, &scripts.join(file_name), hardlink)?;
                 }
             }
+        }
+    }
 
-            // Copy `.zip` files from the top-level.
-            match fs_err::read_dir(python_home) {
-                Ok(entries) => {
-                    for entry in entries {
-                        let entry = entry?;
-                        let path = entry.path();
-                        if path
-                            .extension()
-                            .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
-                        {
-                            if let Some(file_name) = path.file_name() {
-                                fs_err::copy(&path, scripts.join(file_name))?;
-                            }
-                        }
+    // Copy `.zip` files from the top-level.
+    match fs_err::read_dir(python_home) {
+        Ok(entries) => {
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                if path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+                {
+                    if let Some(file_name) = path.file_name() {
+                        link_or_copy(&path, &scripts.join(file_name), hardlink)?;
                     }
                 }
-                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
-                Err(err) => {
-                    return Err(err.into());
-                }
-            };
-
-            return Ok(());
+            }
         }
         Err(err) if err.kind() == io::ErrorKind::NotFound => {}
         Err(err) => {
             return Err(err.into());
         }
+    };
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::{
+        apply_permissions, conda_dll_dirs, conda_dlls_pth, copy_executable_windows,
+        copy_relative_libraries, extra_paths_pth, is_removable_virtualenv, link_or_copy,
+        normalize_timestamps, project_name, replace_placeholder, Quoting, StagingDir,
+    };
+
+    #[test]
+    fn extra_paths() {
+        let cwd = std::env::current_dir().unwrap();
+        let relative = PathBuf::from("packages").join("foo");
+        let contents = extra_paths_pth(&[relative.clone(), cwd.join("bar")]).unwrap();
+        let lines: Vec<PathBuf> = contents.lines().map(PathBuf::from).collect();
+        assert_eq!(lines, vec![cwd.join(relative), cwd.join("bar")]);
+
+        assert!(extra_paths_pth(&[PathBuf::from("foo\nimport os")]).is_err());
     }
 
-    Err(Error::NotFound(base_python.user_display().to_string()))
+    #[test]
+    fn copy_executable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let home = temp_dir.path().join("python");
+        let scripts = temp_dir.path().join("venv").join("Scripts");
+        fs_err::create_dir_all(home.join("DLLs")).unwrap();
+        fs_err::create_dir_all(&scripts).unwrap();
+        for file in [
+            "python3.13t.exe",
+            "python313t.dll",
+            "python313t.zip",
+            "LICENSE.txt",
+            "DLLs/_ssl.pyd",
+        ] {
+            fs_err::write(home.join(file), file).unwrap();
+        }
+
+        // The base executable is copied under the venv's name, along with the files it loads.
+        assert!(copy_executable_windows(
+            &home.join("python3.13t.exe"),
+            &scripts.join("python.exe"),
+            &home,
+            &home,
+            false,
+        )
+        .unwrap());
+        let mut files = fs_err::read_dir(&scripts)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(
+            files,
+            ["_ssl.pyd", "python.exe", "python313t.dll", "python313t.zip"]
+        );
+        assert_eq!(
+            fs_err::read_to_string(scripts.join("python.exe")).unwrap(),
+            "python3.13t.exe"
+        );
+
+        // A missing base executable is reported to the caller, which falls back to an error.
+        assert!(!copy_executable_windows(
+            &home.join("pythonw.exe"),
+            &scripts.join("pythonw.exe"),
+            &home,
+            &home,
+            false,
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn staging_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join(".venv");
+
+        // The staging directory is removed if it isn't persisted.
+        let staging = StagingDir::new(&venv).unwrap();
+        let path = staging.path().to_path_buf();
+        assert!(path.is_dir());
+        assert_eq!(path.parent(), Some(temp_dir.path()));
+        drop(staging);
+        assert!(!path.exists());
+        assert!(!venv.exists());
+
+        // Persisting replaces an existing venv.
+        fs_err::create_dir(&venv).unwrap();
+        fs_err::write(venv.join("stale"), "").unwrap();
+        let staging = StagingDir::new(&venv).unwrap();
+        fs_err::write(staging.path().join("pyvenv.cfg"), "").unwrap();
+        staging.persist(&venv).unwrap();
+        assert!(venv.join("pyvenv.cfg").is_file());
+        assert!(!venv.join("stale").exists());
+        assert_eq!(fs_err::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn nearest_project_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path().join("project");
+        let nested = project.join("src").join("nested");
+        fs_err::create_dir_all(&nested).unwrap();
+        assert_eq!(project_name(&nested), None);
+
+        fs_err::write(
+            project.join("pyproject.toml"),
+            "[project]\nname = \"my-project\"\n",
+        )
+        .unwrap();
+        assert_eq!(project_name(&nested), Some("my-project".to_string()));
+
+        // The nearest `pyproject.toml` wins, even without a project name.
+        fs_err::write(
+            nested.join("pyproject.toml"),
+            "[tool.uv.workspace]\nmembers = []\n",
+        )
+        .unwrap();
+        assert_eq!(project_name(&nested), None);
+    }
+
+    #[test]
+    fn removable_virtualenv() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join("venv");
+        fs_err::create_dir_all(venv.join("bin")).unwrap();
+        assert!(!is_removable_virtualenv(&venv).unwrap());
+
+        fs_err::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        assert!(is_removable_virtualenv(&venv).unwrap());
+    }
+
+    #[test]
+    fn quote_activation_values() {
+        let value = r#"/home/it's a "venv" $HOME\ü 100%!"#;
+        assert_eq!(
+            Quoting::Posix.quote(value),
+            r#"'/home/it'\''s a "venv" $HOME\ü 100%!'"#
+        );
+        assert_eq!(
+            Quoting::Csh.quote(value),
+            r#"'/home/it'\''s a "venv" $HOME\ü 100%\!'"#
+        );
+        assert_eq!(
+            Quoting::Fish.quote(value),
+            r#"'/home/it\'s a "venv" $HOME\\ü 100%!'"#
+        );
+        assert_eq!(
+            Quoting::Nushell.quote(value),
+            r#""/home/it's a \"venv\" $HOME\\ü 100%!""#
+        );
+        assert_eq!(
+            Quoting::Python.quote(value),
+            r#""/home/it's a \"venv\" $HOME\\ü 100%!""#
+        );
+        assert_eq!(
+            Quoting::PowerShell.quote("C:\\it's ‘my’ venv"),
+            "'C:\\it''s ‘‘my’’ venv'"
+        );
+        assert_eq!(Quoting::Batch.quote(r"C:\100% venv"), r"C:\100%% venv");
+    }
+
+    #[test]
+    fn hardlink_or_copy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("python");
+        fs_err::write(&source, "python").unwrap();
+
+        let linked = temp_dir.path().join("linked");
+        link_or_copy(&source, &linked, true).unwrap();
+        let copied = temp_dir.path().join("copied");
+        link_or_copy(&source, &copied, false).unwrap();
+
+        // Writing through the hard link is visible in the source, unlike the copy.
+        fs_err::write(&linked, "linked").unwrap();
+        assert_eq!(fs_err::read_to_string(&source).unwrap(), "linked");
+        assert_eq!(fs_err::read_to_string(&copied).unwrap(), "python");
+    }
+
+    #[test]
+    fn relative_libraries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let prefix = temp_dir.path().join("python");
+        let venv = temp_dir.path().join("venv");
+        fs_err::create_dir_all(prefix.join("bin")).unwrap();
+        fs_err::create_dir_all(prefix.join("lib")).unwrap();
+        fs_err::write(prefix.join("lib").join("libpython3.12.so.1.0"), "").unwrap();
+        let base_python = prefix.join("bin").join("python3.12");
+
+        // An executable that doesn't load libraries relative to itself, e.g., a wrapper script,
+        // doesn't need a copy.
+        fs_err::write(
+            &base_python,
+            "#!/bin/sh\n$ORIGIN/../lib\nlibpython3.12.so.1.0\n",
+        )
+        .unwrap();
+        copy_relative_libraries(&base_python, &venv, false).unwrap();
+        assert!(!venv.join("lib").exists());
+    }
+
+    #[test]
+    fn activation_env() {
+        let env = [("DEBUG".to_string(), "it's on".to_string())];
+        let template = "# activate\n{{ ACTIVATE_ENV }}\n# deactivate\n{{ DEACTIVATE_ENV }}\n";
+
+        let script = replace_placeholder(
+            template,
+            "{{ ACTIVATE_ENV }}",
+            &Quoting::Posix.activate_env(&env),
+        );
+        assert!(script.contains("DEBUG='it'\\''s on'\nexport DEBUG\n"));
+        assert!(script.contains("_OLD_VIRTUAL_ENV_DEBUG=\"$DEBUG\"\n"));
+
+        // Without variables, the placeholder lines are removed.
+        let script = replace_placeholder(template, "{{ ACTIVATE_ENV }}", "");
+        let script = replace_placeholder(&script, "{{ DEACTIVATE_ENV }}", "");
+        assert_eq!(script, "# activate\n# deactivate\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        use crate::Permissions;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs_err::create_dir_all(venv.join("bin")).unwrap();
+        fs_err::write(venv.join("pyvenv.cfg"), "").unwrap();
+        fs_err::write(venv.join("bin").join("activate"), "").unwrap();
+        fs_err::set_permissions(
+            venv.join("bin").join("activate"),
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+
+        let mode =
+            |path: &std::path::Path| fs_err::metadata(path).unwrap().permissions().mode() & 0o7777;
+
+        apply_permissions(&venv, Permissions::Private).unwrap();
+        assert_eq!(mode(&venv), 0o700);
+        assert_eq!(mode(&venv.join("pyvenv.cfg")), 0o600);
+        assert_eq!(mode(&venv.join("bin").join("activate")), 0o700);
+
+        apply_permissions(&venv, Permissions::Shared).unwrap();
+        assert_eq!(mode(&venv.join("bin")), 0o2775);
+        assert_eq!(mode(&venv.join("pyvenv.cfg")), 0o664);
+        assert_eq!(mode(&venv.join("bin").join("activate")), 0o775);
+
+        // Files hard-linked from the base interpreter keep their permissions.
+        let base_python = temp_dir.path().join("python3");
+        fs_err::write(&base_python, "").unwrap();
+        fs_err::set_permissions(&base_python, std::fs::Permissions::from_mode(0o755)).unwrap();
+        fs_err::hard_link(&base_python, venv.join("bin").join("python3")).unwrap();
+        apply_permissions(&venv, Permissions::Private).unwrap();
+        assert_eq!(mode(&base_python), 0o755);
+        assert_eq!(mode(&venv.join("bin").join("activate")), 0o700);
+    }
+
+    #[test]
+    fn encode_activation_scripts() {
+        let script = "$env:VIRTUAL_ENV_PROMPT = 'café'\n".to_string();
+        assert_eq!(
+            Quoting::PowerShell.encode(script.clone()),
+            [b"\xef\xbb\xbf".as_slice(), script.as_bytes()].concat()
+        );
+        assert_eq!(Quoting::Posix.encode(script.clone()), script.as_bytes());
+        assert_eq!(
+            Quoting::PowerShell.encode("$env:PATH\n".to_string()),
+            b"$env:PATH\n"
+        );
+    }
+
+    #[test]
+    fn conda_dlls() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let prefix = temp_dir.path();
+        let library_bin = prefix.join("Library").join("bin");
+        fs_err::create_dir_all(&library_bin).unwrap();
+
+        // Not a conda environment.
+        assert!(conda_dll_dirs(prefix).is_empty());
+
+        fs_err::create_dir_all(prefix.join("conda-meta")).unwrap();
+        let dll_dirs = conda_dll_dirs(prefix);
+        assert_eq!(dll_dirs, vec![library_bin]);
+
+        let pth = conda_dlls_pth(&[PathBuf::from(r"C:\conda\Library\bin")]);
+        assert!(pth.starts_with("import os; dirs = [\"C:\\\\conda\\\\Library\\\\bin\"];"));
+        assert_eq!(pth.lines().count(), 1);
+    }
+
+    #[test]
+    fn deterministic_timestamps() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs_err::create_dir_all(venv.join("bin")).unwrap();
+        fs_err::write(venv.join("pyvenv.cfg"), "").unwrap();
+        fs_err::write(venv.join("bin").join("activate"), "").unwrap();
+
+        // Files hard-linked from the base interpreter keep their timestamps.
+        #[cfg(unix)]
+        let base_python = {
+            let base_python = temp_dir.path().join("python3");
+            fs_err::write(&base_python, "").unwrap();
+            fs_err::hard_link(&base_python, venv.join("bin").join("python3")).unwrap();
+            base_python
+        };
+
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_secs(315_532_800);
+        normalize_timestamps(&venv, timestamp).unwrap();
+        #[cfg(unix)]
+        assert_ne!(
+            fs_err::metadata(base_python).unwrap().modified().unwrap(),
+            timestamp
+        );
+        for path in [venv.join("pyvenv.cfg"), venv.join("bin").join("activate")] {
+            assert_eq!(
+                fs_err::metadata(&path).unwrap().modified().unwrap(),
+                timestamp
+            );
+        }
+        #[cfg(unix)]
+        assert_eq!(
+            fs_err::metadata(venv.join("bin"))
+                .unwrap()
+                .modified()
+                .unwrap(),
+            timestamp
+        );
+    }
 }
@@ -1,14 +1,22 @@
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
 
 use thiserror::Error;
+use tracing::debug;
 
+use install_wheel_rs::linker::LinkMode;
 use platform_tags::PlatformError;
+use uv_fs::Simplified;
+use uv_interpreter::managed::InstalledToolchains;
 use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_normalize::PackageName;
 
-pub use crate::bare::create_bare_venv;
+use crate::bare::{create_bare_venv, normalize_timestamps, source_date_epoch};
+pub use crate::seed::{seed_package_names, SeedPackages};
 
 mod bare;
+mod seed;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -22,13 +30,33 @@ pub enum Error {
     Platform(#[from] PlatformError),
     #[error("Could not find a suitable Python executable for the virtual environment based on the interpreter: {0}")]
     NotFound(String),
+    #[error("No Python interpreter was provided for the virtual environment")]
+    MissingInterpreter,
+    #[error("Failed to seed `{0}` into the virtual environment")]
+    Seed(PackageName, #[source] install_wheel_rs::Error),
+    #[error("Post-create hook `{hook}` failed with {status}")]
+    PostCreate {
+        hook: PostCreate,
+        status: ExitStatus,
+    },
+    #[error("Invalid environment variable name `{0}`: names may only contain letters, numbers, and `_`, and may not start with a number")]
+    InvalidEnvName(String),
+    #[error("Invalid line {line} in `{}`: expected `NAME=value`", path.user_display())]
+    InvalidEnvFile { path: PathBuf, line: usize },
+    #[error("Virtual environment permissions are only supported on Unix; on this platform, the virtual environment inherits the ACLs of its parent directory")]
+    UnsupportedPermissions,
 }
 
 /// The value to use for the shell prompt when inside a virtual environment.
 #[derive(Debug)]
 pub enum Prompt {
-    /// Use the current directory name as the prompt.
+    /// Use the name of the project containing the current directory as the prompt, or the current
+    /// directory name if there is no project.
     CurrentDirectoryName,
+    /// Use the name of the project containing the virtual environment as the prompt. If there is
+    /// no project and the virtual environment's directory is hidden (e.g., `.venv`), use the name
+    /// of its parent directory; otherwise, fall back to [`Prompt::None`].
+    Derived,
     /// Use the fixed string as the prompt.
     Static(String),
     /// Default to no prompt. The prompt is then set by the activator script
@@ -36,35 +64,447 @@ pub enum Prompt {
     None,
 }
 
+/// The permissions of the files and directories in a virtualenv.
+///
+/// Permissions are only supported on Unix. On Windows, the virtualenv inherits the ACLs of its
+/// parent directory, and requesting permissions other than [`Permissions::Default`] is an error.
+///
+/// Files that are hard-linked into the virtualenv, e.g., with [`VenvBuilder::hardlinks`], keep
+/// their permissions, since changing them would change the permissions of the base interpreter.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Permissions {
+    /// Use the permissions implied by the umask of the current process.
+    #[default]
+    Default,
+    /// Make the virtualenv writable by the owning group, e.g., for environments shared between
+    /// CI jobs: directories are `2775`, such that new files inherit the group, files are `664`,
+    /// and executables are `775`.
+    Shared,
+    /// Restrict the virtualenv to its owner, e.g., on multi-user hosts: directories and
+    /// executables are `700`, and files are `600`.
+    Private,
+}
+
+/// What to do when the target directory of a virtualenv already exists.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OnExisting {
+    /// Fail unless the directory is empty.
+    Fail,
+    /// Write into the existing directory, regardless of its contents.
+    Allow,
+    /// Remove the existing directory and recreate it, if it looks like a virtualenv; otherwise,
+    /// fail unless the directory is empty.
+    #[default]
+    Clear,
+}
+
+impl OnExisting {
+    /// Determine the policy from the `--allow-existing` command line flag.
+    pub fn from_args(allow_existing: bool) -> Self {
+        if allow_existing {
+            Self::Allow
+        } else {
+            Self::Clear
+        }
+    }
+}
+
 impl Prompt {
     /// Determine the prompt value to be used from the command line arguments.
     pub fn from_args(prompt: Option<String>) -> Self {
         match prompt {
             Some(prompt) if prompt == "." => Self::CurrentDirectoryName,
             Some(prompt) => Self::Static(prompt),
-            None => Self::None,
+            None => Self::Derived,
+        }
+    }
+}
+
+/// A hook to run inside a virtualenv once it's created and seeded, see
+/// [`VenvBuilder::post_create`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostCreate {
+    /// A shell command, e.g., `pre-commit install`, run with the platform shell (`sh -c` or
+    /// `cmd /C`).
+    Command(String),
+    /// A requirements file to install into the virtualenv with `uv pip install -r`.
+    Requirements {
+        /// The path to the requirements file, e.g., `requirements.txt`.
+        path: PathBuf,
+        /// The `uv` executable to install the requirements with.
+        uv: PathBuf,
+    },
+}
+
+impl std::fmt::Display for PostCreate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Command(command) => f.write_str(command),
+            Self::Requirements { path, .. } => {
+                write!(f, "uv pip install -r {}", path.user_display())
+            }
         }
     }
 }
 
-/// Create a virtualenv.
-pub fn create_venv(
-    location: &Path,
-    interpreter: Interpreter,
+/// A builder for a virtualenv.
+///
+/// ```ignore
+/// let venv = VenvBuilder::new(".venv")
+///     .interpreter(interpreter)
+///     .prompt(Prompt::Static("my-project".to_string()))
+///     .system_site_packages(true)
+///     .create()?;
+/// ```
+#[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct VenvBuilder {
+    location: PathBuf,
+    interpreter: Option<Interpreter>,
     prompt: Prompt,
     system_site_packages: bool,
-    allow_existing: bool,
-) -> Result<PythonEnvironment, Error> {
-    // Create the virtualenv at the given location.
-    let virtualenv = create_bare_venv(
-        location,
-        &interpreter,
-        prompt,
-        system_site_packages,
-        allow_existing,
-    )?;
-
-    // Create the corresponding `PythonEnvironment`.
-    let interpreter = interpreter.with_virtualenv(virtualenv);
-    Ok(PythonEnvironment::from_interpreter(interpreter))
+    on_existing: OnExisting,
+    copies: bool,
+    hardlinks: bool,
+    extra_paths: Vec<PathBuf>,
+    seed: SeedPackages,
+    post_create: Option<PostCreate>,
+    activation_env: Vec<(String, String)>,
+    permissions: Permissions,
+    minimal: bool,
+    deterministic: bool,
+    toolchains: Option<InstalledToolchains>,
+}
+
+impl VenvBuilder {
+    /// Create a builder for a virtualenv at the given location.
+    pub fn new(location: impl Into<PathBuf>) -> Self {
+        Self {
+            location: location.into(),
+            interpreter: None,
+            prompt: Prompt::None,
+            system_site_packages: false,
+            on_existing: OnExisting::default(),
+            copies: false,
+            hardlinks: false,
+            extra_paths: Vec::new(),
+            seed: SeedPackages::default(),
+            post_create: None,
+            activation_env: Vec::new(),
+            permissions: Permissions::default(),
+            minimal: false,
+            deterministic: false,
+            toolchains: None,
+        }
+    }
+
+    /// The base interpreter of the virtualenv. Required.
+    #[must_use]
+    pub fn interpreter(mut self, interpreter: Interpreter) -> Self {
+        self.interpreter = Some(interpreter);
+        self
+    }
+
+    #[must_use]
+    pub fn prompt(mut self, prompt: Prompt) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Give the virtualenv access to the system site packages directory.
+    #[must_use]
+    pub fn system_site_packages(mut self, system_site_packages: bool) -> Self {
+        self.system_site_packages = system_site_packages;
+        self
+    }
+
+    /// What to do if the location already exists, see [`OnExisting`].
+    #[must_use]
+    pub fn on_existing(mut self, on_existing: OnExisting) -> Self {
+        self.on_existing = on_existing;
+        self
+    }
+
+    /// Copy the interpreter into the virtualenv instead of symlinking it, like
+    /// `python -m venv --copies`. On Windows, the interpreter is always copied.
+    #[must_use]
+    pub fn copies(mut self, copies: bool) -> Self {
+        self.copies = copies;
+        self
+    }
+
+    /// Hard-link the interpreter files into the virtualenv wherever they would otherwise be
+    /// copied, i.e., on Windows and with [`VenvBuilder::copies`], to share them between
+    /// environments on the same volume. Falls back to copying if the files can't be hard-linked,
+    /// e.g., across volumes.
+    #[must_use]
+    pub fn hardlinks(mut self, hardlinks: bool) -> Self {
+        self.hardlinks = hardlinks;
+        self
+    }
+
+    /// Extra `sys.path` entries for the virtualenv, e.g., source trees to expose without an
+    /// editable install. The entries are written to a `.pth` file in `site-packages`; relative
+    /// paths are resolved against the current working directory.
+    #[must_use]
+    pub fn extra_paths(mut self, extra_paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.extra_paths.extend(extra_paths);
+        self
+    }
+
+    /// The [`SeedPackages`] to install into the virtualenv.
+    #[must_use]
+    pub fn seed(mut self, seed: SeedPackages) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Environment variables to export in the activation scripts, e.g., read from a `.env` file
+    /// with [`read_env_file`]. Deactivating the virtualenv restores their previous values.
+    #[must_use]
+    pub fn activation_env(mut self, env: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.activation_env.extend(env);
+        self
+    }
+
+    /// The permissions of the created files and directories, see [`Permissions`].
+    ///
+    /// Packages installed into the virtualenv afterwards, including seed packages, are created
+    /// with the umask of the installing process; with [`Permissions::Shared`], they inherit the
+    /// group of their directory.
+    #[must_use]
+    pub fn permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    /// Create a minimal virtualenv, with only `pyvenv.cfg`, `site-packages`, and the `python`
+    /// executable, i.e., without activation scripts, versioned aliases like `python3`, or
+    /// `pythonw.exe`. Useful for build systems that run the virtualenv's interpreter directly.
+    #[must_use]
+    pub fn minimal(mut self, minimal: bool) -> Self {
+        self.minimal = minimal;
+        self
+    }
+
+    /// Create the virtualenv deterministically, such that creations from the same inputs are
+    /// byte-identical, e.g., for content-addressed build caches.
+    ///
+    /// The base executable isn't recorded in `pyvenv.cfg`, and all
+    /// timestamps, including those of the seed packages, are set to `SOURCE_DATE_EPOCH`, or
+    /// 1980-01-01 if unset. The `home` key is still recorded, since the interpreter needs it to
+    /// start. Files created by the post-create hook aren't normalized.
+    #[must_use]
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// The managed toolchains to record the use of the base interpreter's toolchain in, such that
+    /// it isn't pruned as unused. If unset, no use is recorded.
+    #[must_use]
+    pub fn toolchains(mut self, toolchains: InstalledToolchains) -> Self {
+        self.toolchains = Some(toolchains);
+        self
+    }
+
+    /// A [`PostCreate`] hook to run inside the virtualenv once it's created and seeded.
+    ///
+    /// The hook is run in the current directory, with the virtualenv activated, i.e., with
+    /// `VIRTUAL_ENV` set and its scripts directory prepended to `PATH`.
+    #[must_use]
+    pub fn post_create(mut self, hook: PostCreate) -> Self {
+        self.post_create = Some(hook);
+        self
+    }
+
+    /// Create the virtualenv, installing the [`SeedPackages`] into it.
+    pub fn create(mut self) -> Result<PythonEnvironment, Error> {
+        let interpreter = self.interpreter.take().ok_or(Error::MissingInterpreter)?;
+
+        // The names are substituted into the activation scripts verbatim.
+        if let Some((name, _)) = self
+            .activation_env
+            .iter()
+            .find(|(name, _)| !is_valid_env_name(name))
+        {
+            return Err(Error::InvalidEnvName(name.clone()));
+        }
+
+        // Create the virtualenv at the given location.
+        let virtualenv = create_bare_venv(&self, &interpreter)?;
+
+        // Record the use of the base interpreter's managed toolchain, if any, such that it isn't
+        // pruned as unused.
+        if let Some(toolchains) = &self.toolchains {
+            record_toolchain_use(&interpreter, toolchains);
+        }
+
+        // Create the corresponding `PythonEnvironment`.
+        let interpreter = interpreter.with_virtualenv(virtualenv);
+
+        // Install the seed packages, if any. In deterministic venvs, they're copied, since files
+        // hard-linked from the cache would keep the timestamps of the cache.
+        if !self.seed.is_empty() {
+            if self.deterministic {
+                self.seed
+                    .with_link_mode(LinkMode::Copy)
+                    .install(&interpreter)?;
+            } else {
+                self.seed.install(&interpreter)?;
+            }
+        }
+
+        // Normalize the timestamps once the virtualenv is populated.
+        if self.deterministic {
+            normalize_timestamps(interpreter.sys_prefix(), source_date_epoch())?;
+        }
+
+        let venv = PythonEnvironment::from_interpreter(interpreter);
+
+        // Run the post-create hook, if any.
+        if let Some(hook) = self.post_create {
+            run_post_create(hook, &venv)?;
+        }
+
+        Ok(venv)
+    }
+}
+
+/// Read the environment variables from a `.env` file, e.g., for [`VenvBuilder::activation_env`].
+///
+/// Each line is a `NAME=value` assignment, optionally prefixed with `export`; the value may be
+/// wrapped in single or double quotes. Empty lines and lines starting with `#` are ignored.
+pub fn read_env_file(path: &Path) -> Result<Vec<(String, String)>, Error> {
+    let content = fs_err::read_to_string(path)?;
+    let mut env = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((name, value)) = line.split_once('=') else {
+            return Err(Error::InvalidEnvFile {
+                path: path.to_path_buf(),
+                line: index + 1,
+            });
+        };
+        let name = name.trim();
+        if !is_valid_env_name(name) {
+            return Err(Error::InvalidEnvName(name.to_string()));
+        }
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)
+                    .and_then(|value| value.strip_suffix(*quote))
+            })
+            .unwrap_or(value);
+        env.push((name.to_string(), value.to_string()));
+    }
+    Ok(env)
+}
+
+/// Record the use of the managed toolchain the interpreter belongs to, if any.
+///
+/// Failures are not fatal, e.g., if the toolchain directory is read-only.
+fn record_toolchain_use(interpreter: &Interpreter, toolchains: &InstalledToolchains) {
+    if let Some(toolchain) = toolchains.find_for_interpreter(interpreter) {
+        if let Err(err) = toolchain.record_use() {
+            debug!("Failed to record use of toolchain `{toolchain}`: {err}");
+        }
+    }
+}
+
+/// Returns `true` if the name is a portable environment variable name, i.e.,
+/// `[A-Za-z_][A-Za-z0-9_]*`.
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Run a [`PostCreate`] hook with the virtualenv activated.
+fn run_post_create(hook: PostCreate, venv: &PythonEnvironment) -> Result<(), Error> {
+    debug!("Running post-create hook: `{hook}`");
+
+    let mut process = match &hook {
+        PostCreate::Command(command) if cfg!(windows) => {
+            let mut process = Command::new("cmd");
+            process.arg("/C").arg(command);
+            process
+        }
+        PostCreate::Command(command) => {
+            let mut process = Command::new("sh");
+            process.arg("-c").arg(command);
+            process
+        }
+        PostCreate::Requirements { path, uv } => {
+            let mut process = Command::new(uv);
+            process
+                .args(["pip", "install", "--python"])
+                .arg(venv.python_executable())
+                .arg("-r")
+                .arg(path);
+            process
+        }
+    };
+
+    let path = std::env::join_paths(
+        std::iter::once(venv.scripts().to_path_buf()).chain(
+            std::env::var_os("PATH")
+                .as_ref()
+                .iter()
+                .flat_map(std::env::split_paths),
+        ),
+    )
+    .map_err(io::Error::other)?;
+    process
+        .env("VIRTUAL_ENV", venv.root())
+        .env("PATH", path)
+        .env_remove("PYTHONHOME")
+        .env_remove("CONDA_PREFIX");
+
+    let status = process.status()?;
+    if !status.success() {
+        return Err(Error::PostCreate { hook, status });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_env_file;
+
+    #[test]
+    fn env_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(".env");
+        fs_err::write(
+            &path,
+            "# Settings\nDJANGO_SETTINGS_MODULE=app.settings\n\nexport DEBUG = '1'\nGREETING=\"hello world\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_env_file(&path).unwrap(),
+            vec![
+                (
+                    "DJANGO_SETTINGS_MODULE".to_string(),
+                    "app.settings".to_string()
+                ),
+                ("DEBUG".to_string(), "1".to_string()),
+                ("GREETING".to_string(), "hello world".to_string()),
+            ]
+        );
+
+        fs_err::write(&path, "DEBUG\n").unwrap();
+        assert!(read_env_file(&path).is_err());
+        fs_err::write(&path, "1DEBUG=1\n").unwrap();
+        assert!(read_env_file(&path).is_err());
+    }
 }
@@ -0,0 +1,79 @@
+//! Seed packages, e.g., `pip`, into a new virtual environment from cached wheels.
+
+use std::path::PathBuf;
+
+use distribution_filename::WheelFilename;
+use install_wheel_rs::linker::LinkMode;
+use tracing::debug;
+
+use uv_interpreter::Interpreter;
+use uv_normalize::PackageName;
+
+use crate::Error;
+
+/// Unzipped wheels to install into a new virtual environment, e.g., from the uv cache.
+///
+/// Seeding an environment with `pip` allows plain `pip` workflows to use it, as with environments
+/// created by `virtualenv` or `python -m venv`. See [`seed_package_names`] for the packages these
+/// tools install.
+#[derive(Debug, Clone, Default)]
+pub struct SeedPackages {
+    wheels: Vec<(WheelFilename, PathBuf)>,
+    link_mode: LinkMode,
+}
+
+impl SeedPackages {
+    /// Install the unzipped wheel at `path`, e.g., a wheel in the uv cache.
+    #[must_use]
+    pub fn with_wheel(mut self, filename: WheelFilename, path: impl Into<PathBuf>) -> Self {
+        self.wheels.push((filename, path.into()));
+        self
+    }
+
+    /// Install the wheels with the given [`LinkMode`], e.g., to copy rather than hard link files
+    /// from the cache.
+    #[must_use]
+    pub fn with_link_mode(mut self, link_mode: LinkMode) -> Self {
+        self.link_mode = link_mode;
+        self
+    }
+
+    /// Returns `true` if there are no packages to seed.
+    pub fn is_empty(&self) -> bool {
+        self.wheels.is_empty()
+    }
+
+    /// Install the wheels into the virtual environment of the interpreter.
+    pub(crate) fn install(&self, interpreter: &Interpreter) -> Result<(), Error> {
+        let layout = interpreter.layout();
+        for (filename, path) in &self.wheels {
+            debug!("Seeding {filename} into the virtual environment");
+            install_wheel_rs::linker::install_wheel(
+                &layout,
+                path,
+                filename,
+                None,
+                Some("uv"),
+                self.link_mode,
+            )
+            .map_err(|err| Error::Seed(filename.name.clone(), err))?;
+        }
+        Ok(())
+    }
+}
+
+/// The packages to seed into a virtual environment for the interpreter, like `virtualenv`.
+///
+/// `setuptools` and `wheel` are only included before Python 3.12, which no longer bundles
+/// `setuptools` with `ensurepip`.
+pub fn seed_package_names(interpreter: &Interpreter) -> Vec<PackageName> {
+    let names: &[&str] = if interpreter.python_tuple() < (3, 12) {
+        &["pip", "setuptools", "wheel"]
+    } else {
+        &["pip"]
+    };
+    names
+        .iter()
+        .map(|name| PackageName::new((*name).to_string()).expect("valid package name"))
+        .collect()
+}
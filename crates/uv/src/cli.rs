@@ -99,6 +99,13 @@ pub(crate) struct GlobalArgs {
     /// parent directories.
     #[arg(global = true, long, hide = true)]
     pub(crate) isolated: bool,
+
+    /// Display absolute paths in output, for consumption by scripts.
+    ///
+    /// By default, paths are displayed relative to the current directory, where possible. Paths in
+    /// errors and warnings are always displayed relative to the current directory.
+    #[arg(global = true, long, env = "UV_PORCELAIN", value_parser = clap::builder::BoolishValueParser::new())]
+    pub(crate) porcelain: bool,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -113,6 +120,24 @@ pub(crate) enum ColorChoice {
     Never,
 }
 
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum VenvPermissions {
+    /// Make the virtual environment writable by the owning group.
+    Shared,
+
+    /// Restrict the virtual environment to its owner.
+    Private,
+}
+
+impl From<VenvPermissions> for uv_virtualenv::Permissions {
+    fn from(value: VenvPermissions) -> Self {
+        match value {
+            VenvPermissions::Shared => Self::Shared,
+            VenvPermissions::Private => Self::Private,
+        }
+    }
+}
+
 impl From<ColorChoice> for anstream::ColorChoice {
     fn from(value: ColorChoice) -> Self {
         match value {
@@ -253,6 +278,14 @@ fn parse_index_url(input: &str) -> Result<Maybe<IndexUrl>, String> {
     }
 }
 
+/// Parse a `NAME=value` environment variable assignment.
+fn parse_env_var(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("expected `NAME=value`, found `{input}`")),
+    }
+}
+
 /// Parse a string into a [`PathBuf`], mapping the empty string to `None`.
 fn parse_file_path(input: &str) -> Result<Maybe<PathBuf>, String> {
     if input.is_empty() {
@@ -1588,14 +1621,24 @@ pub(crate) struct VenvArgs {
     #[arg(default_value = ".venv")]
     pub(crate) name: PathBuf,
 
+    /// Create the virtual environment in the central environment directory under the given name,
+    /// e.g., `~/.local/share/uv/envs/<NAMED>`, instead of at a path.
+    ///
+    /// Named environments can be requested with `--python env:<NAMED>`. The central directory can
+    /// be set with `UV_ENVIRONMENT_DIR`.
+    #[arg(long, conflicts_with = "name")]
+    pub(crate) named: Option<String>,
+
     /// Provide an alternative prompt prefix for the virtual environment.
     ///
-    /// The default behavior depends on whether the virtual environment path is provided:
+    /// By default, the prompt is set to the name of the project in the nearest `pyproject.toml`
+    /// above the virtual environment. Without a project, the default behavior depends on whether
+    /// the virtual environment path is provided:
     /// - If provided (`uv venv project`), the prompt is set to the virtual environment's directory name.
     /// - If not provided (`uv venv`), the prompt is set to the current directory's name.
     ///
     /// Possible values:
-    /// - `.`: Use the current directory name.
+    /// - `.`: Use the name of the current project, or the current directory name.
     /// - Any string: Use the given string.
     #[arg(long, verbatim_doc_comment)]
     pub(crate) prompt: Option<String>,
@@ -1610,6 +1653,80 @@ pub(crate) struct VenvArgs {
     #[arg(long)]
     pub(crate) system_site_packages: bool,
 
+    /// Copy the Python interpreter into the virtual environment instead of symlinking it.
+    ///
+    /// Useful on filesystems or deployment targets where symlinks break, e.g., some container
+    /// layers. On Windows, the interpreter is always copied.
+    #[arg(long)]
+    pub(crate) copies: bool,
+
+    /// Hard-link the Python interpreter into the virtual environment wherever it would otherwise
+    /// be copied, i.e., on Windows or with `--copies`.
+    ///
+    /// Saves space across many virtual environments on the same volume. Falls back to copying if
+    /// the interpreter is on a different volume.
+    #[arg(long)]
+    pub(crate) hardlinks: bool,
+
+    /// A shell command to run inside the virtual environment once it's created and seeded.
+    ///
+    /// The command is run with `sh -c` (or `cmd /C` on Windows), with `VIRTUAL_ENV` set and the
+    /// virtual environment's scripts directory first on the `PATH`, e.g.,
+    /// `--post-create "pre-commit install"`.
+    #[arg(long)]
+    pub(crate) post_create: Option<String>,
+
+    /// A requirements file to install into the virtual environment once it's created and seeded,
+    /// as with `uv pip install -r`.
+    #[arg(long, conflicts_with = "post_create")]
+    pub(crate) post_create_requirements: Option<PathBuf>,
+
+    /// An environment variable to export when the virtual environment is activated, as
+    /// `NAME=value`.
+    ///
+    /// The variable is set by the activation scripts, and restored to its previous value (or
+    /// unset) by `deactivate`. May be provided multiple times.
+    #[arg(long = "env", value_parser = parse_env_var)]
+    pub(crate) env: Vec<(String, String)>,
+
+    /// A `.env` file with environment variables to export when the virtual environment is
+    /// activated.
+    ///
+    /// Each line is a `NAME=value` assignment. Variables given with `--env` take precedence.
+    #[arg(long)]
+    pub(crate) env_file: Option<PathBuf>,
+
+    /// The permissions of the files and directories in the virtual environment.
+    ///
+    /// By default, permissions follow the umask. With `shared`, the virtual environment is
+    /// writable by the owning group (directories `2775`, files `664`); with `private`, it's only
+    /// accessible by its owner (directories `700`, files `600`).
+    ///
+    /// Files that are hard-linked into the virtual environment, e.g., with `--hardlinks`, keep
+    /// their permissions, since they're shared with the base interpreter.
+    ///
+    /// Only supported on Unix; on Windows, the virtual environment inherits the ACLs of its parent
+    /// directory, and the option is rejected.
+    #[arg(long, value_enum)]
+    pub(crate) permissions: Option<VenvPermissions>,
+
+    /// Create a minimal virtual environment, without activation scripts or extra executables.
+    ///
+    /// The virtual environment only contains `pyvenv.cfg`, `site-packages`, and the `python`
+    /// executable, e.g., for build systems that run the virtual environment's interpreter
+    /// directly.
+    #[arg(long)]
+    pub(crate) minimal: bool,
+
+    /// Create the virtual environment deterministically, for reproducible builds.
+    ///
+    /// The base interpreter's path isn't recorded in `pyvenv.cfg`, and all
+    /// timestamps are set to `SOURCE_DATE_EPOCH` (or 1980-01-01, if unset), such that virtual
+    /// environments created from the same inputs are byte-identical. Seed packages are copied
+    /// rather than linked from the cache, such that their timestamps are normalized too.
+    #[arg(long)]
+    pub(crate) deterministic: bool,
+
     /// The method to use when installing packages from the global cache.
     ///
     /// This option is only used for installing seed packages.
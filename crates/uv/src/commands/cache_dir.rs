@@ -1,8 +1,8 @@
 use owo_colors::OwoColorize;
 use uv_cache::Cache;
-use uv_fs::Simplified;
+use uv_fs::{PathDisplayMode, Simplified};
 
 /// Show the cache directory.
-pub(crate) fn cache_dir(cache: &Cache) {
-    anstream::println!("{}", cache.root().user_display().cyan());
+pub(crate) fn cache_dir(cache: &Cache, path_display: PathDisplayMode) {
+    anstream::println!("{}", cache.root().user_display_with(path_display).cyan());
 }
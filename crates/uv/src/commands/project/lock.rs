@@ -159,7 +159,8 @@ pub(super) async fn do_lock(
     let reinstall = Reinstall::default();
     let setup_py = SetupPyStrategy::default();
 
-    let hasher = HashStrategy::Generate;
+    // Generate hashes for the lockfile, but validate archives pinned to a hash in `tool.uv.sources`.
+    let hasher = HashStrategy::Verify(workspace.source_hashes()?);
     let options = OptionsBuilder::new().exclude_newer(exclude_newer).build();
 
     // If an existing lockfile exists, build up a set of preferences.
@@ -19,6 +19,7 @@ use uv_distribution::Workspace;
 use uv_fs::Simplified;
 use uv_git::GitResolver;
 use uv_installer::{SatisfiesResult, SitePackages};
+use uv_interpreter::managed::InstalledToolchains;
 use uv_interpreter::{find_default_interpreter, PythonEnvironment};
 use uv_requirements::{RequirementsSource, RequirementsSpecification};
 use uv_resolver::{FlatIndex, InMemoryIndex, Options, RequiresPython};
@@ -42,6 +43,9 @@ pub(crate) enum ProjectError {
     #[error(transparent)]
     Virtualenv(#[from] uv_virtualenv::Error),
 
+    #[error(transparent)]
+    Workspace(#[from] uv_distribution::WorkspaceError),
+
     #[error(transparent)]
     Tags(#[from] platform_tags::TagsError),
 
@@ -100,13 +104,11 @@ pub(crate) fn init_environment(
                 venv.user_display().cyan()
             )?;
 
-            Ok(uv_virtualenv::create_venv(
-                &venv,
-                interpreter,
-                uv_virtualenv::Prompt::None,
-                false,
-                false,
-            )?)
+            let mut builder = uv_virtualenv::VenvBuilder::new(&venv).interpreter(interpreter);
+            if let Ok(toolchains) = InstalledToolchains::from_settings() {
+                builder = builder.toolchains(toolchains);
+            }
+            Ok(builder.create()?)
         }
         Err(e) => Err(e.into()),
     }
@@ -64,13 +64,9 @@ pub(crate) async fn run(
     let uv_state_path = std::env::current_dir()?.join(".uv");
     fs_err::create_dir_all(&uv_state_path)?;
     let tmpdir = tempdir_in(uv_state_path)?;
-    let venv = uv_virtualenv::create_venv(
-        tmpdir.path(),
-        interpreter,
-        uv_virtualenv::Prompt::None,
-        false,
-        false,
-    )?;
+    let venv = uv_virtualenv::VenvBuilder::new(tmpdir.path())
+        .interpreter(interpreter)
+        .create()?;
 
     // Install the ephemeral requirements.
     let ephemeral_env = Some(
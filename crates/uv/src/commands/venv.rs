@@ -1,7 +1,6 @@
 use std::fmt::Write;
 use std::path::Path;
 use std::str::FromStr;
-use std::vec;
 
 use anstream::eprint;
 use anyhow::Result;
@@ -9,7 +8,7 @@ use miette::{Diagnostic, IntoDiagnostic};
 use owo_colors::OwoColorize;
 use thiserror::Error;
 
-use distribution_types::IndexLocations;
+use distribution_types::{CachedDist, IndexLocations};
 use install_wheel_rs::linker::LinkMode;
 use pypi_types::Requirement;
 use uv_auth::store_credentials_from_url;
@@ -18,9 +17,12 @@ use uv_client::{Connectivity, FlatIndexClient, RegistryClientBuilder};
 use uv_configuration::{Concurrency, KeyringProviderType, PreviewMode};
 use uv_configuration::{ConfigSettings, IndexStrategy, NoBinary, NoBuild, SetupPyStrategy};
 use uv_dispatch::BuildDispatch;
-use uv_fs::Simplified;
+use uv_distribution::DistributionDatabase;
+use uv_fs::{PathDisplayMode, Simplified};
 use uv_git::GitResolver;
-use uv_interpreter::{PythonEnvironment, SystemPython};
+use uv_installer::Downloader;
+use uv_interpreter::managed::InstalledToolchains;
+use uv_interpreter::{Interpreter, PythonEnvironment, SystemPython};
 use uv_resolver::{ExcludeNewer, FlatIndex, InMemoryIndex, OptionsBuilder};
 use uv_types::{BuildContext, BuildIsolation, HashStrategy, InFlight};
 
@@ -43,12 +45,22 @@ pub(crate) async fn venv(
     keyring_provider: KeyringProviderType,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    copies: bool,
+    hardlinks: bool,
+    post_create: Option<&str>,
+    post_create_requirements: Option<&Path>,
+    env: Vec<(String, String)>,
+    env_file: Option<&Path>,
+    permissions: uv_virtualenv::Permissions,
+    minimal: bool,
+    deterministic: bool,
     connectivity: Connectivity,
     seed: bool,
     allow_existing: bool,
     exclude_newer: Option<ExcludeNewer>,
     native_tls: bool,
     preview: PreviewMode,
+    path_display: PathDisplayMode,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -61,12 +73,22 @@ pub(crate) async fn venv(
         keyring_provider,
         prompt,
         system_site_packages,
+        copies,
+        hardlinks,
+        post_create,
+        post_create_requirements,
+        env,
+        env_file,
+        permissions,
+        minimal,
+        deterministic,
         connectivity,
         seed,
         preview,
         allow_existing,
         exclude_newer,
         native_tls,
+        path_display,
         cache,
         printer,
     )
@@ -90,6 +112,13 @@ enum VenvError {
     #[diagnostic(code(uv::venv::seed))]
     Seed(#[source] anyhow::Error),
 
+    #[error("Can't seed the virtualenv: the interpreter at `{0}` is missing `ensurepip`")]
+    #[diagnostic(
+        code(uv::venv::ensurepip),
+        help("On Debian and Ubuntu, install the `python3-venv` package, or omit `--seed`")
+    )]
+    MissingEnsurepip(String),
+
     #[error("Failed to extract interpreter tags")]
     #[diagnostic(code(uv::venv::tags))]
     Tags(#[source] platform_tags::TagsError),
@@ -110,12 +139,22 @@ async fn venv_impl(
     keyring_provider: KeyringProviderType,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    copies: bool,
+    hardlinks: bool,
+    post_create: Option<&str>,
+    post_create_requirements: Option<&Path>,
+    env: Vec<(String, String)>,
+    env_file: Option<&Path>,
+    permissions: uv_virtualenv::Permissions,
+    minimal: bool,
+    deterministic: bool,
     connectivity: Connectivity,
     seed: bool,
     preview: PreviewMode,
     allow_existing: bool,
     exclude_newer: Option<ExcludeNewer>,
     native_tls: bool,
+    path_display: PathDisplayMode,
     cache: &Cache,
     printer: Printer,
 ) -> miette::Result<ExitStatus> {
@@ -134,120 +173,99 @@ async fn venv_impl(
         printer.stderr(),
         "Using Python {} interpreter at: {}",
         interpreter.python_version(),
-        interpreter.sys_executable().user_display().cyan()
+        interpreter
+            .sys_executable()
+            .user_display_with(path_display)
+            .cyan()
     )
     .into_diagnostic()?;
 
+    // Interpreters without `ensurepip`, e.g., Debian and Ubuntu Pythons without `python3-venv`,
+    // can't provide a complete seeded environment; fail before creating it.
+    if seed && !interpreter.has_ensurepip() {
+        return Err(VenvError::MissingEnsurepip(
+            interpreter.sys_executable().user_display().to_string(),
+        )
+        .into());
+    }
+
+    // Fetch the seed packages into the cache, such that they're installed from cached wheels
+    // when the virtual environment is created.
+    let wheels = if seed {
+        fetch_seed_packages(
+            &interpreter,
+            link_mode,
+            index_locations,
+            index_strategy,
+            keyring_provider,
+            connectivity,
+            preview,
+            exclude_newer,
+            native_tls,
+            cache,
+        )
+        .await?
+    } else {
+        Vec::new()
+    };
+    let seed_packages = wheels.iter().fold(
+        uv_virtualenv::SeedPackages::default().with_link_mode(link_mode),
+        |seed_packages, wheel| seed_packages.with_wheel(wheel.filename().clone(), wheel.path()),
+    );
+
     writeln!(
         printer.stderr(),
         "Creating virtualenv at: {}",
-        path.user_display().cyan()
+        path.user_display_with(path_display).cyan()
     )
     .into_diagnostic()?;
 
-    // Create the virtual environment.
-    let venv = uv_virtualenv::create_venv(
-        path,
-        interpreter,
-        prompt,
-        system_site_packages,
-        allow_existing,
-    )
-    .map_err(VenvError::Creation)?;
-
-    // Install seed packages.
-    if seed {
-        // Extract the interpreter.
-        let interpreter = venv.interpreter();
-
-        // Instantiate a client.
-        let client = RegistryClientBuilder::new(cache.clone())
-            .native_tls(native_tls)
-            .index_urls(index_locations.index_urls())
-            .index_strategy(index_strategy)
-            .keyring(keyring_provider)
-            .connectivity(connectivity)
-            .markers(interpreter.markers())
-            .platform(interpreter.platform())
-            .build();
-
-        // Resolve the flat indexes from `--find-links`.
-        let flat_index = {
-            let tags = interpreter.tags().map_err(VenvError::Tags)?;
-            let client = FlatIndexClient::new(&client, cache);
-            let entries = client
-                .fetch(index_locations.flat_index())
-                .await
-                .map_err(VenvError::FlatIndex)?;
-            FlatIndex::from_entries(
-                entries,
-                tags,
-                &HashStrategy::None,
-                &NoBuild::All,
-                &NoBinary::None,
-            )
-        };
-
-        // Create a shared in-memory index.
-        let index = InMemoryIndex::default();
-        let git = GitResolver::default();
-
-        // Track in-flight downloads, builds, etc., across resolutions.
-        let in_flight = InFlight::default();
+    // Read the activation environment variables, with `--env` taking precedence over the file.
+    let activation_env = match env_file {
+        Some(env_file) => uv_virtualenv::read_env_file(env_file).map_err(VenvError::Creation)?,
+        None => Vec::new(),
+    };
+    let mut activation_env = activation_env
+        .into_iter()
+        .filter(|(name, _)| !env.iter().any(|(override_name, _)| override_name == name))
+        .collect::<Vec<_>>();
+    activation_env.extend(env);
 
-        // For seed packages, assume the default settings and concurrency is sufficient.
-        let config_settings = ConfigSettings::default();
-        let concurrency = Concurrency::default();
+    // The post-create hook runs once the virtual environment is seeded, such that it can use the
+    // seed packages.
+    let post_create = match (post_create, post_create_requirements) {
+        (Some(command), _) => Some(uv_virtualenv::PostCreate::Command(command.to_string())),
+        (None, Some(requirements)) => Some(uv_virtualenv::PostCreate::Requirements {
+            path: requirements.to_path_buf(),
+            uv: std::env::current_exe().into_diagnostic()?,
+        }),
+        (None, None) => None,
+    };
 
-        // Prep the build context.
-        let build_dispatch = BuildDispatch::new(
-            &client,
-            cache,
-            interpreter,
-            index_locations,
-            &flat_index,
-            &index,
-            &git,
-            &in_flight,
-            SetupPyStrategy::default(),
-            &config_settings,
-            BuildIsolation::Isolated,
-            link_mode,
-            &NoBuild::All,
-            &NoBinary::None,
-            concurrency,
-            preview,
-        )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
-
-        // Resolve the seed packages.
-        let requirements = if interpreter.python_tuple() < (3, 12) {
-            // Only include `setuptools` and `wheel` on Python <3.12
-            vec![
-                Requirement::from(pep508_rs::Requirement::from_str("pip").unwrap()),
-                Requirement::from(pep508_rs::Requirement::from_str("setuptools").unwrap()),
-                Requirement::from(pep508_rs::Requirement::from_str("wheel").unwrap()),
-            ]
-        } else {
-            vec![Requirement::from(
-                pep508_rs::Requirement::from_str("pip").unwrap(),
-            )]
-        };
-
-        // Resolve and install the requirements.
-        //
-        // Since the virtual environment is empty, and the set of requirements is trivial (no
-        // constraints, no editables, etc.), we can use the build dispatch APIs directly.
-        let resolution = build_dispatch
-            .resolve(&requirements)
-            .await
-            .map_err(VenvError::Seed)?;
-        let installed = build_dispatch
-            .install(&resolution, &venv)
-            .await
-            .map_err(VenvError::Seed)?;
+    // Create the virtual environment.
+    let mut builder = uv_virtualenv::VenvBuilder::new(path)
+        .interpreter(interpreter)
+        .prompt(prompt)
+        .system_site_packages(system_site_packages)
+        .on_existing(uv_virtualenv::OnExisting::from_args(allow_existing))
+        .copies(copies)
+        .hardlinks(hardlinks)
+        .activation_env(activation_env)
+        .permissions(permissions)
+        .minimal(minimal)
+        .deterministic(deterministic)
+        .seed(seed_packages);
+    if let Some(post_create) = post_create {
+        builder = builder.post_create(post_create);
+    }
+    if let Ok(toolchains) = InstalledToolchains::from_settings() {
+        builder = builder.toolchains(toolchains);
+    }
+    let venv = builder.create().map_err(VenvError::Creation)?;
 
-        pip::operations::report_modifications(installed, Vec::new(), Vec::new(), printer)
+    // Report the seed packages installed into the virtual environment.
+    if !wheels.is_empty() {
+        pip::operations::report_modifications(wheels, Vec::new(), Vec::new(), printer)
             .into_diagnostic()?;
     }
 
@@ -276,13 +294,124 @@ async fn venv_impl(
         )),
         Some(Shell::Cmd) => Some(shlex_windows(venv.scripts().join("activate"), Shell::Cmd)),
     };
-    if let Some(act) = activation {
+    // Minimal virtual environments don't have activation scripts.
+    if let Some(act) = activation.filter(|_| !minimal) {
         writeln!(printer.stderr(), "Activate with: {}", act.green()).into_diagnostic()?;
     }
 
     Ok(ExitStatus::Success)
 }
 
+/// Resolve the seed packages for the interpreter, see [`uv_virtualenv::seed_package_names`], and
+/// fetch their wheels into the cache.
+#[allow(clippy::too_many_arguments)]
+async fn fetch_seed_packages(
+    interpreter: &Interpreter,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProviderType,
+    connectivity: Connectivity,
+    preview: PreviewMode,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    cache: &Cache,
+) -> Result<Vec<CachedDist>, VenvError> {
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .native_tls(native_tls)
+        .index_urls(index_locations.index_urls())
+        .index_strategy(index_strategy)
+        .keyring(keyring_provider)
+        .connectivity(connectivity)
+        .markers(interpreter.markers())
+        .platform(interpreter.platform())
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let tags = interpreter.tags().map_err(VenvError::Tags)?;
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, cache);
+        let entries = client
+            .fetch(index_locations.flat_index())
+            .await
+            .map_err(VenvError::FlatIndex)?;
+        FlatIndex::from_entries(
+            entries,
+            tags,
+            &HashStrategy::None,
+            &NoBuild::All,
+            &NoBinary::None,
+        )
+    };
+
+    // Create a shared in-memory index.
+    let index = InMemoryIndex::default();
+    let git = GitResolver::default();
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    // For seed packages, assume the default settings and concurrency is sufficient.
+    let config_settings = ConfigSettings::default();
+    let concurrency = Concurrency::default();
+
+    // Prep the build context.
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        cache,
+        interpreter,
+        index_locations,
+        &flat_index,
+        &index,
+        &git,
+        &in_flight,
+        SetupPyStrategy::default(),
+        &config_settings,
+        BuildIsolation::Isolated,
+        link_mode,
+        &NoBuild::All,
+        &NoBinary::None,
+        concurrency,
+        preview,
+    )
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+
+    // Resolve the seed packages.
+    //
+    // Since the set of requirements is trivial (no constraints, no editables, etc.), we can use
+    // the build dispatch APIs directly.
+    let requirements = uv_virtualenv::seed_package_names(interpreter)
+        .into_iter()
+        .map(|name| {
+            Requirement::from(
+                pep508_rs::Requirement::from_str(name.as_ref())
+                    .expect("Package names are valid requirements"),
+            )
+        })
+        .collect::<Vec<_>>();
+    let resolution = build_dispatch
+        .resolve(&requirements)
+        .await
+        .map_err(VenvError::Seed)?;
+
+    // Download and unzip the wheels into the cache.
+    let remote = resolution
+        .packages()
+        .filter_map(|name| resolution.get_remote(name).cloned())
+        .collect::<Vec<_>>();
+    let downloader = Downloader::new(
+        cache,
+        tags,
+        &HashStrategy::None,
+        DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads, preview),
+    );
+    downloader
+        .download(remote, &in_flight)
+        .await
+        .map_err(|err| VenvError::Seed(err.into()))
+}
+
 /// Quote a path, if necessary, for safe use in a POSIX-compatible shell command.
 fn shlex_posix(executable: impl AsRef<Path>) -> String {
     // Convert to a display path.
@@ -514,7 +514,7 @@ async fn run() -> Result<ExitStatus> {
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Dir,
         }) => {
-            commands::cache_dir(&cache);
+            commands::cache_dir(&cache, globals.path_display);
             Ok(ExitStatus::Success)
         }
         Commands::Venv(args) => {
@@ -526,17 +526,27 @@ async fn run() -> Result<ExitStatus> {
             // Initialize the cache.
             let cache = cache.init()?;
 
-            // Since we use ".venv" as the default name, we use "." as the default prompt.
-            let prompt = args.prompt.or_else(|| {
-                if args.name == PathBuf::from(".venv") {
-                    Some(".".to_string())
-                } else {
-                    None
-                }
-            });
+            // Named environments are created in the central environment directory, and use their
+            // name as the default prompt.
+            let (path, prompt) = if let Some(named) = args.named {
+                let path = uv_interpreter::NamedEnvironments::from_settings()?
+                    .init()?
+                    .path(&named)?;
+                (path, args.prompt.or(Some(named)))
+            } else {
+                // Since we use ".venv" as the default name, we use "." as the default prompt.
+                let prompt = args.prompt.or_else(|| {
+                    if args.name == PathBuf::from(".venv") {
+                        Some(".".to_string())
+                    } else {
+                        None
+                    }
+                });
+                (args.name, prompt)
+            };
 
             commands::venv(
-                &args.name,
+                &path,
                 args.shared.python.as_deref(),
                 args.shared.link_mode,
                 &args.shared.index_locations,
@@ -544,12 +554,22 @@ async fn run() -> Result<ExitStatus> {
                 args.shared.keyring_provider,
                 uv_virtualenv::Prompt::from_args(prompt),
                 args.system_site_packages,
+                args.copies,
+                args.hardlinks,
+                args.post_create.as_deref(),
+                args.post_create_requirements.as_deref(),
+                args.env,
+                args.env_file.as_deref(),
+                args.permissions,
+                args.minimal,
+                args.deterministic,
                 globals.connectivity,
                 args.seed,
                 args.allow_existing,
                 args.shared.exclude_newer,
                 globals.native_tls,
                 globals.preview,
+                globals.path_display,
                 &cache,
                 printer,
             )
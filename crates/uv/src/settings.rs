@@ -15,6 +15,7 @@ use uv_configuration::{
     Concurrency, ConfigSettings, ExtrasSpecification, IndexStrategy, KeyringProviderType, NoBinary,
     NoBuild, PreviewMode, Reinstall, SetupPyStrategy, TargetTriple, Upgrade,
 };
+use uv_fs::PathDisplayMode;
 use uv_interpreter::{Prefix, PythonVersion, Target};
 use uv_normalize::PackageName;
 use uv_resolver::{AnnotationStyle, DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode};
@@ -38,6 +39,7 @@ pub(crate) struct GlobalSettings {
     pub(crate) connectivity: Connectivity,
     pub(crate) isolated: bool,
     pub(crate) preview: PreviewMode,
+    pub(crate) path_display: PathDisplayMode,
 }
 
 impl GlobalSettings {
@@ -80,6 +82,11 @@ impl GlobalSettings {
                     .combine(workspace.and_then(|workspace| workspace.options.preview))
                     .unwrap_or(false),
             ),
+            path_display: if args.porcelain {
+                PathDisplayMode::Porcelain
+            } else {
+                PathDisplayMode::Human
+            },
         }
     }
 }
@@ -1001,8 +1008,18 @@ pub(crate) struct VenvSettings {
     pub(crate) seed: bool,
     pub(crate) allow_existing: bool,
     pub(crate) name: PathBuf,
+    pub(crate) named: Option<String>,
     pub(crate) prompt: Option<String>,
     pub(crate) system_site_packages: bool,
+    pub(crate) copies: bool,
+    pub(crate) hardlinks: bool,
+    pub(crate) post_create: Option<String>,
+    pub(crate) post_create_requirements: Option<PathBuf>,
+    pub(crate) env: Vec<(String, String)>,
+    pub(crate) env_file: Option<PathBuf>,
+    pub(crate) permissions: uv_virtualenv::Permissions,
+    pub(crate) minimal: bool,
+    pub(crate) deterministic: bool,
 
     // CLI-only settings.
     pub(crate) shared: PipSharedSettings,
@@ -1018,8 +1035,18 @@ impl VenvSettings {
             seed,
             allow_existing,
             name,
+            named,
             prompt,
             system_site_packages,
+            copies,
+            hardlinks,
+            post_create,
+            post_create_requirements,
+            env,
+            env_file,
+            permissions,
+            minimal,
+            deterministic,
             link_mode,
             index_url,
             extra_index_url,
@@ -1036,8 +1063,18 @@ impl VenvSettings {
             seed,
             allow_existing,
             name,
+            named,
             prompt,
             system_site_packages,
+            copies,
+            hardlinks,
+            post_create,
+            post_create_requirements,
+            env,
+            env_file,
+            permissions: permissions.map(Into::into).unwrap_or_default(),
+            minimal,
+            deterministic,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
@@ -203,6 +203,141 @@ fn seed() {
     context.venv.assert(predicates::path::is_dir());
 }
 
+#[test]
+fn seed_from_cache() {
+    let context = VenvTestContext::new(&["3.12"]);
+    context
+        .venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--seed")
+        .arg("--python")
+        .arg("3.12")
+        .assert()
+        .success();
+
+    // The seed packages are installed from the cached wheels, without network access.
+    let venv = ChildPath::new(context.temp_dir.canonicalize().unwrap().join("offline"));
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(venv.as_os_str())
+        .arg("--seed")
+        .arg("--offline")
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.[X] interpreter at: [PATH]
+    Creating virtualenv at: offline
+     + pip==24.0
+    Activate with: source offline/bin/activate
+    "###
+    );
+
+    venv.child("pyvenv.cfg").assert(predicates::path::is_file());
+}
+
+#[test]
+#[cfg(unix)]
+fn post_create() {
+    let context = VenvTestContext::new(&["3.12"]);
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--post-create")
+        .arg("python -c 'import sys; open(\"marker\", \"w\").write(sys.prefix)'")
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.[X] interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    // The hook runs with the virtual environment's interpreter first on the `PATH`.
+    context
+        .temp_dir
+        .child("marker")
+        .assert(predicates::str::diff(
+            context.venv.to_string_lossy().to_string(),
+        ));
+}
+
+/// With `--post-create-requirements`, the requirements are installed into the virtual environment.
+#[test]
+#[cfg(unix)]
+fn post_create_requirements() -> Result<()> {
+    let context = VenvTestContext::new(&["3.12"]);
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+
+    context
+        .venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--post-create-requirements")
+        .arg("requirements.txt")
+        .arg("--python")
+        .arg("3.12")
+        .env("UV_CACHE_DIR", context.cache_dir.path())
+        .assert()
+        .success();
+
+    context
+        .venv
+        .child("lib")
+        .child("python3.12")
+        .child("site-packages")
+        .child("iniconfig")
+        .assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn minimal() {
+    let context = VenvTestContext::new(&["3.12"]);
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--minimal")
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.[X] interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    "###
+    );
+
+    context
+        .venv
+        .child("bin")
+        .child("python")
+        .assert(predicates::path::exists());
+    context
+        .venv
+        .child("bin")
+        .child("python3")
+        .assert(predicates::path::missing());
+    context
+        .venv
+        .child("bin")
+        .child("activate")
+        .assert(predicates::path::missing());
+    context
+        .venv
+        .child("pyvenv.cfg")
+        .assert(predicates::path::is_file());
+}
+
 #[test]
 fn seed_older_python_version() {
     let context = VenvTestContext::new(&["3.10"]);
@@ -543,6 +678,47 @@ fn virtualenv_compatibility() {
     context.venv.assert(predicates::path::is_dir());
 }
 
+/// With `--copies`, the interpreter is copied into the virtual environment rather than symlinked.
+#[test]
+#[cfg(unix)]
+fn copies() {
+    let context = VenvTestContext::new(&["3.12"]);
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--copies")
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.[X] interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    for name in ["python", "python3", "python3.12"] {
+        let executable = context.venv.child("bin").child(name);
+        executable.assert(predicates::path::is_file());
+        assert!(!fs_err::symlink_metadata(executable.path())
+            .unwrap()
+            .file_type()
+            .is_symlink());
+    }
+
+    // The copied interpreter runs from the virtual environment.
+    let output = Command::new(context.venv.child("bin").child("python").path())
+        .arg("-c")
+        .arg("import sys; print(sys.prefix != sys.base_prefix)")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "True");
+}
+
 #[test]
 fn verify_pyvenv_cfg() {
     let context = TestContext::new("3.12");
@@ -642,3 +818,38 @@ fn path_with_trailing_space_gives_proper_error() {
     "###
     );
 }
+
+/// Activation scripts must preserve environment paths with spaces, quotes, and non-ASCII
+/// characters verbatim.
+#[test]
+#[cfg(unix)]
+fn activate_adversarial_path() -> Result<()> {
+    let context = VenvTestContext::new(&["3.12"]);
+    let venv = context.temp_dir.child("it's a \"venv\" $HOME ü!");
+
+    context
+        .venv_command()
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .assert()
+        .success();
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(r#". "$1/bin/activate" && printf '%s\n%s' "$VIRTUAL_ENV" "$VIRTUAL_ENV_PROMPT""#)
+        .arg("sh")
+        .arg(venv.as_os_str())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let (virtual_env, prompt) = stdout.split_once('\n').unwrap();
+    assert_eq!(
+        fs_err::canonicalize(virtual_env)?,
+        fs_err::canonicalize(venv.path())?
+    );
+    assert_eq!(prompt, "it's a \"venv\" $HOME ü!");
+
+    Ok(())
+}